@@ -0,0 +1,211 @@
+//! Off-chain crank for the fair coin flipper program.
+//!
+//! Polls every Game account and calls `cancel_game` on games whose phase deadline
+//! has passed, so stalled games refund their players even if nobody comes back.
+//! Where the joiner asked for auto-pick and is the only one who hasn't committed,
+//! it calls `auto_pick_side` instead, which cancel_game would otherwise reject:
+//! once at the deadline to close the commit phase, and again as soon as player A
+//! has revealed to draw the joiner's side.
+//! The keypair must be a player of the game or the operator configured in
+//! GlobalState; both instructions reject anyone else.
+//!
+//! There is no VRF flow or crank bounty in the program today, so the crank only
+//! handles timeouts and pays its own transaction fees.
+
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use anchor_client::solana_sdk::system_program;
+use anchor_client::{Client, Cluster, Program};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use fair_coin_flipper::{
+    accounts, instruction, Game, GameStatus, GlobalState, FLAG_SETTLEMENT_HALTED, FLAG_VAULT_ESCROW,
+};
+
+#[derive(Parser)]
+#[command(about = "Cancel timed-out fair coin flipper games")]
+struct Args {
+    /// JSON-RPC endpoint; the websocket URL is derived from it
+    #[arg(long, env = "CRANK_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+
+    /// Keypair that signs cancellations (a player or the configured operator)
+    #[arg(long, env = "CRANK_KEYPAIR")]
+    keypair: String,
+
+    /// Seconds between polls
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+
+    /// Run one pass and exit instead of polling forever
+    #[arg(long)]
+    once: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let payer = read_keypair_file(&args.keypair)
+        .map_err(|e| anyhow!("failed to read keypair {}: {e}", args.keypair))?;
+    let payer = Rc::new(payer);
+
+    let ws_url = args.rpc_url.replacen("http", "ws", 1);
+    let client = Client::new_with_options(
+        Cluster::Custom(args.rpc_url.clone(), ws_url),
+        payer.clone(),
+        CommitmentConfig::confirmed(),
+    );
+    let program = client.program(fair_coin_flipper::ID)?;
+
+    loop {
+        match crank_once(&program, &payer) {
+            Ok((cancelled, picked)) => println!(
+                "pass complete: {cancelled} game(s) cancelled, {picked} side(s) auto-picked"
+            ),
+            Err(err) => eprintln!("pass failed: {err:#}"),
+        }
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(args.interval));
+    }
+}
+
+// One sweep over all games; returns how many were cancelled and auto-picked
+fn crank_once(program: &Program<Rc<Keypair>>, payer: &Keypair) -> Result<(usize, usize)> {
+    let (global_state_key, _) = Pubkey::find_program_address(&[b"global_state"], &program.id());
+    let global_state: GlobalState = program.account(global_state_key)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+    let mut cancelled = 0;
+    let mut picked = 0;
+    for (game_key, game) in program.accounts::<Game>(vec![])? {
+        // The draw doesn't wait for a deadline
+        // Player A may not draw B's side
+        let draw_due = game.auto_pick_draw_due()
+            && payer.pubkey() != game.player_a
+            && is_cranker(&game, &global_state, payer.pubkey());
+        if !draw_due && !is_eligible(&game, &global_state, payer.pubkey(), now) {
+            continue;
+        }
+        if draw_due || game.auto_pick_due() {
+            match auto_pick(program, payer, global_state_key, game_key) {
+                Ok(signature) => {
+                    println!("auto-picked in game {} ({game_key}): {signature}", game.game_id);
+                    picked += 1;
+                }
+                Err(err) => eprintln!(
+                    "failed to auto-pick in game {} ({game_key}): {err:#}",
+                    game.game_id
+                ),
+            }
+            continue;
+        }
+        match cancel(program, payer, global_state_key, game_key, &game) {
+            Ok(signature) => {
+                println!("cancelled game {} ({game_key}): {signature}", game.game_id);
+                cancelled += 1;
+            }
+            Err(err) => eprintln!("failed to cancel game {} ({game_key}): {err:#}", game.game_id),
+        }
+    }
+    Ok((cancelled, picked))
+}
+
+// Mirrors the checks cancel_game performs so the crank doesn't waste fees
+fn is_eligible(game: &Game, global_state: &GlobalState, signer: Pubkey, now: i64) -> bool {
+    let unsettled = !matches!(game.status(), GameStatus::Resolved | GameStatus::Cancelled);
+    unsettled
+        && !game.flag(FLAG_SETTLEMENT_HALTED)
+        && is_cranker(game, global_state, signer)
+        && now > game.phase_deadline
+}
+
+// Mirrors is_player_or_operator, which both cranks check
+fn is_cranker(game: &Game, global_state: &GlobalState, signer: Pubkey) -> bool {
+    signer == global_state.operator
+        || signer == game.player_a
+        || (signer == game.player_b && game.player_b != Pubkey::default())
+        || (signer == game.automation_thread && game.automation_thread != Pubkey::default())
+}
+
+fn auto_pick(
+    program: &Program<Rc<Keypair>>,
+    payer: &Keypair,
+    global_state: Pubkey,
+    game_key: Pubkey,
+) -> Result<String> {
+    let signature = program
+        .request()
+        .accounts(accounts::AutoPickSide {
+            cranker: payer.pubkey(),
+            game: game_key,
+            global_state,
+        })
+        .args(instruction::AutoPickSide {})
+        .send()?;
+    Ok(signature.to_string())
+}
+
+fn cancel(
+    program: &Program<Rc<Keypair>>,
+    payer: &Keypair,
+    global_state: Pubkey,
+    game_key: Pubkey,
+    game: &Game,
+) -> Result<String> {
+    let seeds_id = game.game_id.to_le_bytes();
+    // Vault-mode games keep their stakes in the shared escrow vault
+    let escrow = if game.flag(FLAG_VAULT_ESCROW) {
+        Pubkey::create_program_address(&[b"escrow_vault", &[game.escrow_bump]], &program.id())?
+    } else {
+        Pubkey::find_program_address(
+            &[b"escrow", game.creator.as_ref(), &seeds_id],
+            &program.id(),
+        )
+        .0
+    };
+    let (treasury, _) = Pubkey::find_program_address(&[b"treasury"], &program.id());
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &program.id());
+    let sponsor = |key: Pubkey| (key != Pubkey::default()).then_some(key);
+    // Both players' stats are required; cancel_game creates any a lite joiner is missing
+    let stats = |player: Pubkey| {
+        Pubkey::find_program_address(&[b"player_stats", player.as_ref()], &program.id()).0
+    };
+
+    // Before anyone joins, the player B slot accepts any writable account
+    let player_b = if game.player_b == Pubkey::default() {
+        game.player_a
+    } else {
+        game.player_b
+    };
+
+    let signature = program
+        .request()
+        .accounts(accounts::CancelGame {
+            canceller: payer.pubkey(),
+            game: game_key,
+            global_state,
+            player_a: game.player_a,
+            player_b,
+            player_a_stats: stats(game.player_a),
+            player_b_stats: (game.player_b != Pubkey::default()).then(|| stats(game.player_b)),
+            sponsor_a: sponsor(game.sponsor_a),
+            sponsor_b: sponsor(game.sponsor_b),
+            treasury,
+            creator: game.creator,
+            escrow,
+            instructions: None,
+            system_program: system_program::ID,
+            event_authority,
+            program: program.id(),
+        })
+        .args(instruction::CancelGame {})
+        .send()?;
+    Ok(signature.to_string())
+}
@@ -0,0 +1,172 @@
+//! Commitment, coin flip and winner logic for the fair coin flipper.
+//!
+//! The program and off-chain clients (bots, wasm builds for the browser) both
+//! depend on this crate, so a commitment computed by a client is byte-identical
+//! to the one the program checks at reveal. It is `no_std` and allocation-free.
+
+#![no_std]
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoinSide {
+    Heads,
+    Tails,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Player {
+    A,
+    B,
+}
+
+// Commitment to a choice: sha256(sha256(choice byte || 7 zero bytes || secret LE))
+pub fn commitment(choice: CoinSide, secret: u64) -> [u8; 32] {
+    let choice_byte = match choice {
+        CoinSide::Heads => 0u8,
+        CoinSide::Tails => 1u8,
+    };
+
+    let mut commitment_data = [0u8; 16];
+    commitment_data[0] = choice_byte; // Bytes 1..8 are padding
+    commitment_data[8..].copy_from_slice(&secret.to_le_bytes());
+
+    // Double hash for security. Clients already commit with this scheme, so unlike
+    // the flip below it stays as is
+    sha256(&sha256(&commitment_data))
+}
+
+// Coin flip from both secrets plus the slot and timestamp at resolution
+pub fn coin_flip(secret_a: u64, secret_b: u64, slot: u64, timestamp: i64) -> CoinSide {
+    coin_side(&flip_randomness(secret_a, secret_b, slot, timestamp))
+}
+
+// The hash the flip is read from. The program publishes it in GameResolved so
+// verifiers can check it against the revealed secrets and the resolution slot.
+pub fn flip_randomness(secret_a: u64, secret_b: u64, slot: u64, timestamp: i64) -> [u8; 32] {
+    // Use player secrets as primary entropy
+    let secret_entropy = secret_a.wrapping_mul(secret_b);
+
+    // Combine with blockchain entropy. One hash is enough: the inputs are fixed-size
+    // and revealed together, so a second round adds cost but no unpredictability
+    sha256v(&[
+        &secret_entropy.to_le_bytes(),
+        &slot.to_le_bytes(),
+        &(timestamp as u64).to_le_bytes(),
+    ])
+}
+
+// Heads when the low bit of the first 8 bytes (little-endian) is clear
+pub fn coin_side(randomness: &[u8; 32]) -> CoinSide {
+    if first_u64(randomness) & 1 == 0 {
+        CoinSide::Heads
+    } else {
+        CoinSide::Tails
+    }
+}
+
+// Commitment an operator publishes when scheduling a draw: sha256(secret LE)
+pub fn secret_commitment(secret: u64) -> [u8; 32] {
+    sha256(&secret.to_le_bytes())
+}
+
+// Randomness for an operator-scheduled draw: the operator's committed secret mixed
+// with the hash of the slot the draw was scheduled for. Neither the operator (who
+// can't know the slot hash when committing) nor that slot's leader (who doesn't
+// know the secret) controls the result alone.
+pub fn draw_randomness(secret: u64, slot_hash: &[u8; 32]) -> [u8; 32] {
+    sha256v(&[&secret.to_le_bytes(), slot_hash])
+}
+
+// Index in 0..count picked by a draw's randomness. The modulo bias is below
+// count / 2^64, negligible for any realistic number of entries.
+pub fn draw_index(randomness: &[u8; 32], count: u32) -> u32 {
+    (first_u64(randomness) % count as u64) as u32
+}
+
+// Bit `index` of a draw's randomness, counting from the low bit of the first byte.
+// Each match in a bracket round reads its own bit. Callers keep index below 256.
+pub fn draw_bit(randomness: &[u8; 32], index: usize) -> bool {
+    (randomness[index / 8] >> (index % 8)) & 1 == 1
+}
+
+// Whether a flip is golden: 1 in `odds`, read from the second 8 bytes of the
+// randomness so it is independent of the coin side. Odds of 0 turn it off.
+pub fn is_golden_flip(randomness: &[u8; 32], odds: u32) -> bool {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&randomness[8..16]);
+    odds != 0 && u64::from_le_bytes(bytes) % odds as u64 == 0
+}
+
+// Side and secret picked for a joiner who asked for auto-pick and let the commit
+// deadline pass, from the game and player keys and the slot the pick runs in. The
+// secret is kept within the range reveal_choice accepts.
+pub fn auto_pick(game: &[u8; 32], player: &[u8; 32], slot: u64) -> (CoinSide, u64) {
+    let hash = sha256v(&[b"auto_pick", game, player, &slot.to_le_bytes()]);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[8..16]);
+    let secret = u64::from_le_bytes(bytes).clamp(2, u64::MAX - 1);
+    (coin_side(&hash), secret)
+}
+
+// Whoever called the coin wins; ties (both or neither right) go to a hash tiebreaker
+pub fn winner(
+    choice_a: CoinSide,
+    choice_b: CoinSide,
+    coin_result: CoinSide,
+    secret_a: u64,
+    secret_b: u64,
+    slot: u64,
+) -> Player {
+    let a_correct = choice_a == coin_result;
+    let b_correct = choice_b == coin_result;
+
+    match (a_correct, b_correct) {
+        (true, false) => Player::A,
+        (false, true) => Player::B,
+        _ => {
+            let entropy_mix = secret_a.wrapping_mul(secret_b).wrapping_add(slot);
+            let hash_bytes = sha256v(&[&entropy_mix.to_le_bytes(), &slot.to_le_bytes()]);
+
+            if first_u64(&hash_bytes) & 1 == 0 {
+                Player::A
+            } else {
+                Player::B
+            }
+        }
+    }
+}
+
+fn first_u64(hash: &[u8; 32]) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    sha256v(&[data])
+}
+
+// Hash the concatenation of several slices without copying them into one buffer
+#[cfg(target_os = "solana")]
+fn sha256v(vals: &[&[u8]]) -> [u8; 32] {
+    extern "C" {
+        fn sol_sha256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64;
+    }
+
+    let mut result = [0u8; 32];
+    // SAFETY: vals is a valid slice of slices and result has room for the 32-byte digest
+    unsafe {
+        sol_sha256(vals.as_ptr() as *const u8, vals.len() as u64, result.as_mut_ptr());
+    }
+    result
+}
+
+#[cfg(not(target_os = "solana"))]
+fn sha256v(vals: &[&[u8]]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for val in vals {
+        hasher.update(val);
+    }
+    hasher.finalize().into()
+}
@@ -0,0 +1,197 @@
+//! CPI interface for the fair coin flipper program.
+//!
+//! Other programs (tournaments, guilds, ...) depend on this crate instead of the
+//! program itself. It re-exports the Anchor-generated CPI builders and account
+//! types, plus helpers that derive every PDA the game instructions expect.
+
+use anchor_lang::prelude::*;
+
+pub use fair_coin_flipper::cpi;
+pub use fair_coin_flipper::program::FairCoinFlipper;
+pub use fair_coin_flipper::{
+    decode_event, draw_commitment, Bracket, CoinSide, EscrowVault, Game, GameResolved, GameStatus,
+    GlobalState, Lottery, LotteryTicket, Market, MarketPosition, MatchQueue, Receipt,
+    ResolvedSummary, Room, Subscription, ADDRESS_LOOKUP_TABLE_PROGRAM_ID, EVENT_VERSION,
+    FLAG_VAULT_ESCROW, ID,
+};
+
+// PDA derivations, kept in sync with the seeds in the program's account constraints
+pub mod pda {
+    use super::*;
+
+    pub fn global_state() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"global_state"], &ID)
+    }
+
+    pub fn treasury() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"treasury"], &ID)
+    }
+
+    pub fn promo_vault() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"promo_vault"], &ID)
+    }
+
+    pub fn player_nonce(player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"player_nonce", player.as_ref()], &ID)
+    }
+
+    pub fn player_stats(player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"player_stats", player.as_ref()], &ID)
+    }
+
+    pub fn achievements(player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"achievements", player.as_ref()], &ID)
+    }
+
+    pub fn game_history(player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"game_history", player.as_ref()], &ID)
+    }
+
+    // Session key grant letting another signer commit and reveal for the player
+    pub fn delegate(player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"delegate", player.as_ref()], &ID)
+    }
+
+    // Games and escrows are keyed by the original creator, not the current owner
+    pub fn game(creator: &Pubkey, game_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"game", creator.as_ref(), &game_id.to_le_bytes()], &ID)
+    }
+
+    pub fn escrow(creator: &Pubkey, game_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"escrow", creator.as_ref(), &game_id.to_le_bytes()], &ID)
+    }
+
+    // Optional settlement receipt; stays after the game account is archived
+    pub fn receipt(creator: &Pubkey, game_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"receipt", creator.as_ref(), &game_id.to_le_bytes()], &ID)
+    }
+
+    pub fn market(market_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"market", &market_id.to_le_bytes()], &ID)
+    }
+
+    pub fn market_escrow(market_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"market_escrow", market(market_id).0.as_ref()], &ID)
+    }
+
+    pub fn market_position(market_id: u64, player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"market_position", market(market_id).0.as_ref(), player.as_ref()],
+            &ID,
+        )
+    }
+
+    pub fn lottery(lottery_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"lottery", &lottery_id.to_le_bytes()], &ID)
+    }
+
+    pub fn lottery_escrow(lottery_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"lottery_escrow", lottery(lottery_id).0.as_ref()], &ID)
+    }
+
+    pub fn lottery_ticket(lottery_id: u64, number: u32) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"lottery_ticket", lottery(lottery_id).0.as_ref(), &number.to_le_bytes()],
+            &ID,
+        )
+    }
+
+    pub fn room(creator: &Pubkey, room_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"room", creator.as_ref(), &room_id.to_le_bytes()], &ID)
+    }
+
+    pub fn room_escrow(creator: &Pubkey, room_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"room_escrow", room(creator, room_id).0.as_ref()], &ID)
+    }
+
+    pub fn match_queue(stake: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"match_queue", &stake.to_le_bytes()], &ID)
+    }
+
+    pub fn match_queue_escrow(stake: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"match_queue_escrow", match_queue(stake).0.as_ref()], &ID)
+    }
+
+    pub fn bracket(bracket_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"bracket", &bracket_id.to_le_bytes()], &ID)
+    }
+
+    pub fn bracket_escrow(bracket_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"bracket_escrow", bracket(bracket_id).0.as_ref()], &ID)
+    }
+
+    pub fn subscription(player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"subscription", player.as_ref()], &ID)
+    }
+
+    pub fn subscription_escrow(player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"subscription_escrow", subscription(player).0.as_ref()],
+            &ID,
+        )
+    }
+
+    // Shared escrow that replaces `escrow` for games created in vault mode
+    pub fn escrow_vault() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"escrow_vault"], &ID)
+    }
+
+    // Owner of an operator's address lookup table
+    pub fn lookup_authority(operator: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"lookup_authority", operator.as_ref()], &ID)
+    }
+
+    // Table created by create_lookup_table for this operator and recent slot;
+    // derived by the lookup table program, not this one
+    pub fn lookup_table(operator: &Pubkey, recent_slot: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[lookup_authority(operator).0.as_ref(), &recent_slot.to_le_bytes()],
+            &ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+        )
+    }
+}
+
+// Per-player accounts that create_game/join_game initialize on first use
+pub struct PlayerAccounts {
+    pub stats: Pubkey,
+    pub achievements: Pubkey,
+    pub history: Pubkey,
+}
+
+impl PlayerAccounts {
+    pub fn new(player: &Pubkey) -> Self {
+        Self {
+            stats: pda::player_stats(player).0,
+            achievements: pda::achievements(player).0,
+            history: pda::game_history(player).0,
+        }
+    }
+}
+
+// Addresses of a single game's accounts
+pub struct GameAccounts {
+    pub game: Pubkey,
+    pub escrow: Pubkey,
+}
+
+impl GameAccounts {
+    pub fn new(creator: &Pubkey, game_id: u64) -> Self {
+        Self {
+            game: pda::game(creator, game_id).0,
+            escrow: pda::escrow(creator, game_id).0,
+        }
+    }
+
+    // For an existing game, whose escrow depends on the mode it was created in
+    pub fn for_game(game: &Game) -> Self {
+        let escrow = if game.flag(FLAG_VAULT_ESCROW) {
+            pda::escrow_vault().0
+        } else {
+            pda::escrow(&game.creator, game.game_id).0
+        };
+        Self {
+            game: pda::game(&game.creator, game.game_id).0,
+            escrow,
+        }
+    }
+}
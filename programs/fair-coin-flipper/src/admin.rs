@@ -0,0 +1,78 @@
+use crate::*;
+
+// Bounds every configuration change must respect
+pub(crate) fn validate_config_change(change: &ConfigChange) -> Result<()> {
+    match *change {
+        ConfigChange::HouseFee { bps } => {
+            require!(bps <= MAX_HOUSE_FEE_BPS, GameError::InvalidConfigValue);
+        }
+        ConfigChange::CancellationFee { bps } => {
+            require!(
+                bps <= MAX_CANCELLATION_FEE_BPS,
+                GameError::InvalidConfigValue
+            );
+        }
+        ConfigChange::BetLimits { min_bet, max_bet } => {
+            require!(
+                min_bet > 0 && min_bet <= max_bet,
+                GameError::InvalidConfigValue
+            );
+        }
+        ConfigChange::TimelockDelay { seconds } => {
+            require!(
+                (MIN_TIMELOCK_DELAY_SECONDS..=MAX_TIMELOCK_DELAY_SECONDS).contains(&seconds),
+                GameError::InvalidConfigValue
+            );
+        }
+        ConfigChange::HouseWallet { wallet } => {
+            require!(wallet != Pubkey::default(), GameError::InvalidConfigValue);
+        }
+    }
+    Ok(())
+}
+
+// Append an admin operation to the audit log, overwriting the oldest entry when full
+pub(crate) fn log_admin_action(
+    admin_log: &mut AdminLog,
+    action: AdminLogAction,
+    actor: Pubkey,
+    target: Pubkey,
+    value: u64,
+    timestamp: i64,
+) {
+    admin_log.entries[admin_log.head as usize] = AdminLogEntry {
+        action,
+        actor,
+        target,
+        value,
+        timestamp,
+    };
+    admin_log.head = ((admin_log.head as usize + 1) % ADMIN_LOG_LENGTH) as u8;
+    if (admin_log.count as usize) < ADMIN_LOG_LENGTH {
+        admin_log.count += 1;
+    }
+    admin_log.total_actions = admin_log.total_actions.saturating_add(1);
+}
+
+// Pubkey/number pair recorded in the audit log for a config change
+pub(crate) fn config_change_summary(change: &ConfigChange) -> (Pubkey, u64) {
+    match *change {
+        ConfigChange::HouseFee { bps } => (Pubkey::default(), bps),
+        ConfigChange::CancellationFee { bps } => (Pubkey::default(), bps),
+        ConfigChange::BetLimits { max_bet, .. } => (Pubkey::default(), max_bet),
+        ConfigChange::TimelockDelay { seconds } => (Pubkey::default(), seconds as u64),
+        ConfigChange::HouseWallet { wallet } => (wallet, 0),
+    }
+}
+
+// Clear the queued change and return it
+pub(crate) fn take_pending_change(global_state: &mut GlobalState) -> Result<PendingConfigChange> {
+    require!(
+        global_state.pending_change.active,
+        GameError::NoPendingConfigChange
+    );
+    Ok(std::mem::replace(
+        &mut global_state.pending_change,
+        PendingConfigChange::NONE,
+    ))
+}
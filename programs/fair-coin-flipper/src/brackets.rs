@@ -0,0 +1,56 @@
+use crate::*;
+
+// Largest bracket a queue of `len` players can seed: a power of two, at least two
+pub fn bracket_size(len: usize) -> Option<usize> {
+    (len >= 2).then(|| 1 << (usize::BITS - 1 - len.leading_zeros()))
+}
+
+// Winners of one round. Match m pairs alive[2m] with alive[2m + 1], and the second
+// player wins when bit m of the round's randomness is set.
+pub fn bracket_round_winners(alive: &[Pubkey], randomness: &[u8; 32]) -> Vec<Pubkey> {
+    alive
+        .chunks_exact(2)
+        .enumerate()
+        .map(|(m, pair)| {
+            if draw_bit(randomness, m) {
+                pair[1]
+            } else {
+                pair[0]
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn void_running_bracket(bracket: &mut Bracket, voided_by: Pubkey) {
+    bracket.status = BracketStatus::Voided;
+    emit!(BracketVoided {
+        event_version: EVENT_VERSION,
+        bracket_id: bracket.bracket_id,
+        voided_by,
+        round: bracket.round,
+    });
+}
+
+// Once the pot or every refund has been claimed, return the bracket escrow's
+// reserve to the operator who funded it
+pub(crate) fn close_bracket_escrow_if_done<'info>(
+    bracket: &Account<'info, Bracket>,
+    escrow: &AccountInfo<'info>,
+    operator: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if !bracket.is_fully_claimed() {
+        return Ok(());
+    }
+    let bracket_key = bracket.key();
+    close_escrow(
+        escrow,
+        operator,
+        system_program,
+        &[&[
+            b"bracket_escrow",
+            bracket_key.as_ref(),
+            &[bracket.escrow_bump],
+        ]],
+    )
+}
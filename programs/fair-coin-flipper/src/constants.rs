@@ -0,0 +1,125 @@
+use crate::*;
+
+// Constants - Updated Economics. Those marked #[constant] are exported in the IDL
+#[constant]
+pub const HOUSE_FEE_PERCENTAGE: u64 = 700; // 7% = 700 basis points (increased for sustainability)
+#[constant]
+pub const CANCELLATION_FEE_PERCENTAGE: u64 = 200; // 2% = 200 basis points (covers refund costs)
+#[constant]
+pub const REFERRAL_FEE_SHARE_BPS: u64 = 1000; // Referrers earn 10% of the fee on their player's stake
+pub(crate) const REFERRAL_LEADERBOARD_LENGTH: usize = 10;
+
+// Bounds for timelocked configuration changes (defaults are per cluster, below)
+#[constant]
+pub const MAX_HOUSE_FEE_BPS: u64 = 1000; // Fee can never exceed 10%
+#[constant]
+pub const MAX_CANCELLATION_FEE_BPS: u64 = 500; // Cancellation fee can never exceed 5%
+pub(crate) const MAX_TIMELOCK_DELAY_SECONDS: i64 = 2_592_000; // 30 days
+pub(crate) const MIN_CANCEL_TIMEOUT_SECONDS: i64 = 60;
+pub(crate) const MAX_CANCEL_TIMEOUT_SECONDS: i64 = 604_800; // 7 days
+pub(crate) const MAX_GAME_COOLDOWN_SECONDS: i64 = 86_400; // 1 day
+#[constant]
+pub const MAX_GOLDEN_FLIP_BONUS_BPS: u64 = 10_000; // A golden flip can at most double the pot
+pub(crate) const MAX_SELF_FREEZE_SECONDS: i64 = 2_592_000; // 30 days per freeze_self call
+pub(crate) const MAX_DRAW_SCHEDULE_SLOTS: u64 = 1_512_000; // Markets and lotteries draw within about 7 days
+pub const DRAW_WINDOW_SLOTS: u64 = 400; // SlotHashes keeps the last 512 slots
+pub(crate) const MAX_LOTTERY_ENTRIES: u32 = 10_000;
+pub(crate) const MAX_ROOM_QUEUE: usize = 8; // Challengers waiting in a king-of-the-hill room
+pub const ROOM_BOUT_DELAY_SLOTS: u64 = 4; // A bout draws from a slot after its challenger reaches the head
+pub(crate) const MAX_BRACKET_PLAYERS: usize = 16; // Also the match queue's length; a power of two
+pub const BRACKET_ROUND_DELAY_SLOTS: u64 = 4; // Each round draws from a slot after the previous one is drawn
+pub(crate) const CURRENT_GLOBAL_STATE_VERSION: u8 = 16; // v1 accounts predate the version field
+
+// First field of every event. Bump it when fields are appended to any event; fields
+// are never removed or reordered, so older decoders keep reading the prefix they know.
+#[constant]
+pub const EVENT_VERSION: u8 = 6; // v6: join_locked_until on GameListed
+
+pub(crate) const EMERGENCY_RECOVERY_MIN_AGE_SECONDS: i64 = 604_800; // Only games older than 7 days
+pub(crate) const MAX_ALLOWED_JOINERS: usize = 4; // Challenged/allowlisted opponents per game
+pub(crate) const MAX_ALLOWED_CPI_CALLERS: usize = 4; // Programs that may compose fund-moving instructions
+pub(crate) const MAX_JOIN_LOCK_SECONDS: i64 = 300; // Anti-snipe lock can last at most 5 minutes
+pub(crate) const INITIAL_RATING: u32 = 1200; // Starting Elo rating for new players
+pub(crate) const MIN_RATING: u32 = 100; // Ratings never drop below this floor
+pub(crate) const SECONDS_PER_DAY: i64 = 86_400;
+pub(crate) const MIN_USERNAME_LENGTH: usize = 3;
+pub(crate) const MAX_USERNAME_LENGTH: usize = 16;
+pub(crate) const GAME_HISTORY_LENGTH: usize = 32; // Recent games kept per player
+pub(crate) const RECENT_GAMES_LENGTH: usize = 32; // Games shown in the global live feed
+pub(crate) const ADMIN_LOG_LENGTH: usize = 64; // Admin actions kept in the on-chain audit log
+pub(crate) const MAX_DELEGATE_SECONDS: i64 = 86_400; // Session keys last at most a day
+pub(crate) const MAX_BATCH_GAMES: usize = 8; // Games per create_games_batch call
+#[constant]
+pub const MILESTONE_GAMES_INTERVAL: u128 = 1_000; // A Milestone event every 1,000 resolved games
+#[constant]
+pub const MILESTONE_VOLUME_INTERVAL: u128 = 100_000_000_000; // And every 100 SOL of settled volume
+pub(crate) const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+pub(crate) const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub(crate) const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: Pubkey =
+    pubkey!("AddressLookupTab1e1111111111111111111111111");
+// sha256("global:on_game_resolved")[..8], precomputed to keep hashing out of settlement
+pub(crate) const ON_GAME_RESOLVED_DISCRIMINATOR: [u8; 8] = [51, 116, 227, 132, 93, 148, 161, 22];
+pub(crate) const LOOKUP_TABLE_META_SIZE: usize = 56; // Table header before the address list
+
+// Cluster defaults. Mainnet values are used unless the program is built with
+// `--features devnet`, which allows tiny bets and short waits for testing.
+#[cfg(all(feature = "devnet", feature = "mainnet"))]
+compile_error!("the `devnet` and `mainnet` features are mutually exclusive");
+
+#[cfg(not(feature = "devnet"))]
+mod cluster {
+    use anchor_lang::prelude::constant;
+
+    #[constant]
+    pub const MIN_BET_AMOUNT: u64 = 10_000_000; // 0.01 SOL minimum (increased from 0.001)
+    #[constant]
+    pub const MAX_BET_AMOUNT: u64 = 100_000_000_000; // 100 SOL maximum
+    pub const DEFAULT_TIMELOCK_DELAY_SECONDS: i64 = 172_800; // 2 days
+    pub const MIN_TIMELOCK_DELAY_SECONDS: i64 = 3_600; // 1 hour
+    pub const DEFAULT_CANCEL_TIMEOUT_SECONDS: i64 = 3_600; // Games can be cancelled after 1 hour
+}
+
+#[cfg(feature = "devnet")]
+mod cluster {
+    use anchor_lang::prelude::constant;
+
+    #[constant]
+    pub const MIN_BET_AMOUNT: u64 = 1_000_000; // 0.001 SOL minimum
+    #[constant]
+    pub const MAX_BET_AMOUNT: u64 = 1_000_000_000; // 1 SOL maximum
+    pub const DEFAULT_TIMELOCK_DELAY_SECONDS: i64 = 60; // 1 minute
+    pub const MIN_TIMELOCK_DELAY_SECONDS: i64 = 60; // 1 minute
+    pub const DEFAULT_CANCEL_TIMEOUT_SECONDS: i64 = 120; // Games can be cancelled after 2 minutes
+}
+
+pub use cluster::*;
+
+// Game tags (bitflags) for lobby filtering
+pub const TAG_CASUAL: u8 = 1 << 0;
+pub const TAG_HIGH_STAKES: u8 = 1 << 1;
+pub const TAG_STREAMER: u8 = 1 << 2;
+pub const TAG_TOURNAMENT: u8 = 1 << 3;
+pub(crate) const ALL_TAGS: u8 = TAG_CASUAL | TAG_HIGH_STAKES | TAG_STREAMER | TAG_TOURNAMENT;
+
+// Game.flags bits; the GameStatus code lives in the top bits (see docs/account-layout.md)
+pub const FLAG_COMMITMENTS_COMPLETE: u8 = 1 << 0;
+pub const FLAG_SETTLEMENT_HALTED: u8 = 1 << 1; // Set when the pre-settlement escrow audit fails
+pub const FLAG_REPAY_SPONSOR_A: u8 = 1 << 2;
+pub const FLAG_REPAY_SPONSOR_B: u8 = 1 << 3;
+pub const FLAG_VAULT_ESCROW: u8 = 1 << 7; // Stakes sit in the shared escrow vault, not a per-game escrow
+pub(crate) const STATUS_SHIFT: u8 = 4;
+pub(crate) const STATUS_MASK: u8 = 0b0111 << STATUS_SHIFT;
+
+// Game.sides holds three 2-bit CoinSide codes (0 = not set) and the auto-pick bit
+pub(crate) const CHOICE_A_SHIFT: u8 = 0;
+pub(crate) const CHOICE_B_SHIFT: u8 = 2;
+pub(crate) const COIN_RESULT_SHIFT: u8 = 4;
+pub const SIDES_AUTO_PICK_B: u8 = 1 << 6; // Pick player B's side if they miss the commit deadline
+
+// Achievement bits stored in the Achievements bitset
+pub const ACHIEVEMENT_FIRST_WIN: u64 = 1 << 0;
+pub const ACHIEVEMENT_100_GAMES: u64 = 1 << 1;
+pub const ACHIEVEMENT_10_WIN_STREAK: u64 = 1 << 2;
+pub const ACHIEVEMENT_1_SOL_WIN: u64 = 1 << 3;
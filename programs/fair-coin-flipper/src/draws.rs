@@ -0,0 +1,72 @@
+use crate::*;
+
+// Operator-scheduled draws, shared by markets, lotteries, rooms and brackets.
+// Entries close at the cutoff slot and the result comes from the draw slot's hash.
+// Lotteries, rooms and brackets mix in a secret committed when scheduling; markets
+// use the slot hash alone. Stakes sit in a system-owned escrow PDA whose rent
+// reserve the operator funds and gets back.
+
+pub(crate) fn verify_draw_schedule(slot: u64, cutoff_slot: u64, draw_slot: u64) -> Result<()> {
+    require!(
+        slot < cutoff_slot
+            && cutoff_slot < draw_slot
+            && draw_slot <= slot.saturating_add(MAX_DRAW_SCHEDULE_SLOTS),
+        GameError::InvalidDrawSchedule
+    );
+    Ok(())
+}
+
+// Whether the draw slot's hash can still be read: the slot has passed and is
+// within the window SlotHashes keeps
+pub fn is_drawable(draw_slot: u64, slot: u64) -> bool {
+    slot > draw_slot && slot <= draw_slot.saturating_add(DRAW_WINDOW_SLOTS)
+}
+
+// A draw that wasn't made before its window closed can be voided by anyone
+pub fn is_past_draw_window(draw_slot: u64, slot: u64) -> bool {
+    slot > draw_slot.saturating_add(DRAW_WINDOW_SLOTS)
+}
+
+// Check the revealed secret and return the draw slot's hash and the randomness
+pub(crate) fn reveal_draw(
+    secret_commitment: &[u8; 32],
+    secret: u64,
+    draw_slot: u64,
+    slot_hashes: &AccountInfo,
+) -> Result<([u8; 32], [u8; 32])> {
+    require!(
+        draw_commitment(secret) == *secret_commitment,
+        GameError::InvalidDrawSecret
+    );
+    let slot_hash = drawn_slot_hash(draw_slot, slot_hashes)?;
+    Ok((slot_hash, draw_randomness(secret, &slot_hash)))
+}
+
+// The draw slot's hash, once it can be read
+pub(crate) fn drawn_slot_hash(draw_slot: u64, slot_hashes: &AccountInfo) -> Result<[u8; 32]> {
+    require!(
+        is_drawable(draw_slot, Clock::get()?.slot),
+        GameError::NotDrawable
+    );
+    slot_hash_at(&slot_hashes.try_borrow_data()?, draw_slot)
+        .ok_or_else(|| error!(GameError::SlotHashUnavailable))
+}
+
+// The operator covers a draw escrow's rent-exempt reserve, as the rent payer does
+// for a game escrow
+pub(crate) fn fund_draw_escrow<'info>(
+    operator: &Signer<'info>,
+    escrow: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: operator.to_account_info(),
+                to: escrow.clone(),
+            },
+        ),
+        Rent::get()?.minimum_balance(0),
+    )
+}
@@ -0,0 +1,198 @@
+use crate::*;
+
+// Error Codes
+#[error_code]
+pub enum GameError {
+    #[msg("Bet amount is too low")]
+    BetTooLow,
+    #[msg("Bet amount is too high")]
+    BetTooHigh,
+    #[msg("Invalid game status for this operation")]
+    InvalidGameStatus,
+    #[msg("Player is not part of this game")]
+    NotAPlayer,
+    #[msg("Invalid commitment provided")]
+    InvalidCommitment,
+    #[msg("Choice already revealed")]
+    AlreadyRevealed,
+    #[msg("Player has already made a commitment")]
+    AlreadyCommitted,
+    #[msg("Secret value is too weak, use a strong random value")]
+    WeakSecret,
+    #[msg("Game is not ready for resolution")]
+    NotReadyForResolution,
+    #[msg("Game is already resolved")]
+    AlreadyResolved,
+    #[msg("Too early to cancel the game")]
+    TooEarlyToCancel,
+    #[msg("Cannot play against yourself")]
+    CannotPlayAgainstYourself,
+    #[msg("Game ID does not match the player's next nonce")]
+    InvalidGameId,
+    #[msg("Too many allowed joiners")]
+    TooManyAllowedJoiners,
+    #[msg("Invalid join lock configuration")]
+    InvalidJoinLock,
+    #[msg("Game is locked to allowlisted players")]
+    JoinLocked,
+    #[msg("Unknown game tag bits")]
+    InvalidTags,
+    #[msg("Only the game owner can do this")]
+    NotGameOwner,
+    #[msg("Invalid new owner")]
+    InvalidNewOwner,
+    #[msg("Signer is not the pending owner")]
+    NotPendingOwner,
+    #[msg("Signer is not the program authority")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Username must be 3-16 lowercase letters, digits, or underscores")]
+    InvalidUsername,
+    #[msg("Sponsor account must be provided for a sponsored stake")]
+    MissingSponsorAccount,
+    #[msg("Referrer account must be provided for a referred player")]
+    MissingReferrerAccount,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("A configuration change is already pending")]
+    ConfigChangePending,
+    #[msg("No configuration change is pending")]
+    NoPendingConfigChange,
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Configuration value is out of bounds")]
+    InvalidConfigValue,
+    #[msg("Game is too recent for emergency recovery")]
+    TooEarlyForRecovery,
+    #[msg("Only the players or the operator can do this")]
+    NotPlayerOrOperator,
+    #[msg("Account is not a GlobalState")]
+    InvalidGlobalState,
+    #[msg("GlobalState is already at the current version")]
+    AlreadyMigrated,
+    #[msg("Treasury balance is insufficient for this withdrawal")]
+    InsufficientTreasuryBalance,
+    #[msg("New games are paused; existing games can still be settled")]
+    NewGamesPaused,
+    #[msg("An attestation account is required to play")]
+    AttestationRequired,
+    #[msg("Attestation account is not valid for this player")]
+    InvalidAttestation,
+    #[msg("Escrow cannot cover this payout and its rent-exempt reserve")]
+    InsufficientEscrowBalance,
+    #[msg("Game has already been settled")]
+    AlreadySettled,
+    #[msg("Game has timed out and can only be cancelled")]
+    GameExpired,
+    #[msg("Instructions sysvar is required while the CPI guard is enabled")]
+    MissingInstructionsSysvar,
+    #[msg("Instruction was invoked by a program that is not allowlisted")]
+    UntrustedCaller,
+    #[msg("Too many allowlisted CPI callers")]
+    TooManyAllowedCallers,
+    #[msg("Joining requires a matchmaker ticket in the preceding instruction")]
+    MissingMatchTicket,
+    #[msg("Match ticket is not valid for this game and player")]
+    InvalidMatchTicket,
+    #[msg("Match ticket has expired")]
+    MatchTicketExpired,
+    #[msg("Settlement is halted after an escrow discrepancy; use emergency recovery")]
+    SettlementHalted,
+    #[msg("No archive tree has been configured")]
+    ArchiveNotConfigured,
+    #[msg("Hook program and hook authority accounts are required while a hook is configured")]
+    MissingHookProgram,
+    #[msg("Lookup table account does not match the operator's table")]
+    InvalidLookupTable,
+    #[msg("Session key must be a new key and expire within a day")]
+    InvalidDelegateTerms,
+    #[msg("Signer is not this player's session key")]
+    InvalidDelegate,
+    #[msg("Session key has expired")]
+    DelegateExpired,
+    #[msg("Game stake exceeds the session key's limit")]
+    DelegateStakeExceeded,
+    #[msg("Relayed commitments need the player's ed25519 signature in the preceding instruction")]
+    MissingPlayerSignature,
+    #[msg("Player signature is not valid for this game and commitment")]
+    InvalidPlayerSignature,
+    #[msg("Player signature has expired")]
+    PlayerSignatureExpired,
+    #[msg("A batch must create between one and eight games")]
+    InvalidBatchSize,
+    #[msg("Batch accounts must be a game and escrow pair for each id, in order")]
+    InvalidBatchAccounts,
+    #[msg("Escrow account is neither the game's escrow nor the shared escrow vault")]
+    InvalidEscrowAccount,
+    #[msg("Player is still in the cooldown after their last game")]
+    CooldownActive,
+    #[msg("A game cooldown is configured; join with join_game instead")]
+    CooldownNeedsStats,
+    #[msg("The current terms of service must be accepted first")]
+    TermsNotAccepted,
+    #[msg("Only the current terms version can be accepted")]
+    InvalidTermsVersion,
+    #[msg("Player has reached their loss limit for this season")]
+    LossLimitReached,
+    #[msg("A loss limit can only be raised or removed before playing in a season")]
+    LossLimitLocked,
+    #[msg("Player has frozen their account")]
+    AccountFrozen,
+    #[msg("A freeze must end in the future, within 30 days, and not shorten an active freeze")]
+    InvalidFreezePeriod,
+    #[msg("A receipt can only be written by the reveal that resolves the game")]
+    ReceiptBeforeResolution,
+    #[msg("Cutoff must be in the future and before the draw slot, at most about 7 days out")]
+    InvalidDrawSchedule,
+    #[msg("Market no longer takes stakes")]
+    MarketClosed,
+    #[msg("Can only be drawn within the window after the draw slot")]
+    NotDrawable,
+    #[msg("Secret doesn't match the operator's commitment")]
+    InvalidDrawSecret,
+    #[msg("The draw slot's hash isn't in SlotHashes")]
+    SlotHashUnavailable,
+    #[msg("Not drawn or voided yet")]
+    NotYetDrawn,
+    #[msg("Can only be voided once the draw window has closed")]
+    NotVoidable,
+    #[msg("Ticket price or max entries out of range")]
+    InvalidLotteryConfig,
+    #[msg("Lottery has closed or sold out")]
+    LotteryClosed,
+    #[msg("Room queue is full")]
+    RoomQueueFull,
+    #[msg("Already the champion or queued in this room")]
+    AlreadyInRoom,
+    #[msg("No challenger is waiting in this room")]
+    NoBoutPending,
+    #[msg("Bout can still be drawn by the champion")]
+    BoutStillDrawable,
+    #[msg("Room still has queued challengers")]
+    RoomNotEmpty,
+    #[msg("Match queue is full")]
+    MatchQueueFull,
+    #[msg("Player is already in the match queue")]
+    AlreadyInMatchQueue,
+    #[msg("Player is not in the match queue")]
+    NotInMatchQueue,
+    #[msg("A bracket needs at least two queued players")]
+    BracketTooSmall,
+    #[msg("Promo vault must be provided while golden flips are on")]
+    MissingPromoVault,
+    #[msg("No auto-pick is due for this game")]
+    NoAutoPickDue,
+    #[msg("Game is waiting for its auto-pick, not a refund")]
+    AutoPickPending,
+    #[msg("Subscription needs a game count, a budget of at least one stake and another opponent")]
+    InvalidSubscription,
+    #[msg("Game doesn't match the subscription's stake or opponent")]
+    SubscriptionMismatch,
+    #[msg("Subscription has joined its last game or can't cover another stake")]
+    SubscriptionExhausted,
+    #[msg("Player A can't draw the auto-picked side")]
+    AutoPickByPlayerA,
+    #[msg("Player B's stats account is required once they've joined")]
+    MissingPlayerStats,
+}
@@ -0,0 +1,766 @@
+use crate::*;
+
+// Events. Every event starts with `event_version` (EVENT_VERSION at emit time) and
+// only ever grows by appending fields; see docs/events.md. Events about a single
+// game end with that game's `seq` after the instruction that emitted them.
+#[event]
+pub struct GameCreated {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub player_a: Pubkey,
+    pub bet_amount: u64,
+    pub join_locked_until: i64,
+    pub tags: u8,
+    pub seq: u64,
+}
+
+#[event]
+pub struct PlayerJoined {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub player_b: Pubkey,
+    pub tags: u8,
+    pub seq: u64,
+}
+
+#[event]
+pub struct OwnershipOffered {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct OwnershipTransferred {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct CommitmentMade {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub commitment: [u8; 32],
+    pub seq: u64,
+}
+
+#[event]
+pub struct ChoiceRevealed {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub choice: CoinSide,
+    pub secret: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AutoPickScheduled {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub player: Pubkey, // Player B, whose side is drawn once player A reveals
+    pub reveal_deadline: i64,
+    pub scheduled_by: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SideAutoPicked {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub choice: CoinSide,
+    pub secret: u64, // Revealed with the pick; the commitment is stored to match
+    pub picked_by: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct GameResolved {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub winner: Pubkey,
+    pub coin_result: CoinSide,
+    pub winner_payout: u64,
+    pub house_fee: u64,
+    pub sponsor_repayment: u64,
+    pub referral_fees: u64,
+    pub resolved_at: i64,
+    pub winner_rating: u32,
+    pub loser_rating: u32,
+    pub rating_delta: u32,
+    // v2: flip inputs, so the result can be re-derived from this event alone.
+    // coin_result comes from `randomness`; resolution_slot and resolved_at are the
+    // slot and timestamp it hashes, along with both secrets.
+    pub resolution_slot: u64,
+    pub secret_a: u64,
+    pub secret_b: u64,
+    pub randomness: [u8; 32],
+    pub seq: u64,
+    pub resolved_by: Pubkey,
+    // v5: the flip is golden when is_golden_flip(randomness, golden_flip_odds); the
+    // bonus is what the promo vault paid the winner on top of winner_payout
+    pub golden_flip_odds: u32,
+    pub golden_flip_bonus: u64,
+}
+
+#[event]
+pub struct GameCompacted {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub size: u64,          // Account size after the realloc, discriminator included
+    pub rent_refunded: u64, // Split between the two players
+    pub seq: u64,
+}
+
+#[event]
+pub struct GameCancelled {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub cancelled_by: Pubkey,
+    pub status_before: GameStatus,
+    pub refund_per_player: u64,
+    pub cancelled_at: i64,
+    pub total_fees_collected: u64,
+    pub seq: u64,
+}
+
+// Emitted with GameCancelled when a crank (the operator or the game's automation
+// thread) rather than a player cancels a game past its phase deadline
+#[event]
+pub struct TimeoutHandled {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub status: GameStatus, // Phase that timed out
+    pub phase_deadline: i64,
+    pub handled_by: Pubkey,
+    pub handled_at: i64,
+    pub seq: u64,
+}
+
+// A cancelled game's account was archived and closed
+#[event]
+pub struct GameExpiredCleaned {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub cleaned_by: Pubkey,
+    pub rent_returned: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SeasonStarted {
+    pub event_version: u8,
+    pub season_id: u32,
+    pub previous_season_id: u32,
+    pub started_at: i64,
+}
+
+#[event]
+pub struct AchievementUnlocked {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub achievement: u64,
+    pub game_id: u64,
+    pub unlocked_at: i64,
+}
+
+#[event]
+pub struct NewRecord {
+    pub event_version: u8,
+    pub kind: RecordKind,
+    pub holder: Pubkey,
+    pub value: u64,
+    pub previous_holder: Pubkey,
+    pub previous_value: u64,
+    pub game_id: u64,
+}
+
+#[event]
+pub struct Milestone {
+    pub event_version: u8,
+    pub kind: MilestoneKind,
+    pub threshold: u128, // Highest multiple of the interval this game crossed
+    pub game_id: u64,
+}
+
+#[event]
+pub struct UsernameRegistered {
+    pub event_version: u8,
+    pub owner: Pubkey,
+    pub username: String,
+}
+
+#[event]
+pub struct ReferralFeesClaimed {
+    pub event_version: u8,
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub event_version: u8,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub event_version: u8,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct ConfigChangeProposed {
+    pub event_version: u8,
+    pub change: ConfigChange,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub event_version: u8,
+    pub change: ConfigChange,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub event_version: u8,
+    pub change: ConfigChange,
+}
+
+#[event]
+pub struct FeeUpdated {
+    pub event_version: u8,
+    pub previous_house_fee_bps: u64,
+    pub house_fee_bps: u64,
+    pub previous_cancellation_fee_bps: u64,
+    pub cancellation_fee_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct HouseWalletUpdated {
+    pub event_version: u8,
+    pub previous_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct EmergencyRecovery {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub authority: Pubkey,
+    pub status_before: GameStatus,
+    pub escrow_balance: u64,
+    pub refunded_a: u64,
+    pub refunded_b: u64,
+    pub recovered_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct CancelTimeoutUpdated {
+    pub event_version: u8,
+    pub previous_seconds: i64,
+    pub new_seconds: i64,
+}
+
+#[event]
+pub struct GameCooldownUpdated {
+    pub event_version: u8,
+    pub previous_seconds: i64,
+    pub new_seconds: i64,
+}
+
+#[event]
+pub struct TermsVersionUpdated {
+    pub event_version: u8,
+    pub previous_version: u32,
+    pub new_version: u32,
+}
+
+#[event]
+pub struct GoldenFlipUpdated {
+    pub event_version: u8,
+    pub previous_odds: u32,
+    pub new_odds: u32,
+    pub previous_bonus_bps: u64,
+    pub new_bonus_bps: u64,
+}
+
+#[event]
+pub struct PromoVaultFunded {
+    pub event_version: u8,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct Subscribed {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub stake: u64,
+    pub max_games: u32,
+    pub opponent: Pubkey,
+    pub budget: u64,
+}
+
+#[event]
+pub struct SubscriptionJoined {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub game_id: u64,
+    pub games_joined: u32,
+    pub budget_left: u64, // Above the escrow's reserve, after this stake
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub games_joined: u32,
+    pub refunded: u64, // Budget left plus the escrow's reserve
+}
+
+#[event]
+pub struct TermsAccepted {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub version: u32,
+    pub accepted_at: i64,
+}
+
+#[event]
+pub struct LossLimitUpdated {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub season_loss_limit: u64,
+}
+
+#[event]
+pub struct PlayerFrozen {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub frozen_until: i64,
+}
+
+#[event]
+pub struct OperatorUpdated {
+    pub event_version: u8,
+    pub previous_operator: Pubkey,
+    pub new_operator: Pubkey,
+}
+
+#[event]
+pub struct GlobalStateMigrated {
+    pub event_version: u8,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub new_len: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub event_version: u8,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct PauseModeUpdated {
+    pub event_version: u8,
+    pub previous_mode: PauseMode,
+    pub new_mode: PauseMode,
+}
+
+// Paused and Unpaused mark switches into and out of PauseMode::Active, alongside
+// PauseModeUpdated
+#[event]
+pub struct Paused {
+    pub event_version: u8,
+    pub mode: PauseMode,
+    pub paused_by: Pubkey,
+    pub paused_at: i64,
+}
+
+#[event]
+pub struct Unpaused {
+    pub event_version: u8,
+    pub unpaused_by: Pubkey,
+    pub unpaused_at: i64,
+}
+
+#[event]
+pub struct AttestationIssuerUpdated {
+    pub event_version: u8,
+    pub previous_issuer: Pubkey,
+    pub new_issuer: Pubkey,
+}
+
+#[event]
+pub struct CpiGuardUpdated {
+    pub event_version: u8,
+    pub enabled: bool,
+    pub allowed_callers: Vec<Pubkey>,
+}
+
+#[event]
+pub struct MatchmakerUpdated {
+    pub event_version: u8,
+    pub previous_matchmaker: Pubkey,
+    pub new_matchmaker: Pubkey,
+}
+
+#[event]
+pub struct EscrowDiscrepancy {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub status: GameStatus,
+    pub expected: u64,
+    pub actual: u64,
+    pub detected_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AutomationThreadRegistered {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub thread: Pubkey,
+    pub phase_deadline: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct HookProgramUpdated {
+    pub event_version: u8,
+    pub previous_hook_program: Pubkey,
+    pub new_hook_program: Pubkey,
+}
+
+#[event]
+pub struct EscrowModeUpdated {
+    pub event_version: u8,
+    pub vault_escrow: bool,
+    pub vault: Pubkey,
+    pub liabilities: u64,
+}
+
+#[event]
+pub struct LookupTableUpdated {
+    pub event_version: u8,
+    pub operator: Pubkey,
+    pub lookup_table: Pubkey,
+    pub added: u8,
+}
+
+#[event]
+pub struct ArchiveTreeUpdated {
+    pub event_version: u8,
+    pub previous_tree: Pubkey,
+    pub new_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+#[event]
+pub struct GameArchived {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub tree: Pubkey,
+    pub leaf_index: u64,
+    pub leaf: [u8; 32],
+    pub seq: u64,
+}
+
+// Lobby feed, separate from the game and settlement events: a game opened
+#[event]
+pub struct GameListed {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub creator: Pubkey, // With game_id, derives the game PDA
+    pub bet_amount: u64,
+    pub tags: u8,
+    pub listed_at: i64,
+    pub seq: u64,
+    pub join_locked_until: i64, // v6; only the allowlist can join before this, anyone after
+}
+
+// Lobby feed: a listed game can no longer be joined
+#[event]
+pub struct GameDelisted {
+    pub event_version: u8,
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub reason: DelistReason,
+    pub delisted_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct MarketScheduled {
+    pub event_version: u8,
+    pub market_id: u64,
+    pub operator: Pubkey,
+    pub cutoff_slot: u64,
+    pub draw_slot: u64,
+    pub house_fee_bps: u64,
+}
+
+#[event]
+pub struct MarketBacked {
+    pub event_version: u8,
+    pub market_id: u64,
+    pub player: Pubkey,
+    pub side: CoinSide,
+    pub amount: u64,
+    pub heads_total: u64,
+    pub tails_total: u64,
+}
+
+#[event]
+pub struct MarketResolved {
+    pub event_version: u8,
+    pub market_id: u64,
+    pub result: CoinSide,
+    pub slot_hash: [u8; 32],
+    pub randomness: [u8; 32], // draw_randomness(market_id, slot_hash)
+    pub heads_total: u64,
+    pub tails_total: u64,
+    pub house_fee: u64,
+    pub payout_pool: u64,
+}
+
+#[event]
+pub struct MarketVoided {
+    pub event_version: u8,
+    pub market_id: u64,
+    pub voided_by: Pubkey,
+    pub heads_total: u64,
+    pub tails_total: u64,
+}
+
+#[event]
+pub struct MarketClaimed {
+    pub event_version: u8,
+    pub market_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LotteryScheduled {
+    pub event_version: u8,
+    pub lottery_id: u64,
+    pub operator: Pubkey,
+    pub secret_commitment: [u8; 32],
+    pub ticket_price: u64,
+    pub max_entries: u32,
+    pub cutoff_slot: u64,
+    pub draw_slot: u64,
+    pub house_fee_bps: u64,
+}
+
+#[event]
+pub struct TicketBought {
+    pub event_version: u8,
+    pub lottery_id: u64,
+    pub player: Pubkey,
+    pub number: u32,
+    pub entries: u32,
+}
+
+#[event]
+pub struct LotteryDrawn {
+    pub event_version: u8,
+    pub lottery_id: u64,
+    pub winning_ticket: u32, // draw_index(randomness, entries)
+    pub secret: u64,
+    pub slot_hash: [u8; 32],
+    pub randomness: [u8; 32],
+    pub entries: u32,
+    pub house_fee: u64,
+    pub prize: u64,
+}
+
+#[event]
+pub struct LotteryVoided {
+    pub event_version: u8,
+    pub lottery_id: u64,
+    pub voided_by: Pubkey,
+    pub entries: u32,
+}
+
+#[event]
+pub struct LotteryClaimed {
+    pub event_version: u8,
+    pub lottery_id: u64,
+    pub player: Pubkey,
+    pub number: u32,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoomCreated {
+    pub event_version: u8,
+    pub room_id: u64,
+    pub creator: Pubkey,
+    pub stake: u64,
+    pub house_fee_bps: u64,
+}
+
+#[event]
+pub struct ChallengeQueued {
+    pub event_version: u8,
+    pub room_id: u64,
+    pub creator: Pubkey,
+    pub challenger: Pubkey,
+    pub side: CoinSide,
+    pub queue_len: u8,
+    pub bout_draw_slot: u64, // Of the head challenger's bout
+}
+
+#[event]
+pub struct BoutSettled {
+    pub event_version: u8,
+    pub room_id: u64,
+    pub creator: Pubkey,
+    pub champion: Pubkey, // Defending champion
+    pub challenger: Pubkey,
+    pub challenger_side: CoinSide,
+    pub result: CoinSide,
+    pub winner: Pubkey, // Champion of the room from now on
+    pub secret: u64,
+    pub slot_hash: [u8; 32],
+    pub randomness: [u8; 32],
+    pub house_fee: u64,
+    pub winnings: u64, // Paid to the winner; their stake stays in the room
+    pub streak: u32,   // The winner's streak after this bout
+}
+
+#[event]
+pub struct BoutForfeited {
+    pub event_version: u8,
+    pub room_id: u64,
+    pub creator: Pubkey,
+    pub champion: Pubkey, // Champion who didn't reveal in time
+    pub challenger: Pubkey,
+    pub house_fee: u64,
+    pub winnings: u64,
+}
+
+#[event]
+pub struct ChampionRetired {
+    pub event_version: u8,
+    pub room_id: u64,
+    pub creator: Pubkey,
+    pub champion: Pubkey,
+    pub streak: u32,
+    pub winnings: u64,
+    pub bouts: u64, // Settled over the room's lifetime
+}
+
+#[event]
+pub struct MatchQueueEntered {
+    pub event_version: u8,
+    pub stake: u64,
+    pub player: Pubkey,
+    pub queue_len: u8,
+}
+
+#[event]
+pub struct MatchQueueLeft {
+    pub event_version: u8,
+    pub stake: u64,
+    pub player: Pubkey,
+    pub queue_len: u8,
+}
+
+#[event]
+pub struct BracketStarted {
+    pub event_version: u8,
+    pub bracket_id: u64,
+    pub operator: Pubkey,
+    pub stake: u64,
+    pub players: Vec<Pubkey>, // Seeding order; round one pairs them (0, 1), (2, 3), ...
+    pub round_draw_slot: u64,
+}
+
+#[event]
+pub struct BracketRoundDrawn {
+    pub event_version: u8,
+    pub bracket_id: u64,
+    pub round: u8, // 1 for the first round
+    pub secret: u64,
+    pub slot_hash: [u8; 32],
+    pub randomness: [u8; 32],
+    pub winners: Vec<Pubkey>, // bracket_round_winners of the players still in
+}
+
+#[event]
+pub struct BracketWon {
+    pub event_version: u8,
+    pub bracket_id: u64,
+    pub champion: Pubkey,
+    pub rounds: u8,
+    pub house_fee: u64,
+    pub prize: u64,
+}
+
+#[event]
+pub struct BracketVoided {
+    pub event_version: u8,
+    pub bracket_id: u64,
+    pub voided_by: Pubkey,
+    pub round: u8, // Rounds drawn before it was voided
+}
+
+#[event]
+pub struct BracketClaimed {
+    pub event_version: u8,
+    pub bracket_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DelegateAuthorized {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub max_stake: u64,
+}
+
+#[event]
+pub struct DelegateRevoked {
+    pub event_version: u8,
+    pub player: Pubkey,
+    pub session_key: Pubkey,
+}
+
+// Decode an event from its discriminator-prefixed bytes (emit! log data, or the
+// data of a self-CPI'd emit_cpi! event after its 8-byte tag). Bytes past the
+// fields T knows are ignored, so a decoder built against an older version of an
+// event keeps working after fields are appended.
+pub fn decode_event<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    if data.len() < 8 || data[..8] != T::DISCRIMINATOR {
+        return None;
+    }
+    T::deserialize(&mut &data[8..]).ok()
+}
@@ -0,0 +1,103 @@
+use crate::*;
+
+// Commitment, flip and winner logic live in coin-flipper-core so clients compute
+// byte-identical commitments; these wrap it in the program's types
+pub fn generate_commitment(choice: CoinSide, secret: u64) -> [u8; 32] {
+    coin_flipper_core::commitment(choice.into(), secret)
+}
+
+pub fn generate_coin_flip(secret_a: u64, secret_b: u64, slot: u64, timestamp: i64) -> CoinSide {
+    coin_flipper_core::coin_flip(secret_a, secret_b, slot, timestamp).into()
+}
+
+// The hash generate_coin_flip reads the result from, published in GameResolved
+pub fn flip_randomness(secret_a: u64, secret_b: u64, slot: u64, timestamp: i64) -> [u8; 32] {
+    coin_flipper_core::flip_randomness(secret_a, secret_b, slot, timestamp)
+}
+
+pub fn coin_side(randomness: &[u8; 32]) -> CoinSide {
+    coin_flipper_core::coin_side(randomness).into()
+}
+
+pub fn draw_commitment(secret: u64) -> [u8; 32] {
+    coin_flipper_core::secret_commitment(secret)
+}
+
+pub fn draw_randomness(secret: u64, slot_hash: &[u8; 32]) -> [u8; 32] {
+    coin_flipper_core::draw_randomness(secret, slot_hash)
+}
+
+// Callers ensure count > 0
+pub fn draw_index(randomness: &[u8; 32], count: u32) -> u32 {
+    coin_flipper_core::draw_index(randomness, count)
+}
+
+pub fn draw_bit(randomness: &[u8; 32], index: usize) -> bool {
+    coin_flipper_core::draw_bit(randomness, index)
+}
+
+pub fn auto_pick(game: &Pubkey, player: &Pubkey, slot: u64) -> (CoinSide, u64) {
+    let (side, secret) = coin_flipper_core::auto_pick(&game.to_bytes(), &player.to_bytes(), slot);
+    (side.into(), secret)
+}
+
+pub fn is_golden_flip(randomness: &[u8; 32], odds: u32) -> bool {
+    coin_flipper_core::is_golden_flip(randomness, odds)
+}
+
+// Hash of `slot` from raw SlotHashes sysvar data: a u64 count, then (slot, hash)
+// entries newest first. Reading the bytes directly avoids deserializing all 512
+// entries. None once the slot has aged out, or if it was skipped.
+pub fn slot_hash_at(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    const ENTRY_LEN: usize = 40;
+    let count = u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as usize;
+    let entries = data.get(8..8 + count.checked_mul(ENTRY_LEN)?)?;
+    let slot_of = |i: usize| {
+        u64::from_le_bytes(
+            entries[i * ENTRY_LEN..i * ENTRY_LEN + 8]
+                .try_into()
+                .unwrap(),
+        )
+    };
+    // Entries are sorted by descending slot
+    let (mut low, mut high) = (0, count);
+    while low < high {
+        let mid = (low + high) / 2;
+        match slot_of(mid).cmp(&slot) {
+            std::cmp::Ordering::Equal => {
+                let start = mid * ENTRY_LEN + 8;
+                return entries[start..start + 32].try_into().ok();
+            }
+            std::cmp::Ordering::Greater => low = mid + 1,
+            std::cmp::Ordering::Less => high = mid,
+        }
+    }
+    None
+}
+
+// One player's half of a fully revealed game
+pub(crate) struct Revealed {
+    pub player: Pubkey,
+    pub choice: CoinSide,
+    pub secret: u64,
+}
+
+// Determine winner with secure tiebreaker
+pub(crate) fn determine_winner(
+    a: Revealed,
+    b: Revealed,
+    coin_result: CoinSide,
+    slot: u64,
+) -> Pubkey {
+    match coin_flipper_core::winner(
+        a.choice.into(),
+        b.choice.into(),
+        coin_result.into(),
+        a.secret,
+        b.secret,
+        slot,
+    ) {
+        coin_flipper_core::Player::A => a.player,
+        coin_flipper_core::Player::B => b.player,
+    }
+}
@@ -0,0 +1,202 @@
+use crate::*;
+
+// When an attestation issuer is configured, the player must present the issuer's
+// attestation PDA for their wallet (seeds ["attestation", player], owned by the issuer)
+pub(crate) fn verify_attestation(
+    global_state: &GlobalState,
+    player: Pubkey,
+    attestation: Option<&AccountInfo>,
+) -> Result<()> {
+    let issuer = global_state.attestation_issuer;
+    if issuer == Pubkey::default() {
+        return Ok(());
+    }
+
+    let attestation = attestation.ok_or(GameError::AttestationRequired)?;
+    let (expected, _) = Pubkey::find_program_address(&[b"attestation", player.as_ref()], &issuer);
+    require!(
+        attestation.key() == expected
+            && *attestation.owner == issuer
+            && !attestation.data_is_empty(),
+        GameError::InvalidAttestation
+    );
+    Ok(())
+}
+
+// With a cooldown configured, a player can't start or join a game until it has
+// passed since their last resolved game
+pub(crate) fn verify_cooldown(
+    global_state: &GlobalState,
+    stats: &PlayerStats,
+    now: i64,
+) -> Result<()> {
+    if global_state.game_cooldown_seconds == 0 || stats.last_resolved_at == 0 {
+        return Ok(());
+    }
+    let ready_at = stats
+        .last_resolved_at
+        .saturating_add(global_state.game_cooldown_seconds);
+    require!(now >= ready_at, GameError::CooldownActive);
+    Ok(())
+}
+
+// Players who set a loss limit can't start, join or commit to games once their
+// net loss for the current season reaches it
+pub(crate) fn verify_loss_limit(global_state: &GlobalState, stats: &PlayerStats) -> Result<()> {
+    if stats.season_loss_limit == 0 {
+        return Ok(());
+    }
+    require!(
+        stats.season_net_loss(global_state.current_season_id) < stats.season_loss_limit,
+        GameError::LossLimitReached
+    );
+    Ok(())
+}
+
+// A player who froze themselves can't start, join or commit to games until it ends
+pub(crate) fn verify_not_frozen(stats: &PlayerStats, now: i64) -> Result<()> {
+    require!(!stats.is_frozen(now), GameError::AccountFrozen);
+    Ok(())
+}
+
+// With a terms version set, the player must have accepted exactly that version
+pub(crate) fn verify_terms(
+    global_state: &GlobalState,
+    acceptance: Option<&Account<TermsAcceptance>>,
+) -> Result<()> {
+    if global_state.terms_version == 0 {
+        return Ok(());
+    }
+    let acceptance = acceptance.ok_or(GameError::TermsNotAccepted)?;
+    require!(
+        acceptance.version == global_state.terms_version,
+        GameError::TermsNotAccepted
+    );
+    Ok(())
+}
+
+// With the CPI guard on, fund-moving instructions must be top-level or sit inside a
+// transaction instruction addressed to an allowlisted program
+pub(crate) fn verify_cpi_caller(
+    global_state: &GlobalState,
+    instructions: Option<&AccountInfo>,
+) -> Result<()> {
+    if !global_state.cpi_guard_enabled || get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(());
+    }
+
+    let instructions = instructions.ok_or(GameError::MissingInstructionsSysvar)?;
+    let outer = get_instruction_relative(0, instructions)?;
+    require!(
+        outer.program_id != Pubkey::default()
+            && global_state.allowed_cpi_callers.contains(&outer.program_id),
+        GameError::UntrustedCaller
+    );
+    Ok(())
+}
+
+// Signer and message of the ed25519 program instruction just before this one. The
+// runtime has already verified the signature; this reads back who signed what,
+// failing with `missing` when there is no such instruction and `invalid` unless it
+// holds a single self-contained signature over a 72-byte message.
+pub(crate) fn preceding_ed25519_message(
+    instructions: &AccountInfo,
+    missing: GameError,
+    invalid: GameError,
+) -> Result<(Pubkey, [u8; 72])> {
+    let signed_ix = get_instruction_relative(-1, instructions).map_err(|_| error!(missing))?;
+    if signed_ix.program_id != ed25519_program::ID {
+        return Err(error!(missing));
+    }
+
+    // Layout: [num_signatures, padding, then one 14-byte offsets record]
+    let data = &signed_ix.data;
+    if data.len() < 16 || data[0] != 1 {
+        return Err(error!(invalid));
+    }
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+
+    // Signature, key, and message must all live in the ed25519 instruction itself,
+    // and the message must be exactly 72 bytes
+    let self_contained =
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX;
+    if !self_contained || read_u16(12) != 72 {
+        return Err(error!(invalid));
+    }
+    let key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+
+    let signer = data.get(key_offset..key_offset + 32).ok_or(invalid)?;
+    let message = data
+        .get(message_offset..message_offset + 72)
+        .ok_or(invalid)?;
+
+    let mut signed = [0u8; 72];
+    signed.copy_from_slice(message);
+    Ok((Pubkey::try_from(signer).unwrap(), signed))
+}
+
+// Expiry stored in the last 8 bytes of a signed ed25519 message
+pub(crate) fn signed_expiry(message: &[u8; 72]) -> i64 {
+    let mut expiry = [0u8; 8];
+    expiry.copy_from_slice(&message[64..72]);
+    i64::from_le_bytes(expiry)
+}
+
+// When a matchmaker is configured, join_game must be preceded by an ed25519 program
+// instruction carrying the matchmaker's signature over game || player || expiry (i64 LE)
+pub(crate) fn verify_match_ticket(
+    global_state: &GlobalState,
+    game: Pubkey,
+    player: Pubkey,
+    instructions: Option<&AccountInfo>,
+    now: i64,
+) -> Result<()> {
+    let matchmaker = global_state.matchmaker;
+    if matchmaker == Pubkey::default() {
+        return Ok(());
+    }
+
+    let instructions = instructions.ok_or(GameError::MissingInstructionsSysvar)?;
+    let (signer, message) = preceding_ed25519_message(
+        instructions,
+        GameError::MissingMatchTicket,
+        GameError::InvalidMatchTicket,
+    )?;
+    require!(signer == matchmaker, GameError::InvalidMatchTicket);
+    require!(
+        message[..32] == game.to_bytes() && message[32..64] == player.to_bytes(),
+        GameError::InvalidMatchTicket
+    );
+    require!(
+        now <= signed_expiry(&message),
+        GameError::MatchTicketExpired
+    );
+    Ok(())
+}
+
+// A relayed commitment must be preceded by an ed25519 program instruction carrying
+// the player's own signature over game || commitment || expiry (i64 LE)
+pub(crate) fn verify_player_signature(
+    game: Pubkey,
+    player: Pubkey,
+    commitment: [u8; 32],
+    instructions: &AccountInfo,
+    now: i64,
+) -> Result<()> {
+    let (signer, message) = preceding_ed25519_message(
+        instructions,
+        GameError::MissingPlayerSignature,
+        GameError::InvalidPlayerSignature,
+    )?;
+    require!(signer == player, GameError::InvalidPlayerSignature);
+    require!(
+        message[..32] == game.to_bytes() && message[32..64] == commitment,
+        GameError::InvalidPlayerSignature
+    );
+    require!(
+        now <= signed_expiry(&message),
+        GameError::PlayerSignatureExpired
+    );
+    Ok(())
+}
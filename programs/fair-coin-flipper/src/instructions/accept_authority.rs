@@ -0,0 +1,45 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, seeds = [b"admin_log"], bump = admin_log.bump)]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+}
+
+// The proposed authority signs (directly or via multisig CPI) to take over
+pub(crate) fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    require!(
+        global_state.pending_authority != Pubkey::default()
+            && ctx.accounts.new_authority.key() == global_state.pending_authority,
+        GameError::Unauthorized
+    );
+
+    let previous_authority = global_state.authority;
+    global_state.authority = global_state.pending_authority;
+    global_state.pending_authority = Pubkey::default();
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::AcceptAuthority,
+        ctx.accounts.new_authority.key(),
+        previous_authority,
+        0,
+        clock.unix_timestamp,
+    );
+
+    emit!(AuthorityTransferred {
+        event_version: EVENT_VERSION,
+        previous_authority,
+        new_authority: global_state.authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AcceptGameOwnership<'info> {
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    // Required once a terms version is set
+    #[account(
+        seeds = [b"terms", new_owner.key().as_ref()],
+        bump = terms_acceptance.bump
+    )]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Achievements::INIT_SPACE,
+        seeds = [b"achievements", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_achievements: Account<'info, Achievements>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_history: Box<Account<'info, GameHistory>>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, needed for the CPI guard
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Accept a pending ownership offer, becoming Player A of the game. The new owner
+// takes over a stake in play, so they pass the same player checks as a joiner.
+pub(crate) fn handler(ctx: Context<AcceptGameOwnership>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let global_state = &ctx.accounts.global_state;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        game.status() == GameStatus::WaitingForPlayer,
+        GameError::InvalidGameStatus
+    );
+    require!(
+        game.pending_owner != Pubkey::default()
+            && ctx.accounts.new_owner.key() == game.pending_owner,
+        GameError::NotPendingOwner
+    );
+
+    init_player_stats_if_needed(
+        &mut ctx.accounts.new_owner_stats,
+        ctx.accounts.new_owner.key(),
+        ctx.bumps.new_owner_stats,
+    );
+    verify_attestation(
+        global_state,
+        ctx.accounts.new_owner.key(),
+        ctx.accounts.attestation.as_ref(),
+    )?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    verify_loss_limit(global_state, &ctx.accounts.new_owner_stats)?;
+    verify_not_frozen(&ctx.accounts.new_owner_stats, now)?;
+    verify_cooldown(global_state, &ctx.accounts.new_owner_stats, now)?;
+    init_achievements_if_needed(
+        &mut ctx.accounts.new_owner_achievements,
+        ctx.accounts.new_owner.key(),
+        ctx.bumps.new_owner_achievements,
+    );
+    init_game_history_if_needed(
+        &mut ctx.accounts.new_owner_history,
+        ctx.accounts.new_owner.key(),
+        ctx.bumps.new_owner_history,
+    );
+
+    let previous_owner = game.player_a;
+    game.player_a = game.pending_owner;
+    game.pending_owner = Pubkey::default();
+    game.next_seq();
+
+    emit!(OwnershipTransferred {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        previous_owner,
+        new_owner: game.player_a,
+        seq: game.seq,
+    });
+
+    Ok(())
+}
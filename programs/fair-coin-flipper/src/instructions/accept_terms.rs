@@ -0,0 +1,49 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AcceptTerms<'info> {
+    pub player: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + TermsAcceptance::INIT_SPACE,
+        seeds = [b"terms", player.key().as_ref()],
+        bump
+    )]
+    pub terms_acceptance: Account<'info, TermsAcceptance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Record the player's acceptance of the current terms. The account only holds
+// the latest version; the TermsAccepted events are the full consent trail.
+pub(crate) fn handler(ctx: Context<AcceptTerms>, version: u32) -> Result<()> {
+    require!(
+        version != 0 && version == ctx.accounts.global_state.terms_version,
+        GameError::InvalidTermsVersion
+    );
+
+    let accepted_at = Clock::get()?.unix_timestamp;
+    let terms_acceptance = &mut ctx.accounts.terms_acceptance;
+    terms_acceptance.player = ctx.accounts.player.key();
+    terms_acceptance.version = version;
+    terms_acceptance.accepted_at = accepted_at;
+    terms_acceptance.bump = ctx.bumps.terms_acceptance;
+
+    emit!(TermsAccepted {
+        event_version: EVENT_VERSION,
+        player: terms_acceptance.player,
+        version,
+        accepted_at,
+    });
+
+    Ok(())
+}
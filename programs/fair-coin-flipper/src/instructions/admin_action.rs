@@ -0,0 +1,19 @@
+use crate::*;
+
+// Admin instructions take the authority as a plain (non-mut) signer so a
+// multisig executor can satisfy it with a PDA signature over CPI.
+#[derive(Accounts)]
+pub struct AdminAction<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ GameError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, seeds = [b"admin_log"], bump = admin_log.bump)]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+}
@@ -0,0 +1,97 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ArchiveGame<'info> {
+    pub archiver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump,
+        close = creator
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(mut, address = game.load()?.creator)]
+    /// CHECK: Receives the closed game's rent
+    pub creator: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, address = global_state.archive_tree)]
+    /// CHECK: Archive tree configured in global state
+    pub merkle_tree: AccountInfo<'info>,
+
+    #[account(seeds = [b"archive_authority"], bump)]
+    /// CHECK: PDA that signs as the tree authority
+    pub archive_authority: AccountInfo<'info>,
+
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    /// CHECK: SPL account compression program
+    pub compression_program: AccountInfo<'info>,
+
+    #[account(address = NOOP_PROGRAM_ID)]
+    /// CHECK: SPL noop program used as the compression log wrapper
+    pub noop_program: AccountInfo<'info>,
+}
+
+// Append a settled game's summary to the archive tree as a compressed leaf and
+// close the game account, returning its rent to the creator. The full summary
+// is logged through the noop program so indexers can rebuild the leaf. Resolved
+// games have already been shrunk to a ResolvedSummary and go through
+// archive_resolved_game instead.
+pub(crate) fn handler(ctx: Context<ArchiveGame>) -> Result<()> {
+    let game = &ctx.accounts.game.load()?;
+
+    require!(
+        ctx.accounts.global_state.archive_tree != Pubkey::default(),
+        GameError::ArchiveNotConfigured
+    );
+    require!(
+        is_player_or_operator(
+            ctx.accounts.archiver.key(),
+            game,
+            &ctx.accounts.global_state,
+        ),
+        GameError::NotPlayerOrOperator
+    );
+    require!(is_settled(&game.status()), GameError::InvalidGameStatus);
+
+    // Closing the account is the game's last change
+    let seq = game.seq.wrapping_add(1);
+    let archived = ArchivedGame {
+        game_id: game.game_id,
+        creator: game.creator,
+        player_a: game.player_a,
+        player_b: game.player_b,
+        bet_amount: game.bet_amount,
+        status: game.status(),
+        coin_result: game.coin_result(),
+        winner: game.winner(),
+        house_fee: game.house_fee,
+        created_at: game.created_at,
+        resolved_at: game.resolved_at(),
+    };
+    append_archive_leaf(
+        &archived,
+        &mut ctx.accounts.global_state,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.archive_authority,
+        &ctx.accounts.noop_program,
+        &ctx.accounts.compression_program,
+        ctx.bumps.archive_authority,
+        seq,
+    )?;
+
+    emit!(GameExpiredCleaned {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        creator: game.creator,
+        cleaned_by: ctx.accounts.archiver.key(),
+        rent_returned: ctx.accounts.game.to_account_info().lamports(),
+        seq,
+    });
+
+    Ok(())
+}
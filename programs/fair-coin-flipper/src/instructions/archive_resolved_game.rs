@@ -0,0 +1,69 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ArchiveResolvedGame<'info> {
+    pub archiver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.creator.as_ref(), &game.game_id.to_le_bytes()],
+        bump = game.bump,
+        close = creator
+    )]
+    pub game: Account<'info, ResolvedSummary>,
+
+    #[account(mut, address = game.creator)]
+    /// CHECK: Receives the closed summary's rent
+    pub creator: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, address = global_state.archive_tree)]
+    /// CHECK: Archive tree configured in global state
+    pub merkle_tree: AccountInfo<'info>,
+
+    #[account(seeds = [b"archive_authority"], bump)]
+    /// CHECK: PDA that signs as the tree authority
+    pub archive_authority: AccountInfo<'info>,
+
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    /// CHECK: SPL account compression program
+    pub compression_program: AccountInfo<'info>,
+
+    #[account(address = NOOP_PROGRAM_ID)]
+    /// CHECK: SPL noop program used as the compression log wrapper
+    pub noop_program: AccountInfo<'info>,
+}
+
+// archive_game for a game that resolved and was shrunk to its ResolvedSummary.
+// The leaf is built from the same ArchivedGame fields, so indexers can't tell the
+// two paths apart.
+pub(crate) fn handler(ctx: Context<ArchiveResolvedGame>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let global_state = &ctx.accounts.global_state;
+
+    require!(
+        global_state.archive_tree != Pubkey::default(),
+        GameError::ArchiveNotConfigured
+    );
+    let archiver = ctx.accounts.archiver.key();
+    require!(
+        archiver == game.player_a
+            || archiver == game.player_b
+            || (archiver == global_state.operator && global_state.operator != Pubkey::default()),
+        GameError::NotPlayerOrOperator
+    );
+
+    let archived = game.archived();
+    append_archive_leaf(
+        &archived,
+        &mut ctx.accounts.global_state,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.archive_authority,
+        &ctx.accounts.noop_program,
+        &ctx.accounts.compression_program,
+        ctx.bumps.archive_authority,
+        game.seq.wrapping_add(1), // Closing the account is the game's last change
+    )
+}
@@ -0,0 +1,36 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AssertEscrowInvariants<'info> {
+    #[account(
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(address = escrow_address(&*game.load()?)?)]
+    /// CHECK: The game's escrow PDA or the shared escrow vault; only read
+    pub escrow: AccountInfo<'info>,
+}
+
+// Permissionless check that a game's escrow holds exactly what the game still
+// owes, or for a vault-mode game that the vault covers its ledger. Never fails on
+// a mismatch; monitoring bots watch for the event instead.
+pub(crate) fn handler(ctx: Context<AssertEscrowInvariants>) -> Result<()> {
+    let game = &ctx.accounts.game.load()?;
+    let (expected, actual, reconciled) = check_escrow(game, &ctx.accounts.escrow)?;
+
+    if !reconciled {
+        emit!(EscrowDiscrepancy {
+            event_version: EVENT_VERSION,
+            game_id: game.game_id,
+            status: game.status(),
+            expected,
+            actual,
+            detected_at: Clock::get()?.unix_timestamp,
+            seq: game.seq,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AuthorizeDelegate<'info> {
+    pub player: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Delegate::INIT_SPACE,
+        seeds = [b"delegate", player.key().as_ref()],
+        bump
+    )]
+    pub delegate: Account<'info, Delegate>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Let a session key commit and reveal for the player until `expires_at`, in
+// games staking at most `max_stake`, so frontends can skip the wallet popup
+// on every move. Authorizing again replaces the previous session key.
+pub(crate) fn handler(
+    ctx: Context<AuthorizeDelegate>,
+    session_key: Pubkey,
+    expires_at: i64,
+    max_stake: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        session_key != Pubkey::default() && session_key != ctx.accounts.player.key(),
+        GameError::InvalidDelegateTerms
+    );
+    require!(
+        expires_at > clock.unix_timestamp
+            && expires_at - clock.unix_timestamp <= MAX_DELEGATE_SECONDS,
+        GameError::InvalidDelegateTerms
+    );
+
+    let delegate = &mut ctx.accounts.delegate;
+    delegate.player = ctx.accounts.player.key();
+    delegate.session_key = session_key;
+    delegate.expires_at = expires_at;
+    delegate.max_stake = max_stake;
+    delegate.bump = ctx.bumps.delegate;
+
+    emit!(DelegateAuthorized {
+        event_version: EVENT_VERSION,
+        player: delegate.player,
+        session_key,
+        expires_at,
+        max_stake,
+    });
+
+    Ok(())
+}
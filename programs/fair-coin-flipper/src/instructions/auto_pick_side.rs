@@ -0,0 +1,76 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AutoPickSide<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+// Play out a game whose joiner asked for auto-pick and let the commit deadline pass.
+// The first call closes the commit phase without player B and gives player A a full
+// phase to reveal; the second, once A has revealed, draws B's side. A's choice is
+// fixed and public before B's side exists, and A can't crank the draw, so A neither
+// learns the pick in time to withhold a reveal nor picks the slot it comes from.
+pub(crate) fn handler(ctx: Context<AutoPickSide>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let clock = Clock::get()?;
+
+    // Picking is a crank for the players or the operator, like cancellation
+    require!(
+        is_player_or_operator(ctx.accounts.cranker.key(), game, &ctx.accounts.global_state,),
+        GameError::NotPlayerOrOperator
+    );
+
+    if game.auto_pick_due() && is_cancellable(game, clock.unix_timestamp) {
+        game.set_flag(FLAG_COMMITMENTS_COMPLETE, true);
+        game.transition(GameEvent::CommitmentsComplete)?;
+        set_phase_deadline(game, &ctx.accounts.global_state, clock.unix_timestamp)?;
+        game.next_seq();
+
+        emit!(AutoPickScheduled {
+            event_version: EVENT_VERSION,
+            game_id: game.game_id,
+            player: game.player_b,
+            reveal_deadline: game.phase_deadline,
+            scheduled_by: ctx.accounts.cranker.key(),
+            seq: game.seq,
+        });
+        return Ok(());
+    }
+    require!(game.auto_pick_draw_due(), GameError::NoAutoPickDue);
+    // B's side comes from the slot the draw lands in, so A doesn't get to choose it
+    require!(
+        ctx.accounts.cranker.key() != game.player_a,
+        GameError::AutoPickByPlayerA
+    );
+
+    let (choice, secret) = auto_pick(&ctx.accounts.game.key(), &game.player_b, clock.slot);
+    game.commitment_b = generate_commitment(choice, secret);
+    game.set_choice_b(choice);
+    game.secret_b = secret;
+    // Both sides are in; either player or the operator resolves with
+    // resolve_game_manual within a fresh phase
+    set_phase_deadline(game, &ctx.accounts.global_state, clock.unix_timestamp)?;
+    game.next_seq();
+
+    emit!(SideAutoPicked {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        player: game.player_b,
+        choice,
+        secret,
+        picked_by: ctx.accounts.cranker.key(),
+        seq: game.seq,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct BackMarket<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    #[account(seeds = [b"terms", player.key().as_ref()], bump = terms_acceptance.bump)]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump = market.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the market's stakes
+    pub market_escrow: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MarketPosition::INIT_SPACE,
+        seeds = [b"market_position", market.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, MarketPosition>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Stake on one side of an open market. A player can back either side, and add to
+// a side, any number of times before the cutoff.
+pub(crate) fn handler(ctx: Context<BackMarket>, side: CoinSide, amount: u64) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let market = &mut ctx.accounts.market;
+    let player = ctx.accounts.player.key();
+    let clock = Clock::get()?;
+
+    require!(
+        market.status == MarketStatus::Open && clock.slot < market.cutoff_slot,
+        GameError::MarketClosed
+    );
+    require!(amount >= global_state.min_bet_amount, GameError::BetTooLow);
+    require!(amount <= global_state.max_bet_amount, GameError::BetTooHigh);
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_stats,
+        player,
+        ctx.bumps.player_stats,
+    );
+    verify_attestation(global_state, player, ctx.accounts.attestation.as_ref())?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    verify_loss_limit(global_state, &ctx.accounts.player_stats)?;
+    verify_not_frozen(&ctx.accounts.player_stats, clock.unix_timestamp)?;
+    verify_cooldown(
+        global_state,
+        &ctx.accounts.player_stats,
+        clock.unix_timestamp,
+    )?;
+    record_pooled_stake(
+        &mut ctx.accounts.player_stats,
+        amount,
+        global_state.current_season_id,
+    );
+
+    let position = &mut ctx.accounts.position;
+    if position.player == Pubkey::default() {
+        position.market = market.key();
+        position.player = player;
+        position.bump = ctx.bumps.position;
+        market.positions = market
+            .positions
+            .checked_add(1)
+            .ok_or(GameError::MathOverflow)?;
+    }
+    let (stake, total) = match side {
+        CoinSide::Heads => (&mut position.heads, &mut market.heads_total),
+        CoinSide::Tails => (&mut position.tails, &mut market.tails_total),
+    };
+    *stake = stake.checked_add(amount).ok_or(GameError::MathOverflow)?;
+    *total = total.checked_add(amount).ok_or(GameError::MathOverflow)?;
+
+    deposit_stake(
+        ctx.accounts.player.to_account_info(),
+        &ctx.accounts.market_escrow,
+        &ctx.accounts.system_program,
+        amount,
+    )?;
+
+    emit!(MarketBacked {
+        event_version: EVENT_VERSION,
+        market_id: market.market_id,
+        player,
+        side,
+        amount,
+        heads_total: market.heads_total,
+        tails_total: market.tails_total,
+    });
+
+    Ok(())
+}
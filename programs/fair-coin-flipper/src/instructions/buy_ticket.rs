@@ -0,0 +1,130 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct BuyTicket<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    #[account(seeds = [b"terms", player.key().as_ref()], bump = terms_acceptance.bump)]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery".as_ref(), &lottery.lottery_id.to_le_bytes()],
+        bump = lottery.bump
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery_escrow", lottery.key().as_ref()],
+        bump = lottery.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the lottery's ticket sales
+    pub lottery_escrow: AccountInfo<'info>,
+
+    // Numbered by sale order, so the next ticket is always at `entries`
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LotteryTicket::INIT_SPACE,
+        seeds = [
+            b"lottery_ticket",
+            lottery.key().as_ref(),
+            &lottery.entries.to_le_bytes()
+        ],
+        bump
+    )]
+    pub ticket: Account<'info, LotteryTicket>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Buy the next ticket in an open lottery. A player can hold any number of tickets,
+// one account each.
+pub(crate) fn handler(ctx: Context<BuyTicket>) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let lottery = &mut ctx.accounts.lottery;
+    let player = ctx.accounts.player.key();
+    let clock = Clock::get()?;
+
+    require!(
+        lottery.status == LotteryStatus::Open
+            && clock.slot < lottery.cutoff_slot
+            && lottery.entries < lottery.max_entries,
+        GameError::LotteryClosed
+    );
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_stats,
+        player,
+        ctx.bumps.player_stats,
+    );
+    verify_attestation(global_state, player, ctx.accounts.attestation.as_ref())?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    verify_loss_limit(global_state, &ctx.accounts.player_stats)?;
+    verify_not_frozen(&ctx.accounts.player_stats, clock.unix_timestamp)?;
+    verify_cooldown(
+        global_state,
+        &ctx.accounts.player_stats,
+        clock.unix_timestamp,
+    )?;
+    record_pooled_stake(
+        &mut ctx.accounts.player_stats,
+        lottery.ticket_price,
+        global_state.current_season_id,
+    );
+
+    let number = lottery.entries;
+    ctx.accounts.ticket.set_inner(LotteryTicket {
+        lottery: lottery.key(),
+        player,
+        number,
+        bump: ctx.bumps.ticket,
+    });
+    lottery.entries = number.checked_add(1).ok_or(GameError::MathOverflow)?;
+
+    deposit_stake(
+        ctx.accounts.player.to_account_info(),
+        &ctx.accounts.lottery_escrow,
+        &ctx.accounts.system_program,
+        lottery.ticket_price,
+    )?;
+
+    emit!(TicketBought {
+        event_version: EVENT_VERSION,
+        lottery_id: lottery.lottery_id,
+        player,
+        number,
+        entries: lottery.entries,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::*;
+
+// Drop a queued change before it executes
+pub(crate) fn handler(ctx: Context<AdminAction>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let pending = take_pending_change(global_state)?;
+
+    let (target, value) = config_change_summary(&pending.change);
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::CancelConfigChange,
+        ctx.accounts.authority.key(),
+        target,
+        value,
+        clock.unix_timestamp,
+    );
+
+    emit!(ConfigChangeCancelled {
+        event_version: EVENT_VERSION,
+        change: pending.change,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelGame<'info> {
+    #[account(mut)]
+    pub canceller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, address = game.load()?.player_a)]
+    /// CHECK: Player A account for refunds
+    pub player_a: AccountInfo<'info>,
+
+    // Before anyone joins there is no player B; any writable account may fill the slot
+    #[account(
+        mut,
+        constraint = game.load()?.player_b == Pubkey::default() || player_b.key() == game.load()?.player_b
+    )]
+    /// CHECK: Player B account for refunds
+    pub player_b: AccountInfo<'info>,
+
+    // Created here for a lite joiner who never committed, so the fee is always recorded
+    #[account(
+        init_if_needed,
+        payer = canceller,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_a_stats: Box<Account<'info, PlayerStats>>,
+
+    // Required once player B has joined
+    #[account(
+        init_if_needed,
+        payer = canceller,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player_b.key().as_ref()],
+        bump
+    )]
+    pub player_b_stats: Option<Box<Account<'info, PlayerStats>>>,
+
+    #[account(mut, address = game.load()?.sponsor_a)]
+    /// CHECK: Player A's sponsor, required only when the stake was sponsored
+    pub sponsor_a: Option<AccountInfo<'info>>,
+
+    #[account(mut, address = game.load()?.sponsor_b)]
+    /// CHECK: Player B's sponsor, required only when the stake was sponsored
+    pub sponsor_b: Option<AccountInfo<'info>>,
+
+    #[account(mut, seeds = [b"treasury"], bump = global_state.treasury_bump)]
+    /// CHECK: Program-derived fee treasury; holds lamports only
+    pub treasury: AccountInfo<'info>,
+
+    #[account(mut, address = game.load()?.creator)]
+    /// CHECK: Original creator; receives the escrow's rent reserve when it closes
+    pub creator: AccountInfo<'info>,
+
+    #[account(mut, address = escrow_address(&*game.load()?)?)]
+    /// CHECK: The game's escrow PDA, or the shared escrow vault for vault-mode games
+    pub escrow: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Cancel game function with fees
+pub(crate) fn handler(ctx: Context<CancelGame>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let clock = Clock::get()?;
+
+    // Cancellation is a crank for the players or the operator
+    require!(
+        is_player_or_operator(
+            ctx.accounts.canceller.key(),
+            game,
+            &ctx.accounts.global_state,
+        ),
+        GameError::NotPlayerOrOperator
+    );
+    verify_cpi_caller(
+        &ctx.accounts.global_state,
+        ctx.accounts.instructions.as_ref(),
+    )?;
+    require!(
+        game.player_b == Pubkey::default() || ctx.accounts.player_b_stats.is_some(),
+        GameError::MissingPlayerStats
+    );
+
+    // Only allow cancellation after the configured timeout
+    require!(
+        is_cancellable(game, clock.unix_timestamp),
+        GameError::TooEarlyToCancel
+    );
+
+    // Game must not be resolved or already cancelled
+    require!(!is_settled(&game.status()), GameError::AlreadySettled);
+
+    // A joiner who asked for auto-pick gets a side instead of a refund
+    require!(
+        !game.auto_pick_due() && !game.auto_pick_draw_due(),
+        GameError::AutoPickPending
+    );
+
+    game.next_seq();
+
+    // Refund nothing from an escrow that doesn't hold exactly what the game owes
+    if !audit_escrow(game, &ctx.accounts.escrow, clock.unix_timestamp)? {
+        return Ok(());
+    }
+
+    // Calculate cancellation fee (2% per player)
+    let (cancellation_fee, refund_amount) =
+        cancellation_amounts(game.bet_amount, game.cancellation_fee_bps)?;
+    debug_log!(
+        "game {} cancelled, refund {} fee {}",
+        game.game_id,
+        refund_amount,
+        cancellation_fee
+    );
+    let total_fees_collected = if game.player_b != Pubkey::default() {
+        cancellation_fee
+            .checked_mul(2)
+            .ok_or(GameError::MathOverflow)?
+    } else {
+        cancellation_fee
+    };
+
+    // Seeds for PDA signing
+    let seeds = &[
+        b"escrow",
+        game.creator.as_ref(),
+        &game.game_id.to_le_bytes(),
+        &[game.escrow_bump],
+    ];
+
+    // Refunds go back to whoever funded each stake
+    let refund_a = stake_refund_account(
+        &ctx.accounts.player_a,
+        &ctx.accounts.sponsor_a,
+        game.sponsor_a,
+    )?;
+    let refund_b = stake_refund_account(
+        &ctx.accounts.player_b,
+        &ctx.accounts.sponsor_b,
+        game.sponsor_b,
+    )?;
+
+    // Refund based on game state
+    if game.status() == GameStatus::WaitingForPlayer {
+        // Only player A joined, refund them minus fee
+        transfer_from_escrow(
+            &ctx.accounts.escrow,
+            refund_a.clone(),
+            &ctx.accounts.system_program,
+            &[seeds],
+            refund_amount,
+        )?;
+
+        // House gets the cancellation fee
+        transfer_from_escrow(
+            &ctx.accounts.escrow,
+            ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program,
+            &[seeds],
+            cancellation_fee,
+        )?;
+    } else if game.player_b != Pubkey::default() {
+        // Both players joined, refund both minus fees
+
+        // Refund player A
+        transfer_from_escrow(
+            &ctx.accounts.escrow,
+            refund_a.clone(),
+            &ctx.accounts.system_program,
+            &[seeds],
+            refund_amount,
+        )?;
+
+        // Refund player B
+        transfer_from_escrow(
+            &ctx.accounts.escrow,
+            refund_b.clone(),
+            &ctx.accounts.system_program,
+            &[seeds],
+            refund_amount,
+        )?;
+
+        // House gets both cancellation fees
+        transfer_from_escrow(
+            &ctx.accounts.escrow,
+            ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program,
+            &[seeds],
+            total_fees_collected,
+        )?;
+    }
+
+    close_escrow(
+        &ctx.accounts.escrow,
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+        &[seeds],
+    )?;
+
+    // A player refunded their own stake loses the cancellation fee; a sponsored
+    // stake's fee is the sponsor's loss
+    let season_id = ctx.accounts.global_state.current_season_id;
+    let self_funded_a = game.sponsor_a == Pubkey::default();
+    let self_funded_b = game.player_b != Pubkey::default() && game.sponsor_b == Pubkey::default();
+    let player_a = game.player_a;
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_a_stats,
+        player_a,
+        ctx.bumps.player_a_stats,
+    );
+    if self_funded_a {
+        record_cancellation(
+            &mut ctx.accounts.player_a_stats,
+            cancellation_fee,
+            season_id,
+        );
+    }
+    if let Some(stats) = ctx.accounts.player_b_stats.as_mut() {
+        init_player_stats_if_needed(stats, game.player_b, ctx.bumps.player_b_stats);
+        if self_funded_b {
+            record_cancellation(stats, cancellation_fee, season_id);
+        }
+    }
+
+    let status_before = game.status();
+    let was_listed = status_before == GameStatus::WaitingForPlayer;
+    game.transition(GameEvent::Cancel)?;
+
+    // A player cancelling their own stalled game is a plain cancel; the operator
+    // or the game's automation thread cancelling it is a crank handling the timeout
+    let canceller = ctx.accounts.canceller.key();
+    if canceller != game.player_a && canceller != game.player_b {
+        emit_cpi!(TimeoutHandled {
+            event_version: EVENT_VERSION,
+            game_id: game.game_id,
+            status: status_before.clone(),
+            phase_deadline: game.phase_deadline,
+            handled_by: canceller,
+            handled_at: clock.unix_timestamp,
+            seq: game.seq,
+        });
+    }
+    emit_cpi!(GameCancelled {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        cancelled_by: canceller,
+        status_before,
+        refund_per_player: refund_amount,
+        cancelled_at: clock.unix_timestamp,
+        total_fees_collected,
+        seq: game.seq,
+    });
+    if was_listed {
+        emit_delisted(game, DelistReason::Expired, clock.unix_timestamp);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        has_one = player @ GameError::Unauthorized,
+        seeds = [b"subscription", player.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription_escrow", subscription.key().as_ref()],
+        bump = subscription.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the subscription's budget
+    pub subscription_escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Stop joining games and take back what's left of the budget. Games already
+// joined still refund or repay into the escrow; subscribing again picks that up.
+pub(crate) fn handler(ctx: Context<CancelSubscription>) -> Result<()> {
+    let subscription = &ctx.accounts.subscription;
+    let refunded = ctx.accounts.subscription_escrow.lamports();
+
+    let subscription_key = subscription.key();
+    let seeds: &[&[u8]] = &[
+        b"subscription_escrow",
+        subscription_key.as_ref(),
+        &[subscription.escrow_bump],
+    ];
+    close_escrow(
+        &ctx.accounts.subscription_escrow,
+        &ctx.accounts.player,
+        &ctx.accounts.system_program,
+        &[seeds],
+    )?;
+
+    emit!(SubscriptionCancelled {
+        event_version: EVENT_VERSION,
+        player: subscription.player,
+        games_joined: subscription.games_joined,
+        refunded,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ChallengeRoom<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    #[account(seeds = [b"terms", challenger.key().as_ref()], bump = terms_acceptance.bump)]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", challenger.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"room", room.creator.as_ref(), &room.room_id.to_le_bytes()],
+        bump = room.bump
+    )]
+    pub room: Account<'info, Room>,
+
+    #[account(
+        mut,
+        seeds = [b"room_escrow", room.key().as_ref()],
+        bump = room.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the champion's and challengers' stakes
+    pub room_escrow: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Stake the room's amount and join the back of its queue, calling the side the
+// coin must land on for the challenger to win. `commitment` is the draw_commitment
+// of the secret the challenger defends with if they take the room.
+pub(crate) fn handler(
+    ctx: Context<ChallengeRoom>,
+    side: CoinSide,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let room = &mut ctx.accounts.room;
+    let challenger = ctx.accounts.challenger.key();
+    let clock = Clock::get()?;
+
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    require!(
+        challenger != room.champion && !room.is_queued(&challenger),
+        GameError::AlreadyInRoom
+    );
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_stats,
+        challenger,
+        ctx.bumps.player_stats,
+    );
+    verify_attestation(global_state, challenger, ctx.accounts.attestation.as_ref())?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    verify_loss_limit(global_state, &ctx.accounts.player_stats)?;
+    verify_not_frozen(&ctx.accounts.player_stats, clock.unix_timestamp)?;
+    verify_cooldown(
+        global_state,
+        &ctx.accounts.player_stats,
+        clock.unix_timestamp,
+    )?;
+
+    room.push_challenger(RoomChallenger {
+        player: challenger,
+        side,
+        commitment,
+    })?;
+    if room.queue_len == 1 {
+        schedule_next_bout(room, clock.slot);
+    }
+
+    deposit_stake(
+        ctx.accounts.challenger.to_account_info(),
+        &ctx.accounts.room_escrow,
+        &ctx.accounts.system_program,
+        room.stake,
+    )?;
+
+    emit!(ChallengeQueued {
+        event_version: EVENT_VERSION,
+        room_id: room.room_id,
+        creator: room.creator,
+        challenger,
+        side,
+        queue_len: room.queue_len,
+        bout_draw_slot: room.bout_draw_slot,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ClaimBracket<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"bracket".as_ref(), &bracket.bracket_id.to_le_bytes()],
+        bump = bracket.bump
+    )]
+    pub bracket: Box<Account<'info, Bracket>>,
+
+    #[account(
+        mut,
+        seeds = [b"bracket_escrow", bracket.key().as_ref()],
+        bump = bracket.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the bracket's stakes
+    pub bracket_escrow: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(mut, address = bracket.operator)]
+    /// CHECK: Gets the escrow's reserve back with the last claim
+    pub operator: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The champion claims the pot of a finished bracket; in a voided one every player
+// claims their stake back
+pub(crate) fn handler(ctx: Context<ClaimBracket>) -> Result<()> {
+    let bracket = &mut ctx.accounts.bracket;
+    let player = ctx.accounts.player.key();
+
+    let index = bracket.position(&player).ok_or(GameError::NotAPlayer)?;
+    let bit = 1u16 << index;
+    require!(bracket.claimed & bit == 0, GameError::NothingToClaim);
+    let stats = &mut ctx.accounts.player_stats;
+    let season_id = ctx.accounts.global_state.current_season_id;
+    let amount = match bracket.status {
+        BracketStatus::Running => return err!(GameError::NotYetDrawn),
+        BracketStatus::Finished if player == bracket.champion => {
+            record_pooled_payout(
+                stats,
+                bracket.prize,
+                season_id,
+                Clock::get()?.unix_timestamp,
+            );
+            bracket.prize
+        }
+        BracketStatus::Finished => return err!(GameError::NothingToClaim),
+        BracketStatus::Voided => {
+            record_pooled_refund(stats, bracket.stake, season_id);
+            bracket.stake
+        }
+    };
+    bracket.claimed |= bit;
+
+    let bracket_key = bracket.key();
+    transfer_from_escrow(
+        &ctx.accounts.bracket_escrow,
+        ctx.accounts.player.to_account_info(),
+        &ctx.accounts.system_program,
+        &[&[
+            b"bracket_escrow",
+            bracket_key.as_ref(),
+            &[bracket.escrow_bump],
+        ]],
+        amount,
+    )?;
+    close_bracket_escrow_if_done(
+        bracket,
+        &ctx.accounts.bracket_escrow,
+        &ctx.accounts.operator,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(BracketClaimed {
+        event_version: EVENT_VERSION,
+        bracket_id: bracket.bracket_id,
+        player,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ClaimLottery<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery".as_ref(), &lottery.lottery_id.to_le_bytes()],
+        bump = lottery.bump
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery_escrow", lottery.key().as_ref()],
+        bump = lottery.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the lottery's ticket sales
+    pub lottery_escrow: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        has_one = player @ GameError::Unauthorized,
+        seeds = [
+            b"lottery_ticket",
+            lottery.key().as_ref(),
+            &ticket.number.to_le_bytes()
+        ],
+        bump = ticket.bump
+    )]
+    pub ticket: Account<'info, LotteryTicket>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(mut, address = lottery.operator)]
+    /// CHECK: Gets the escrow's reserve back when the last ticket claims
+    pub operator: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Settle a ticket once its lottery is drawn or voided and close it. The winning
+// ticket takes the prize and the rest get only their rent back; a voided lottery
+// refunds every ticket's price.
+pub(crate) fn handler(ctx: Context<ClaimLottery>) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    let number = ctx.accounts.ticket.number;
+
+    let amount = match lottery.status {
+        LotteryStatus::Open => return err!(GameError::NotYetDrawn),
+        LotteryStatus::Drawn if number == lottery.winning_ticket => lottery.prize,
+        LotteryStatus::Drawn => 0,
+        LotteryStatus::Voided => lottery.ticket_price,
+    };
+    let stats = &mut ctx.accounts.player_stats;
+    let season_id = ctx.accounts.global_state.current_season_id;
+    if lottery.status == LotteryStatus::Voided {
+        record_pooled_refund(stats, amount, season_id);
+    } else {
+        record_pooled_payout(stats, amount, season_id, Clock::get()?.unix_timestamp);
+    }
+
+    let lottery_key = lottery.key();
+    transfer_from_escrow(
+        &ctx.accounts.lottery_escrow,
+        ctx.accounts.player.to_account_info(),
+        &ctx.accounts.system_program,
+        &[&[
+            b"lottery_escrow",
+            lottery_key.as_ref(),
+            &[lottery.escrow_bump],
+        ]],
+        amount,
+    )?;
+    lottery.claimed_tickets = lottery
+        .claimed_tickets
+        .checked_add(1)
+        .ok_or(GameError::MathOverflow)?;
+    close_lottery_escrow_if_done(
+        lottery,
+        &ctx.accounts.lottery_escrow,
+        &ctx.accounts.operator,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(LotteryClaimed {
+        event_version: EVENT_VERSION,
+        lottery_id: lottery.lottery_id,
+        player: ctx.accounts.player.key(),
+        number,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ClaimMarket<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump = market.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the market's stakes
+    pub market_escrow: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"market_position", market.key().as_ref(), player.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, MarketPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(mut, address = market.operator)]
+    /// CHECK: Gets the escrow's reserve back when the last position claims
+    pub operator: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Pay out a position once its market is settled and close it. A resolved market
+// pays the winning side's share of the payout pool, so a losing position gets
+// only its rent back; a voided market refunds every stake.
+pub(crate) fn handler(ctx: Context<ClaimMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let position = &ctx.accounts.position;
+    let stats = &mut ctx.accounts.player_stats;
+    let season_id = ctx.accounts.global_state.current_season_id;
+
+    let amount = match market.status {
+        MarketStatus::Open => return err!(GameError::NotYetDrawn),
+        MarketStatus::Resolved => {
+            let stake = match market.result {
+                CoinSide::Heads => position.heads,
+                CoinSide::Tails => position.tails,
+            };
+            let amount =
+                market_payout(stake, market.side_total(market.result), market.payout_pool)?;
+            record_pooled_payout(stats, amount, season_id, Clock::get()?.unix_timestamp);
+            amount
+        }
+        MarketStatus::Voided => {
+            let amount = position
+                .heads
+                .checked_add(position.tails)
+                .ok_or(GameError::MathOverflow)?;
+            record_pooled_refund(stats, amount, season_id);
+            amount
+        }
+    };
+
+    let market_key = market.key();
+    transfer_from_escrow(
+        &ctx.accounts.market_escrow,
+        ctx.accounts.player.to_account_info(),
+        &ctx.accounts.system_program,
+        &[&[b"market_escrow", market_key.as_ref(), &[market.escrow_bump]]],
+        amount,
+    )?;
+    market.claimed_positions = market
+        .claimed_positions
+        .checked_add(1)
+        .ok_or(GameError::MathOverflow)?;
+    close_market_escrow_if_done(
+        market,
+        &ctx.accounts.market_escrow,
+        &ctx.accounts.operator,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(MarketClaimed {
+        event_version: EVENT_VERSION,
+        market_id: market.market_id,
+        player: ctx.accounts.player.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", wallet.key().as_ref()],
+        bump = referrer.bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+}
+
+// Withdraw accrued referral fees, keeping the account rent-exempt
+pub(crate) fn handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let referrer_info = ctx.accounts.referrer.to_account_info();
+    let rent_reserve = Rent::get()?.minimum_balance(referrer_info.data_len());
+    let amount = referrer_info.lamports().saturating_sub(rent_reserve);
+    require!(amount > 0, GameError::NothingToClaim);
+
+    **referrer_info.try_borrow_mut_lamports()? -= amount;
+    **ctx
+        .accounts
+        .wallet
+        .to_account_info()
+        .try_borrow_mut_lamports()? += amount;
+
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.fees_claimed = referrer.fees_claimed.saturating_add(amount);
+
+    emit!(ReferralFeesClaimed {
+        event_version: EVENT_VERSION,
+        referrer: referrer.referrer,
+        amount,
+    });
+
+    Ok(())
+}
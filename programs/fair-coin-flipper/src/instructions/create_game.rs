@@ -0,0 +1,257 @@
+use crate::*;
+
+// Context Structs
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CreateGame<'info> {
+    #[account(mut)]
+    pub player_a: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    // Required once a terms version is set
+    #[account(
+        seeds = [b"terms", player_a.key().as_ref()],
+        bump = terms_acceptance.bump
+    )]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(mut)]
+    pub sponsor: Option<Signer<'info>>,
+
+    #[account(mut)]
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerNonce::INIT_SPACE,
+        seeds = [b"player_nonce", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_nonce: Account<'info, PlayerNonce>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_a_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Achievements::INIT_SPACE,
+        seeds = [b"achievements", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_a_achievements: Account<'info, Achievements>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_a_history: Box<Account<'info, GameHistory>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Game::INIT_SPACE,
+        seeds = [b"game", player_a.key().as_ref(), &game_id.to_le_bytes()],
+        bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(mut)]
+    /// CHECK: The game's escrow PDA, or the shared escrow vault in vault mode; checked in the handler
+    pub escrow: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<CreateGame>,
+    game_id: u64,
+    bet_amount: u64,
+    allowed_joiners: Vec<Pubkey>,
+    join_lock_seconds: i64,
+    tags: u8,
+    repay_sponsor: bool,
+) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_init()?;
+    let clock = Clock::get()?;
+
+    // Validate bet amount
+    let global_state = &ctx.accounts.global_state;
+    require!(
+        bet_amount >= global_state.min_bet_amount,
+        GameError::BetTooLow
+    );
+    require!(
+        bet_amount <= global_state.max_bet_amount,
+        GameError::BetTooHigh
+    );
+    require!(tags & !ALL_TAGS == 0, GameError::InvalidTags);
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    verify_attestation(
+        global_state,
+        ctx.accounts.player_a.key(),
+        ctx.accounts.attestation.as_ref(),
+    )?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    verify_loss_limit(global_state, &ctx.accounts.player_a_stats)?;
+    verify_not_frozen(&ctx.accounts.player_a_stats, clock.unix_timestamp)?;
+    verify_cooldown(
+        global_state,
+        &ctx.accounts.player_a_stats,
+        clock.unix_timestamp,
+    )?;
+
+    // Game IDs come from the player's nonce so every game PDA is derivable.
+    // The nonce only moves forward, so an id can never be re-initialized
+    // even after its game account is closed.
+    let player_nonce = &mut ctx.accounts.player_nonce;
+    if player_nonce.player == Pubkey::default() {
+        player_nonce.player = ctx.accounts.player_a.key();
+        player_nonce.bump = ctx.bumps.player_nonce;
+    }
+    require!(
+        game_id == player_nonce.next_game_id,
+        GameError::InvalidGameId
+    );
+    player_nonce.next_game_id = player_nonce
+        .next_game_id
+        .checked_add(1)
+        .ok_or(GameError::InvalidGameId)?;
+
+    // Validate optional anti-snipe lock
+    require!(
+        allowed_joiners.len() <= MAX_ALLOWED_JOINERS,
+        GameError::TooManyAllowedJoiners
+    );
+    require!(
+        (0..=MAX_JOIN_LOCK_SECONDS).contains(&join_lock_seconds),
+        GameError::InvalidJoinLock
+    );
+    require!(
+        join_lock_seconds == 0 || !allowed_joiners.is_empty(),
+        GameError::InvalidJoinLock
+    );
+
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_a_stats,
+        ctx.accounts.player_a.key(),
+        ctx.bumps.player_a_stats,
+    );
+    init_achievements_if_needed(
+        &mut ctx.accounts.player_a_achievements,
+        ctx.accounts.player_a.key(),
+        ctx.bumps.player_a_achievements,
+    );
+    init_game_history_if_needed(
+        &mut ctx.accounts.player_a_history,
+        ctx.accounts.player_a.key(),
+        ctx.bumps.player_a_history,
+    );
+    set_referrer_if_needed(&mut ctx.accounts.player_a_stats, &mut ctx.accounts.referrer);
+
+    // Initialize game account
+    init_game(
+        game,
+        global_state,
+        game_id,
+        ctx.accounts.player_a.key(),
+        bet_amount,
+        tags,
+        clock.unix_timestamp,
+    )?;
+
+    // Optional third-party sponsor funds the stake; the player keeps credit
+    game.sponsor_a = ctx
+        .accounts
+        .sponsor
+        .as_ref()
+        .map_or(Pubkey::default(), |sponsor| sponsor.key());
+    let repay = repay_sponsor && game.sponsor_a != Pubkey::default();
+    game.set_flag(FLAG_REPAY_SPONSOR_A, repay);
+    game.sponsor_b = Pubkey::default();
+
+    // Anti-snipe lock: only allowlisted/challenged players may join until it expires
+    game.allowed_joiners = [Pubkey::default(); MAX_ALLOWED_JOINERS];
+    for (slot, joiner) in game.allowed_joiners.iter_mut().zip(allowed_joiners.iter()) {
+        *slot = *joiner;
+    }
+    game.join_locked_until = clock.unix_timestamp + join_lock_seconds;
+
+    // PDA bumps
+    let (escrow_key, escrow_bump) =
+        new_game_escrow(global_state, ctx.accounts.player_a.key(), game_id)?;
+    require_keys_eq!(
+        ctx.accounts.escrow.key(),
+        escrow_key,
+        GameError::InvalidEscrowAccount
+    );
+    game.bump = ctx.bumps.game;
+    game.escrow_bump = escrow_bump;
+
+    // Transfer bet amount to escrow from the player or their sponsor
+    let funder = match &ctx.accounts.sponsor {
+        Some(sponsor) => sponsor.to_account_info(),
+        None => ctx.accounts.player_a.to_account_info(),
+    };
+    deposit_stake(
+        funder,
+        &ctx.accounts.escrow,
+        &ctx.accounts.system_program,
+        bet_amount,
+    )?;
+
+    // The rent payer also covers a per-game escrow's rent-exempt reserve so partial
+    // payouts never leave it in a rent-paying state; the creator gets it back
+    if !game.flag(FLAG_VAULT_ESCROW) {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(0),
+        )?;
+    }
+
+    emit!(GameCreated {
+        event_version: EVENT_VERSION,
+        game_id,
+        player_a: game.player_a,
+        bet_amount,
+        join_locked_until: game.join_locked_until,
+        tags,
+        seq: game.seq,
+    });
+    emit_listed(game, clock.unix_timestamp);
+
+    Ok(())
+}
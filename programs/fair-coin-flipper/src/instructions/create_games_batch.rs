@@ -0,0 +1,210 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CreateGamesBatch<'info> {
+    #[account(mut)]
+    pub player_a: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    // Required once a terms version is set
+    #[account(
+        seeds = [b"terms", player_a.key().as_ref()],
+        bump = terms_acceptance.bump
+    )]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerNonce::INIT_SPACE,
+        seeds = [b"player_nonce", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_nonce: Account<'info, PlayerNonce>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_a_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Achievements::INIT_SPACE,
+        seeds = [b"achievements", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_a_achievements: Account<'info, Achievements>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", player_a.key().as_ref()],
+        bump
+    )]
+    pub player_a_history: Box<Account<'info, GameHistory>>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: one writable (game, escrow) pair per bet amount, in id order;
+    // in vault mode every pair's escrow is the shared vault
+}
+
+// Post several open games at different stakes in one transaction. Each game takes
+// the next (game, escrow) pair from remaining_accounts and the next id from the
+// player's nonce, starting at first_game_id. Batched games have no sponsor or
+// join lock; use create_game for those.
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateGamesBatch<'info>>,
+    first_game_id: u64,
+    bet_amounts: Vec<u64>,
+    tags: u8,
+) -> Result<()> {
+    require!(
+        !bet_amounts.is_empty() && bet_amounts.len() <= MAX_BATCH_GAMES,
+        GameError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == bet_amounts.len() * 2,
+        GameError::InvalidBatchAccounts
+    );
+
+    let global_state = &ctx.accounts.global_state;
+    require!(tags & !ALL_TAGS == 0, GameError::InvalidTags);
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    verify_attestation(
+        global_state,
+        ctx.accounts.player_a.key(),
+        ctx.accounts.attestation.as_ref(),
+    )?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    let now = Clock::get()?.unix_timestamp;
+    verify_loss_limit(global_state, &ctx.accounts.player_a_stats)?;
+    verify_not_frozen(&ctx.accounts.player_a_stats, now)?;
+    verify_cooldown(global_state, &ctx.accounts.player_a_stats, now)?;
+
+    let player = ctx.accounts.player_a.key();
+    let player_nonce = &mut ctx.accounts.player_nonce;
+    if player_nonce.player == Pubkey::default() {
+        player_nonce.player = player;
+        player_nonce.bump = ctx.bumps.player_nonce;
+    }
+    require!(
+        first_game_id == player_nonce.next_game_id,
+        GameError::InvalidGameId
+    );
+
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_a_stats,
+        player,
+        ctx.bumps.player_a_stats,
+    );
+    init_achievements_if_needed(
+        &mut ctx.accounts.player_a_achievements,
+        player,
+        ctx.bumps.player_a_achievements,
+    );
+    init_game_history_if_needed(
+        &mut ctx.accounts.player_a_history,
+        player,
+        ctx.bumps.player_a_history,
+    );
+
+    let reserve = Rent::get()?.minimum_balance(0);
+    for (&bet_amount, pair) in bet_amounts.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let (game_info, escrow) = (&pair[0], &pair[1]);
+        require!(
+            bet_amount >= global_state.min_bet_amount,
+            GameError::BetTooLow
+        );
+        require!(
+            bet_amount <= global_state.max_bet_amount,
+            GameError::BetTooHigh
+        );
+
+        let game_id = player_nonce.next_game_id;
+        player_nonce.next_game_id = game_id.checked_add(1).ok_or(GameError::InvalidGameId)?;
+
+        let id_bytes = game_id.to_le_bytes();
+        let (game_key, bump) =
+            Pubkey::find_program_address(&[b"game", player.as_ref(), &id_bytes], ctx.program_id);
+        let (escrow_key, escrow_bump) = new_game_escrow(global_state, player, game_id)?;
+        require!(
+            game_info.key() == game_key && escrow.key() == escrow_key,
+            GameError::InvalidBatchAccounts
+        );
+
+        create_pda_account(
+            &ctx.accounts.payer.to_account_info(),
+            game_info,
+            &ctx.accounts.system_program.to_account_info(),
+            8 + Game::INIT_SPACE,
+            &[b"game", player.as_ref(), &id_bytes, &[bump]],
+        )?;
+        let loader = AccountLoader::<Game>::try_from_unchecked(ctx.program_id, game_info)?;
+        let seq = {
+            let game = &mut loader.load_init()?;
+            init_game(game, global_state, game_id, player, bet_amount, tags, now)?;
+            game.join_locked_until = now;
+            game.bump = bump;
+            game.escrow_bump = escrow_bump;
+            game.seq
+        };
+        // Writes the discriminator, which Anchor only does itself for declared accounts
+        loader.exit(ctx.program_id)?;
+
+        // Stake from the player, escrow reserve from the rent payer, as in create_game
+        deposit_stake(
+            ctx.accounts.player_a.to_account_info(),
+            escrow,
+            &ctx.accounts.system_program,
+            bet_amount,
+        )?;
+        if !global_state.vault_escrow {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: escrow.clone(),
+                    },
+                ),
+                reserve,
+            )?;
+        }
+
+        emit!(GameCreated {
+            event_version: EVENT_VERSION,
+            game_id,
+            player_a: player,
+            bet_amount,
+            join_locked_until: now,
+            tags,
+            seq,
+        });
+        emit_listed(&*loader.load()?, now);
+    }
+
+    Ok(())
+}
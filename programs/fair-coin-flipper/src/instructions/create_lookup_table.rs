@@ -0,0 +1,67 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ManageLookupTable<'info> {
+    #[account(
+        mut,
+        constraint = operator.key() == global_state.operator
+            || operator.key() == global_state.authority @ GameError::Unauthorized
+    )]
+    pub operator: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    /// CHECK: Address derived from the authority and recent slot; validated by the lookup table program
+    pub lookup_table: AccountInfo<'info>,
+
+    #[account(seeds = [b"lookup_authority", operator.key().as_ref()], bump)]
+    /// CHECK: PDA that owns the operator's lookup table
+    pub lookup_authority: AccountInfo<'info>,
+
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    /// CHECK: Address lookup table program
+    pub address_lookup_table_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Create an address lookup table holding the accounts every settlement
+// shares (see shared_lookup_addresses), so resolution transactions can
+// reference them by index. The table is owned by a per-operator PDA, so
+// only this program decides what goes into it.
+pub(crate) fn handler(ctx: Context<ManageLookupTable>, recent_slot: u64) -> Result<()> {
+    let operator = ctx.accounts.operator.key();
+    let authority_bump = ctx.bumps.lookup_authority;
+    let (table, table_bump) = Pubkey::find_program_address(
+        &[
+            ctx.accounts.lookup_authority.key().as_ref(),
+            &recent_slot.to_le_bytes(),
+        ],
+        &ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+    );
+    require_keys_eq!(
+        ctx.accounts.lookup_table.key(),
+        table,
+        GameError::InvalidLookupTable
+    );
+
+    // CreateLookupTable { recent_slot, bump_seed }
+    let mut data = 0u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&recent_slot.to_le_bytes());
+    data.push(table_bump);
+    invoke_lookup_table(ctx.accounts, data, operator, authority_bump)?;
+
+    let addresses = shared_lookup_addresses(&ctx.accounts.global_state);
+    append_lookup_addresses(ctx.accounts, &addresses, operator, authority_bump)?;
+
+    emit!(LookupTableUpdated {
+        event_version: EVENT_VERSION,
+        operator,
+        lookup_table: table,
+        added: addresses.len() as u8,
+    });
+
+    Ok(())
+}
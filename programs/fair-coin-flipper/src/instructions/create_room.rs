@@ -0,0 +1,121 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: u64)]
+pub struct CreateRoom<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    #[account(seeds = [b"terms", creator.key().as_ref()], bump = terms_acceptance.bump)]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", creator.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Room::INIT_SPACE,
+        seeds = [b"room", creator.key().as_ref(), &room_id.to_le_bytes()],
+        bump
+    )]
+    pub room: Account<'info, Room>,
+
+    #[account(mut, seeds = [b"room_escrow", room.key().as_ref()], bump)]
+    /// CHECK: System-owned PDA holding the champion's and challengers' stakes
+    pub room_escrow: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Open a king-of-the-hill room with the creator as its first champion. Every bout
+// in the room is played for `stake`. `champion_commitment` is the draw_commitment
+// of the secret the creator reveals to settle their first bout.
+pub(crate) fn handler(
+    ctx: Context<CreateRoom>,
+    room_id: u64,
+    stake: u64,
+    champion_commitment: [u8; 32],
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let creator = ctx.accounts.creator.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(stake >= global_state.min_bet_amount, GameError::BetTooLow);
+    require!(stake <= global_state.max_bet_amount, GameError::BetTooHigh);
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_stats,
+        creator,
+        ctx.bumps.player_stats,
+    );
+    verify_attestation(global_state, creator, ctx.accounts.attestation.as_ref())?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    verify_loss_limit(global_state, &ctx.accounts.player_stats)?;
+    verify_not_frozen(&ctx.accounts.player_stats, now)?;
+    verify_cooldown(global_state, &ctx.accounts.player_stats, now)?;
+
+    ctx.accounts.room.set_inner(Room {
+        room_id,
+        creator,
+        stake,
+        house_fee_bps: global_state.house_fee_bps,
+        champion: creator,
+        champion_commitment,
+        streak: 0,
+        winnings: 0,
+        bouts: 0,
+        bout_draw_slot: 0,
+        queue: [RoomChallenger::EMPTY; MAX_ROOM_QUEUE],
+        queue_len: 0,
+        escrow_bump: ctx.bumps.room_escrow,
+        bump: ctx.bumps.room,
+    });
+
+    // Returned to the creator when the room closes
+    fund_draw_escrow(
+        &ctx.accounts.payer,
+        &ctx.accounts.room_escrow,
+        &ctx.accounts.system_program,
+    )?;
+    deposit_stake(
+        ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.room_escrow,
+        &ctx.accounts.system_program,
+        stake,
+    )?;
+
+    emit!(RoomCreated {
+        event_version: EVENT_VERSION,
+        room_id,
+        creator,
+        stake,
+        house_fee_bps: global_state.house_fee_bps,
+    });
+
+    Ok(())
+}
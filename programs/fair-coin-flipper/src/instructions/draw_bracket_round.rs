@@ -0,0 +1,112 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct DrawBracketRound<'info> {
+    #[account(mut, address = bracket.operator @ GameError::Unauthorized)]
+    pub operator: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"bracket".as_ref(), &bracket.bracket_id.to_le_bytes()],
+        bump = bracket.bump
+    )]
+    pub bracket: Box<Account<'info, Bracket>>,
+
+    #[account(
+        mut,
+        seeds = [b"bracket_escrow", bracket.key().as_ref()],
+        bump = bracket.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the bracket's stakes
+    pub bracket_escrow: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump = global_state.treasury_bump)]
+    /// CHECK: Program-derived fee treasury; holds lamports only
+    pub treasury: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    /// CHECK: SlotHashes sysvar, read raw by slot_hash_at
+    pub slot_hashes: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Draw every match of the current round by revealing its secret, and commit to the
+// secret for the next round. When one player is left they are the champion: the
+// house fee comes off the combined pot and the champion claims the rest.
+pub(crate) fn handler(
+    ctx: Context<DrawBracketRound>,
+    secret: u64,
+    next_commitment: [u8; 32],
+) -> Result<()> {
+    let bracket = &mut ctx.accounts.bracket;
+    require!(
+        bracket.status == BracketStatus::Running,
+        GameError::AlreadySettled
+    );
+    let (slot_hash, randomness) = reveal_draw(
+        &bracket.secret_commitment,
+        secret,
+        bracket.round_draw_slot,
+        &ctx.accounts.slot_hashes,
+    )?;
+
+    let winners =
+        bracket_round_winners(&bracket.alive[..bracket.alive_count as usize], &randomness);
+    bracket.alive = [Pubkey::default(); MAX_BRACKET_PLAYERS];
+    bracket.alive[..winners.len()].copy_from_slice(&winners);
+    bracket.alive_count = winners.len() as u8;
+    bracket.round += 1;
+    bracket.secret_commitment = next_commitment;
+    bracket.round_draw_slot = Clock::get()?.slot.saturating_add(BRACKET_ROUND_DELAY_SLOTS);
+
+    emit!(BracketRoundDrawn {
+        event_version: EVENT_VERSION,
+        bracket_id: bracket.bracket_id,
+        round: bracket.round,
+        secret,
+        slot_hash,
+        randomness,
+        winners: winners.clone(),
+    });
+
+    if winners.len() > 1 {
+        return Ok(());
+    }
+
+    let pot = bracket
+        .stake
+        .checked_mul(bracket.player_count as u64)
+        .ok_or(GameError::MathOverflow)?;
+    let house_fee = bps_of(pot, bracket.house_fee_bps)?;
+    bracket.champion = winners[0];
+    bracket.prize = pot - house_fee;
+    bracket.status = BracketStatus::Finished;
+
+    let bracket_key = bracket.key();
+    transfer_from_escrow(
+        &ctx.accounts.bracket_escrow,
+        ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.system_program,
+        &[&[
+            b"bracket_escrow",
+            bracket_key.as_ref(),
+            &[bracket.escrow_bump],
+        ]],
+        house_fee,
+    )?;
+
+    emit!(BracketWon {
+        event_version: EVENT_VERSION,
+        bracket_id: bracket.bracket_id,
+        champion: bracket.champion,
+        rounds: bracket.round,
+        house_fee,
+        prize: bracket.prize,
+    });
+
+    Ok(())
+}
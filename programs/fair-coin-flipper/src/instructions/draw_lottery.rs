@@ -0,0 +1,100 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct DrawLottery<'info> {
+    #[account(mut, address = lottery.operator @ GameError::Unauthorized)]
+    pub operator: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery".as_ref(), &lottery.lottery_id.to_le_bytes()],
+        bump = lottery.bump
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery_escrow", lottery.key().as_ref()],
+        bump = lottery.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the lottery's ticket sales
+    pub lottery_escrow: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump = global_state.treasury_bump)]
+    /// CHECK: Program-derived fee treasury; holds lamports only
+    pub treasury: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    /// CHECK: SlotHashes sysvar, read raw by slot_hash_at
+    pub slot_hashes: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Pick the winning ticket by revealing the operator's secret. The house fee comes
+// off the pot and the winning ticket's holder claims the rest. A lottery that sold
+// no tickets is voided instead, and one the operator doesn't draw in time becomes
+// voidable like a market.
+pub(crate) fn handler(ctx: Context<DrawLottery>, secret: u64) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    require!(
+        lottery.status == LotteryStatus::Open,
+        GameError::AlreadySettled
+    );
+    let (slot_hash, randomness) = reveal_draw(
+        &lottery.secret_commitment,
+        secret,
+        lottery.draw_slot,
+        &ctx.accounts.slot_hashes,
+    )?;
+    lottery.randomness = randomness;
+
+    if lottery.entries == 0 {
+        void_open_lottery(lottery, ctx.accounts.operator.key());
+        return close_lottery_escrow_if_done(
+            lottery,
+            &ctx.accounts.lottery_escrow,
+            &ctx.accounts.operator,
+            &ctx.accounts.system_program,
+        );
+    }
+
+    let pot = lottery
+        .ticket_price
+        .checked_mul(lottery.entries as u64)
+        .ok_or(GameError::MathOverflow)?;
+    let house_fee = bps_of(pot, lottery.house_fee_bps)?;
+    lottery.winning_ticket = draw_index(&randomness, lottery.entries);
+    lottery.prize = pot - house_fee;
+    lottery.status = LotteryStatus::Drawn;
+
+    let lottery_key = lottery.key();
+    transfer_from_escrow(
+        &ctx.accounts.lottery_escrow,
+        ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.system_program,
+        &[&[
+            b"lottery_escrow",
+            lottery_key.as_ref(),
+            &[lottery.escrow_bump],
+        ]],
+        house_fee,
+    )?;
+
+    emit!(LotteryDrawn {
+        event_version: EVENT_VERSION,
+        lottery_id: lottery.lottery_id,
+        winning_ticket: lottery.winning_ticket,
+        secret,
+        slot_hash,
+        randomness,
+        entries: lottery.entries,
+        house_fee,
+        prize: lottery.prize,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct EmergencyRecover<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ GameError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(mut, address = game.load()?.player_a)]
+    /// CHECK: Player A account for refunds
+    pub player_a: AccountInfo<'info>,
+
+    // Before anyone joins there is no player B; any writable account may fill the slot
+    #[account(
+        mut,
+        constraint = game.load()?.player_b == Pubkey::default() || player_b.key() == game.load()?.player_b
+    )]
+    /// CHECK: Player B account for refunds
+    pub player_b: AccountInfo<'info>,
+
+    #[account(mut, address = game.load()?.sponsor_a)]
+    /// CHECK: Player A's sponsor, required only when the stake was sponsored
+    pub sponsor_a: Option<AccountInfo<'info>>,
+
+    #[account(mut, address = game.load()?.sponsor_b)]
+    /// CHECK: Player B's sponsor, required only when the stake was sponsored
+    pub sponsor_b: Option<AccountInfo<'info>>,
+
+    #[account(mut, address = game.load()?.creator)]
+    /// CHECK: Original creator; receives the escrow's rent reserve when it closes
+    pub creator: AccountInfo<'info>,
+
+    #[account(mut, address = escrow_address(&*game.load()?)?)]
+    /// CHECK: The game's escrow PDA, or the shared escrow vault for vault-mode games
+    pub escrow: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"admin_log"], bump = admin_log.bump)]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Refund stakes from a game stuck in an unrecoverable state, without fees.
+// Restricted to the authority and to games older than the safety threshold.
+pub(crate) fn handler(ctx: Context<EmergencyRecover>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(!is_settled(&game.status()), GameError::AlreadySettled);
+    // Halted games can be recovered straight away
+    require!(
+        game.flag(FLAG_SETTLEMENT_HALTED)
+            || clock.unix_timestamp - game.created_at >= EMERGENCY_RECOVERY_MIN_AGE_SECONDS,
+        GameError::TooEarlyForRecovery
+    );
+
+    // The rent-exempt reserve stays behind; only stakes are recoverable here. From
+    // the shared vault a game can recover at most its own ledger entry.
+    let escrow = &ctx.accounts.escrow;
+    let mut escrow_balance = escrow
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(escrow.data_len()));
+    let vault_entry = expected_escrow_balance(&game.status(), game.bet_amount, 0)?;
+    if is_vault(escrow) {
+        escrow_balance = escrow_balance.min(vault_entry);
+    }
+    let refunded_a = game.bet_amount.min(escrow_balance);
+    let refunded_b = if game.player_b != Pubkey::default() {
+        game.bet_amount.min(escrow_balance - refunded_a)
+    } else {
+        0
+    };
+
+    let refund_a = stake_refund_account(
+        &ctx.accounts.player_a,
+        &ctx.accounts.sponsor_a,
+        game.sponsor_a,
+    )?;
+    let refund_b = stake_refund_account(
+        &ctx.accounts.player_b,
+        &ctx.accounts.sponsor_b,
+        game.sponsor_b,
+    )?;
+
+    let seeds = &[
+        b"escrow",
+        game.creator.as_ref(),
+        &game.game_id.to_le_bytes(),
+        &[game.escrow_bump],
+    ];
+
+    for (to, amount) in [(refund_a, refunded_a), (refund_b, refunded_b)] {
+        if amount > 0 {
+            transfer_from_escrow(
+                &ctx.accounts.escrow,
+                to,
+                &ctx.accounts.system_program,
+                &[seeds],
+                amount,
+            )?;
+        }
+    }
+
+    // Whatever part of a vault entry the vault couldn't cover leaves the ledger too
+    if is_vault(escrow) {
+        release_vault_liability(escrow, vault_entry - refunded_a - refunded_b)?;
+    }
+
+    close_escrow(
+        &ctx.accounts.escrow,
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+        &[seeds],
+    )?;
+
+    let status_before = game.status();
+    let was_listed = status_before == GameStatus::WaitingForPlayer;
+    game.transition(GameEvent::Cancel)?;
+    game.next_seq();
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::EmergencyRecover,
+        ctx.accounts.authority.key(),
+        ctx.accounts.game.key(),
+        escrow_balance,
+        clock.unix_timestamp,
+    );
+
+    emit!(EmergencyRecovery {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        authority: ctx.accounts.authority.key(),
+        status_before,
+        escrow_balance,
+        refunded_a,
+        refunded_b,
+        recovered_at: clock.unix_timestamp,
+        seq: game.seq,
+    });
+    if was_listed {
+        emit_delisted(game, DelistReason::Recovered, clock.unix_timestamp);
+    }
+
+    Ok(())
+}
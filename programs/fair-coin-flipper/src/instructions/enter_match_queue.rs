@@ -0,0 +1,114 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(stake: u64)]
+pub struct EnterMatchQueue<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    #[account(seeds = [b"terms", player.key().as_ref()], bump = terms_acceptance.bump)]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MatchQueue::INIT_SPACE,
+        seeds = [b"match_queue".as_ref(), &stake.to_le_bytes()],
+        bump
+    )]
+    pub match_queue: Account<'info, MatchQueue>,
+
+    #[account(mut, seeds = [b"match_queue_escrow", match_queue.key().as_ref()], bump)]
+    /// CHECK: System-owned PDA holding the queued players' stakes
+    pub match_queue_escrow: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Stake `stake` and wait in that stake's queue for the operator to start a bracket
+pub(crate) fn handler(ctx: Context<EnterMatchQueue>, stake: u64) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let match_queue = &mut ctx.accounts.match_queue;
+    let player = ctx.accounts.player.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(stake >= global_state.min_bet_amount, GameError::BetTooLow);
+    require!(stake <= global_state.max_bet_amount, GameError::BetTooHigh);
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_stats,
+        player,
+        ctx.bumps.player_stats,
+    );
+    verify_attestation(global_state, player, ctx.accounts.attestation.as_ref())?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    verify_loss_limit(global_state, &ctx.accounts.player_stats)?;
+    verify_not_frozen(&ctx.accounts.player_stats, now)?;
+    verify_cooldown(global_state, &ctx.accounts.player_stats, now)?;
+    record_pooled_stake(
+        &mut ctx.accounts.player_stats,
+        stake,
+        global_state.current_season_id,
+    );
+
+    // The first player at a stake creates its queue and funds the escrow's reserve,
+    // which stays for as long as the queue does
+    if match_queue.stake == 0 {
+        match_queue.stake = stake;
+        match_queue.escrow_bump = ctx.bumps.match_queue_escrow;
+        match_queue.bump = ctx.bumps.match_queue;
+        fund_draw_escrow(
+            &ctx.accounts.payer,
+            &ctx.accounts.match_queue_escrow,
+            &ctx.accounts.system_program,
+        )?;
+    }
+    require!(
+        match_queue.position(&player).is_none(),
+        GameError::AlreadyInMatchQueue
+    );
+    match_queue.push(player)?;
+
+    deposit_stake(
+        ctx.accounts.player.to_account_info(),
+        &ctx.accounts.match_queue_escrow,
+        &ctx.accounts.system_program,
+        stake,
+    )?;
+
+    emit!(MatchQueueEntered {
+        event_version: EVENT_VERSION,
+        stake,
+        player,
+        queue_len: match_queue.len,
+    });
+
+    Ok(())
+}
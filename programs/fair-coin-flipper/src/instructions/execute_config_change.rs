@@ -0,0 +1,70 @@
+use crate::*;
+
+// Apply the queued change once its timelock has elapsed
+pub(crate) fn handler(ctx: Context<AdminAction>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let pending = take_pending_change(global_state)?;
+    require!(
+        clock.unix_timestamp >= pending.eta,
+        GameError::TimelockNotElapsed
+    );
+
+    let (previous_house_fee_bps, previous_cancellation_fee_bps) = (
+        global_state.house_fee_bps,
+        global_state.cancellation_fee_bps,
+    );
+    match pending.change {
+        ConfigChange::HouseFee { bps } => global_state.house_fee_bps = bps,
+        ConfigChange::CancellationFee { bps } => global_state.cancellation_fee_bps = bps,
+        ConfigChange::BetLimits { min_bet, max_bet } => {
+            global_state.min_bet_amount = min_bet;
+            global_state.max_bet_amount = max_bet;
+        }
+        ConfigChange::TimelockDelay { seconds } => {
+            global_state.timelock_delay_seconds = seconds;
+        }
+        ConfigChange::HouseWallet { wallet } => {
+            emit!(HouseWalletUpdated {
+                event_version: EVENT_VERSION,
+                previous_wallet: global_state.house_wallet,
+                new_wallet: wallet,
+                updated_at: clock.unix_timestamp,
+            });
+            global_state.house_wallet = wallet;
+        }
+    }
+
+    if matches!(
+        pending.change,
+        ConfigChange::HouseFee { .. } | ConfigChange::CancellationFee { .. }
+    ) {
+        emit!(FeeUpdated {
+            event_version: EVENT_VERSION,
+            previous_house_fee_bps,
+            house_fee_bps: global_state.house_fee_bps,
+            previous_cancellation_fee_bps,
+            cancellation_fee_bps: global_state.cancellation_fee_bps,
+            updated_at: clock.unix_timestamp,
+        });
+    }
+
+    let (target, value) = config_change_summary(&pending.change);
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::ExecuteConfigChange,
+        ctx.accounts.authority.key(),
+        target,
+        value,
+        clock.unix_timestamp,
+    );
+
+    emit!(ConfigChangeExecuted {
+        event_version: EVENT_VERSION,
+        change: pending.change,
+        executed_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
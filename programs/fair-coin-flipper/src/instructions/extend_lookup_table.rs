@@ -0,0 +1,42 @@
+use crate::*;
+
+// Append shared accounts that are missing from the operator's table, e.g. a
+// hook program or archive tree configured after the table was created
+pub(crate) fn handler(ctx: Context<ManageLookupTable>) -> Result<()> {
+    let operator = ctx.accounts.operator.key();
+    require_keys_eq!(
+        *ctx.accounts.lookup_table.owner,
+        ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+        GameError::InvalidLookupTable
+    );
+
+    let existing: Vec<Pubkey> = {
+        let data = ctx.accounts.lookup_table.try_borrow_data()?;
+        require!(
+            data.len() >= LOOKUP_TABLE_META_SIZE,
+            GameError::InvalidLookupTable
+        );
+        data[LOOKUP_TABLE_META_SIZE..]
+            .chunks_exact(32)
+            .map(|key| Pubkey::try_from(key).unwrap())
+            .collect()
+    };
+    let missing: Vec<Pubkey> = shared_lookup_addresses(&ctx.accounts.global_state)
+        .into_iter()
+        .filter(|address| !existing.contains(address))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    append_lookup_addresses(ctx.accounts, &missing, operator, ctx.bumps.lookup_authority)?;
+
+    emit!(LookupTableUpdated {
+        event_version: EVENT_VERSION,
+        operator,
+        lookup_table: ctx.accounts.lookup_table.key(),
+        added: missing.len() as u8,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ForfeitBout<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"room", room.creator.as_ref(), &room.room_id.to_le_bytes()],
+        bump = room.bump
+    )]
+    pub room: Account<'info, Room>,
+
+    #[account(
+        mut,
+        seeds = [b"room_escrow", room.key().as_ref()],
+        bump = room.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the champion's and challengers' stakes
+    pub room_escrow: AccountInfo<'info>,
+
+    #[account(mut, address = room.head().ok_or(GameError::NoBoutPending)?.player)]
+    /// CHECK: The challenger at the head of the queue, who takes the bout
+    pub challenger: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", room.champion.as_ref()],
+        bump = champion_stats.bump
+    )]
+    pub champion_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", challenger.key().as_ref()],
+        bump = challenger_stats.bump
+    )]
+    pub challenger_stats: Account<'info, PlayerStats>,
+
+    #[account(mut, seeds = [b"treasury"], bump = global_state.treasury_bump)]
+    /// CHECK: Program-derived fee treasury; holds lamports only
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can award a bout to the challenger once the champion has let its draw
+// window pass without revealing. The payout and the new champion are the same as
+// if the challenger had won the flip.
+pub(crate) fn handler(ctx: Context<ForfeitBout>) -> Result<()> {
+    let room = &mut ctx.accounts.room;
+    let clock = Clock::get()?;
+    let slot = clock.slot;
+    require!(
+        is_past_draw_window(room.bout_draw_slot, slot),
+        GameError::BoutStillDrawable
+    );
+    let champion = room.champion;
+
+    let (house_fee, winnings) = pay_bout(
+        room,
+        &ctx.accounts.room_escrow,
+        ctx.accounts.challenger.to_account_info(),
+        &ctx.accounts.treasury,
+        &ctx.accounts.system_program,
+    )?;
+    record_bout(
+        &mut ctx.accounts.champion_stats,
+        &mut ctx.accounts.challenger_stats,
+        true,
+        room.stake,
+        winnings,
+        ctx.accounts.global_state.current_season_id,
+        clock.unix_timestamp,
+    )?;
+    let challenger = finish_bout(room, true, winnings, slot)?;
+
+    emit!(BoutForfeited {
+        event_version: EVENT_VERSION,
+        room_id: room.room_id,
+        creator: room.creator,
+        champion,
+        challenger: challenger.player,
+        house_fee,
+        winnings,
+    });
+
+    Ok(())
+}
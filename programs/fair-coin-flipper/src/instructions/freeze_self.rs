@@ -0,0 +1,49 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct FreezeSelf<'info> {
+    pub player: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Block the caller from wagering until `until`. A freeze lasts at most 30 days
+// per call and can be extended but not shortened. Reveals, resolution and
+// cancellation of games already in progress still go through.
+pub(crate) fn handler(ctx: Context<FreezeSelf>, until: i64) -> Result<()> {
+    let stats = &mut ctx.accounts.player_stats;
+    let player = ctx.accounts.player.key();
+    let now = Clock::get()?.unix_timestamp;
+    init_player_stats_if_needed(stats, player, ctx.bumps.player_stats);
+
+    let max_until = now
+        .checked_add(MAX_SELF_FREEZE_SECONDS)
+        .ok_or(GameError::MathOverflow)?;
+    require!(
+        until > now && until <= max_until && until >= stats.frozen_until,
+        GameError::InvalidFreezePeriod
+    );
+
+    stats.frozen_until = until;
+
+    emit!(PlayerFrozen {
+        event_version: EVENT_VERSION,
+        player,
+        frozen_until: until,
+    });
+
+    Ok(())
+}
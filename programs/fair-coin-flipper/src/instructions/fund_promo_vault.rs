@@ -0,0 +1,40 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct FundPromoVault<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(mut, seeds = [b"promo_vault"], bump)]
+    /// CHECK: Lamport-only PDA paying golden flip bonuses
+    pub promo_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone may top up the promo vault; only golden flips ever pay out of it
+pub(crate) fn handler(ctx: Context<FundPromoVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, GameError::InvalidConfigValue);
+
+    // The first deposit also seeds the rent reserve, which is never paid out
+    fund_treasury_reserve(
+        &ctx.accounts.funder,
+        &ctx.accounts.promo_vault,
+        &ctx.accounts.system_program,
+    )?;
+    deposit_stake(
+        ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.promo_vault,
+        &ctx.accounts.system_program,
+        amount,
+    )?;
+
+    emit!(PromoVaultFunded {
+        event_version: EVENT_VERSION,
+        funder: ctx.accounts.funder.key(),
+        amount,
+        balance: ctx.accounts.promo_vault.lamports(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct GetGameSummary<'info> {
+    // A raw account so the view also reads games already shrunk to a ResolvedSummary
+    #[account(
+        seeds = [b"game", game_address(&game)?.0.as_ref(), &game_address(&game)?.1.to_le_bytes()],
+        bump = game_address(&game)?.2
+    )]
+    /// CHECK: Owner and discriminator are checked by load_resolved or load_game
+    pub game: AccountInfo<'info>,
+}
+
+// A resolved game's summary, or None while the account still holds a live Game
+fn load_resolved(info: &AccountInfo) -> Result<Option<ResolvedSummary>> {
+    let data = info.try_borrow_data()?;
+    if *info.owner != crate::ID || !data.starts_with(&ResolvedSummary::DISCRIMINATOR) {
+        return Ok(None);
+    }
+    Ok(Some(ResolvedSummary::try_deserialize(&mut &data[..])?))
+}
+
+// Creator, game id and bump the game PDA was derived from, in either layout
+fn game_address(info: &AccountInfo) -> Result<(Pubkey, u64, u8)> {
+    if let Some(summary) = load_resolved(info)? {
+        return Ok((summary.creator, summary.game_id, summary.bump));
+    }
+    let game = load_game(info)?;
+    Ok((game.creator, game.game_id, game.bump))
+}
+
+// Compact view of a game returned via return data, so light clients can
+// simulate one instruction instead of fetching and decoding the account
+pub(crate) fn handler(ctx: Context<GetGameSummary>) -> Result<LiveGameSummary> {
+    if let Some(summary) = load_resolved(&ctx.accounts.game)? {
+        return Ok(summary.live_summary());
+    }
+    let game = load_game(&ctx.accounts.game)?;
+
+    Ok(LiveGameSummary {
+        game_id: game.game_id,
+        player_a: game.player_a,
+        player_b: game.player_b,
+        bet_amount: game.bet_amount,
+        status: game.status(),
+        phase_deadline: game.phase_deadline,
+        committed_a: game.commitment_a != [0; 32],
+        committed_b: game.commitment_b != [0; 32],
+        revealed_a: game.choice_a().is_some(),
+        revealed_b: game.choice_b().is_some(),
+        coin_result: game.coin_result(),
+        winner: game.winner(),
+    })
+}
@@ -0,0 +1,63 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct GetQuote<'info> {
+    #[account(
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(seeds = [b"player_stats", game.load()?.player_a.as_ref()], bump = player_a_stats.bump)]
+    pub player_a_stats: Account<'info, PlayerStats>,
+
+    // Omitted until someone has joined
+    #[account(seeds = [b"player_stats", game.load()?.player_b.as_ref()], bump = player_b_stats.bump)]
+    pub player_b_stats: Option<Account<'info, PlayerStats>>,
+}
+
+// Read-only preview of what settling this game would pay out, returned via
+// return data so clients can simulate it before asking for a signature. Only
+// unresolved games can be quoted: once a game resolves it is shrunk to a
+// ResolvedSummary, which the Game loader above rejects, and the amounts paid
+// are in its GameResolved event and get_game_summary instead
+pub(crate) fn handler(ctx: Context<GetQuote>) -> Result<PayoutQuote> {
+    let game = &ctx.accounts.game.load()?;
+
+    let (total_pot, house_fee, winner_payout) =
+        settlement_amounts(game.bet_amount, game.house_fee_bps)?;
+
+    let referral_fee_per_player = bps_of(house_fee / 2, REFERRAL_FEE_SHARE_BPS)?;
+    let referred_players = [
+        Some(&ctx.accounts.player_a_stats),
+        ctx.accounts.player_b_stats.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|stats| stats.referrer != Pubkey::default())
+    .count() as u64;
+    let referral_fees = referral_fee_per_player
+        .checked_mul(referred_players)
+        .ok_or(GameError::MathOverflow)?;
+
+    Ok(PayoutQuote {
+        total_pot,
+        winner_payout,
+        house_fee,
+        referral_fees,
+        treasury_fee: house_fee
+            .checked_sub(referral_fees)
+            .ok_or(GameError::MathOverflow)?,
+        rent_refund: if game.flag(FLAG_VAULT_ESCROW) {
+            0
+        } else {
+            Rent::get()?.minimum_balance(0)
+        },
+        game_rent_refund: ctx
+            .accounts
+            .game
+            .to_account_info()
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(8 + ResolvedSummary::INIT_SPACE)),
+    })
+}
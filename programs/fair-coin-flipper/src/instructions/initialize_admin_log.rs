@@ -0,0 +1,35 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct InitializeAdminLog<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ GameError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AdminLog::INIT_SPACE,
+        seeds = [b"admin_log"],
+        bump
+    )]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// One-time setup of the admin audit log
+pub(crate) fn handler(ctx: Context<InitializeAdminLog>) -> Result<()> {
+    let admin_log = &mut ctx.accounts.admin_log;
+    admin_log.bump = ctx.bumps.admin_log;
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct InitializeGlobalState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GlobalState::INIT_SPACE,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    /// CHECK: Program-derived fee treasury; funded with its rent-exempt reserve here
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// One-time setup of the program-wide configuration account.
+// The authority may be a multisig PDA; it never needs to sign here.
+pub(crate) fn handler(
+    ctx: Context<InitializeGlobalState>,
+    authority: Pubkey,
+    house_wallet: Pubkey,
+) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    global_state.authority = authority;
+    global_state.pending_authority = Pubkey::default();
+    global_state.operator = Pubkey::default();
+    global_state.house_wallet = house_wallet;
+    global_state.house_fee_bps = HOUSE_FEE_PERCENTAGE;
+    global_state.cancellation_fee_bps = CANCELLATION_FEE_PERCENTAGE;
+    global_state.min_bet_amount = MIN_BET_AMOUNT;
+    global_state.max_bet_amount = MAX_BET_AMOUNT;
+    global_state.timelock_delay_seconds = DEFAULT_TIMELOCK_DELAY_SECONDS;
+    global_state.cancel_timeout_seconds = DEFAULT_CANCEL_TIMEOUT_SECONDS;
+    global_state.pending_change = PendingConfigChange::NONE;
+    global_state.current_season_id = 1;
+    global_state.season_started_at = clock.unix_timestamp;
+    global_state.bump = ctx.bumps.global_state;
+    global_state.version = CURRENT_GLOBAL_STATE_VERSION;
+    global_state.treasury_bump = ctx.bumps.treasury;
+
+    fund_treasury_reserve(
+        &ctx.accounts.payer,
+        &ctx.accounts.treasury,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(SeasonStarted {
+        event_version: EVENT_VERSION,
+        season_id: global_state.current_season_id,
+        previous_season_id: 0,
+        started_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
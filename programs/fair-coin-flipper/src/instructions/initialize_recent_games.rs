@@ -0,0 +1,35 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct InitializeRecentGames<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ GameError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RecentGames::INIT_SPACE,
+        seeds = [b"recent_games"],
+        bump
+    )]
+    pub recent_games: Box<Account<'info, RecentGames>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// One-time setup of the global recent-games feed
+pub(crate) fn handler(ctx: Context<InitializeRecentGames>) -> Result<()> {
+    let recent_games = &mut ctx.accounts.recent_games;
+    recent_games.bump = ctx.bumps.recent_games;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct InitializeReferralLeaderboard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ GameError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ReferralLeaderboard::INIT_SPACE,
+        seeds = [b"referral_leaderboard"],
+        bump
+    )]
+    pub referral_leaderboard: Box<Account<'info, ReferralLeaderboard>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// One-time setup of the top-referrers leaderboard
+pub(crate) fn handler(ctx: Context<InitializeReferralLeaderboard>) -> Result<()> {
+    let leaderboard = &mut ctx.accounts.referral_leaderboard;
+    leaderboard.bump = ctx.bumps.referral_leaderboard;
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct JoinGame<'info> {
+    #[account(mut)]
+    pub player_b: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    // Required once a terms version is set
+    #[account(
+        seeds = [b"terms", player_b.key().as_ref()],
+        bump = terms_acceptance.bump
+    )]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(mut)]
+    pub sponsor: Option<Signer<'info>>,
+
+    #[account(mut)]
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player_b.key().as_ref()],
+        bump
+    )]
+    pub player_b_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Achievements::INIT_SPACE,
+        seeds = [b"achievements", player_b.key().as_ref()],
+        bump
+    )]
+    pub player_b_achievements: Account<'info, Achievements>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", player_b.key().as_ref()],
+        bump
+    )]
+    pub player_b_history: Box<Account<'info, GameHistory>>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(mut, address = escrow_address(&*game.load()?)?)]
+    /// CHECK: The game's escrow PDA, or the shared escrow vault for vault-mode games
+    pub escrow: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, needed for the CPI guard and match tickets
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<JoinGame>, repay_sponsor: bool, auto_pick: bool) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+
+    require!(
+        ctx.accounts.global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    verify_attestation(
+        &ctx.accounts.global_state,
+        ctx.accounts.player_b.key(),
+        ctx.accounts.attestation.as_ref(),
+    )?;
+    verify_terms(
+        &ctx.accounts.global_state,
+        ctx.accounts.terms_acceptance.as_ref(),
+    )?;
+    verify_cpi_caller(
+        &ctx.accounts.global_state,
+        ctx.accounts.instructions.as_ref(),
+    )?;
+    verify_match_ticket(
+        &ctx.accounts.global_state,
+        ctx.accounts.game.key(),
+        ctx.accounts.player_b.key(),
+        ctx.accounts.instructions.as_ref(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    verify_loss_limit(&ctx.accounts.global_state, &ctx.accounts.player_b_stats)?;
+    verify_not_frozen(&ctx.accounts.player_b_stats, Clock::get()?.unix_timestamp)?;
+    verify_cooldown(
+        &ctx.accounts.global_state,
+        &ctx.accounts.player_b_stats,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Validate game status
+    let next_status = advance(&game.status(), GameEvent::Join)?;
+
+    // Prevent player from playing against themselves
+    require!(
+        ctx.accounts.player_b.key() != game.player_a,
+        GameError::CannotPlayAgainstYourself
+    );
+
+    // During the anti-snipe lock only allowlisted players may join
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < game.join_locked_until {
+        require!(
+            game.allowed_joiners.contains(&ctx.accounts.player_b.key()),
+            GameError::JoinLocked
+        );
+    }
+
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_b_stats,
+        ctx.accounts.player_b.key(),
+        ctx.bumps.player_b_stats,
+    );
+    init_achievements_if_needed(
+        &mut ctx.accounts.player_b_achievements,
+        ctx.accounts.player_b.key(),
+        ctx.bumps.player_b_achievements,
+    );
+    init_game_history_if_needed(
+        &mut ctx.accounts.player_b_history,
+        ctx.accounts.player_b.key(),
+        ctx.bumps.player_b_history,
+    );
+    set_referrer_if_needed(&mut ctx.accounts.player_b_stats, &mut ctx.accounts.referrer);
+
+    // Set Player B data
+    game.player_b = ctx.accounts.player_b.key();
+    game.next_seq();
+    set_phase_deadline(game, &ctx.accounts.global_state, clock.unix_timestamp)?;
+    game.set_status(next_status);
+    game.sponsor_b = ctx
+        .accounts
+        .sponsor
+        .as_ref()
+        .map_or(Pubkey::default(), |sponsor| sponsor.key());
+    let repay = repay_sponsor && game.sponsor_b != Pubkey::default();
+    game.set_flag(FLAG_REPAY_SPONSOR_B, repay);
+    game.set_auto_pick_b(auto_pick);
+
+    // Transfer bet amount to escrow from the player or their sponsor
+    let funder = match &ctx.accounts.sponsor {
+        Some(sponsor) => sponsor.to_account_info(),
+        None => ctx.accounts.player_b.to_account_info(),
+    };
+    deposit_stake(
+        funder,
+        &ctx.accounts.escrow,
+        &ctx.accounts.system_program,
+        game.bet_amount,
+    )?;
+
+    emit!(PlayerJoined {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        player_b: game.player_b,
+        tags: game.tags,
+        seq: game.seq,
+    });
+    emit_delisted(game, DelistReason::Joined, clock.unix_timestamp);
+
+    Ok(())
+}
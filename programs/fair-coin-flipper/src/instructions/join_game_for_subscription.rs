@@ -0,0 +1,155 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct JoinGameForSubscription<'info> {
+    // Anyone may crank a join; the subscription decides which games qualify
+    pub cranker: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.player.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription_escrow", subscription.key().as_ref()],
+        bump = subscription.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the subscription's budget
+    pub subscription_escrow: AccountInfo<'info>,
+
+    /// CHECK: Issuer-owned attestation PDA, verified in the handler when required
+    pub attestation: Option<AccountInfo<'info>>,
+
+    // Required once a terms version is set
+    #[account(
+        seeds = [b"terms", subscription.player.as_ref()],
+        bump = terms_acceptance.bump
+    )]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(seeds = [b"player_stats", subscription.player.as_ref()], bump = player_stats.bump)]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(mut, address = escrow_address(&*game.load()?)?)]
+    /// CHECK: The game's escrow PDA, or the shared escrow vault for vault-mode games
+    pub escrow: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, needed for the CPI guard and match tickets
+    pub instructions: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Join a matching open game as the subscriber, paying the stake from the
+// subscription escrow. The escrow is recorded as the sponsor and repaid from a
+// win. The subscriber commits and reveals as usual, directly or through a
+// session key (see authorize_delegate).
+pub(crate) fn handler(ctx: Context<JoinGameForSubscription>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let global_state = &ctx.accounts.global_state;
+    let subscription = &ctx.accounts.subscription;
+    let player = subscription.player;
+    let now = Clock::get()?.unix_timestamp;
+
+    // The same checks join_game makes of a joiner
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    verify_attestation(global_state, player, ctx.accounts.attestation.as_ref())?;
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+    verify_cpi_caller(global_state, ctx.accounts.instructions.as_ref())?;
+    verify_match_ticket(
+        global_state,
+        ctx.accounts.game.key(),
+        player,
+        ctx.accounts.instructions.as_ref(),
+        now,
+    )?;
+    verify_loss_limit(global_state, &ctx.accounts.player_stats)?;
+    verify_not_frozen(&ctx.accounts.player_stats, now)?;
+    verify_cooldown(global_state, &ctx.accounts.player_stats, now)?;
+
+    let next_status = advance(&game.status(), GameEvent::Join)?;
+    require!(
+        player != game.player_a,
+        GameError::CannotPlayAgainstYourself
+    );
+    if now < game.join_locked_until {
+        require!(
+            game.allowed_joiners.contains(&player),
+            GameError::JoinLocked
+        );
+    }
+
+    // And what the subscriber asked for
+    require!(subscription.matches(game), GameError::SubscriptionMismatch);
+    let available = ctx
+        .accounts
+        .subscription_escrow
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(
+        subscription.games_joined < subscription.max_games && available >= game.bet_amount,
+        GameError::SubscriptionExhausted
+    );
+
+    game.player_b = player;
+    game.next_seq();
+    set_phase_deadline(game, global_state, now)?;
+    game.set_status(next_status);
+    game.sponsor_b = ctx.accounts.subscription_escrow.key();
+    game.set_flag(FLAG_REPAY_SPONSOR_B, true);
+
+    let subscription_key = subscription.key();
+    let seeds: &[&[u8]] = &[
+        b"subscription_escrow",
+        subscription_key.as_ref(),
+        &[subscription.escrow_bump],
+    ];
+    transfer_from_escrow(
+        &ctx.accounts.subscription_escrow,
+        ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.system_program,
+        &[seeds],
+        game.bet_amount,
+    )?;
+    if is_vault(&ctx.accounts.escrow) {
+        add_vault_liability(&ctx.accounts.escrow, game.bet_amount)?;
+    }
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.games_joined += 1;
+
+    emit!(PlayerJoined {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        player_b: player,
+        tags: game.tags,
+        seq: game.seq,
+    });
+    emit_delisted(game, DelistReason::Joined, now);
+    emit!(SubscriptionJoined {
+        event_version: EVENT_VERSION,
+        player,
+        game_id: game.game_id,
+        games_joined: subscription.games_joined,
+        budget_left: available - game.bet_amount,
+    });
+
+    Ok(())
+}
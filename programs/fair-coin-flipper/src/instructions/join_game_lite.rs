@@ -0,0 +1,100 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct JoinGameLite<'info> {
+    #[account(mut)]
+    pub player_b: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(seeds = [b"player_stats", player_b.key().as_ref()], bump)]
+    /// CHECK: The joiner's stats PDA; uninitialized until their first game
+    pub player_b_stats: UncheckedAccount<'info>,
+
+    #[account(mut, address = escrow_address(&*game.load()?)?)]
+    /// CHECK: The game's escrow PDA, or the shared escrow vault for vault-mode games
+    pub escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// One-click join for Solana Actions/Blinks: only the joiner, the game, its
+// escrow and the fixed global_state PDA. Self-funded only, with no referrer.
+// Stats, achievements and history are created on the joiner's first
+// make_commitment. Deployments that need attestations, match tickets, terms
+// acceptance or a game cooldown reject it, since those require join_game's
+// extra accounts. A returning joiner's stats PDA is read for self-freezes and
+// loss limits; a first-time joiner has neither.
+pub(crate) fn handler(ctx: Context<JoinGameLite>) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let global_state = &ctx.accounts.global_state;
+    let player_b = ctx.accounts.player_b.key();
+
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    verify_attestation(global_state, player_b, None)?;
+    verify_cpi_caller(global_state, None)?;
+    require!(
+        global_state.matchmaker == Pubkey::default(),
+        GameError::MissingMatchTicket
+    );
+    require!(
+        global_state.game_cooldown_seconds == 0,
+        GameError::CooldownNeedsStats
+    );
+    verify_terms(global_state, None)?;
+
+    let clock = Clock::get()?;
+    let stats_info = ctx.accounts.player_b_stats.to_account_info();
+    if stats_info.owner == ctx.program_id {
+        let stats = PlayerStats::try_deserialize(&mut &stats_info.try_borrow_data()?[..])?;
+        verify_loss_limit(global_state, &stats)?;
+        verify_not_frozen(&stats, clock.unix_timestamp)?;
+    }
+
+    let next_status = advance(&game.status(), GameEvent::Join)?;
+    require!(
+        player_b != game.player_a,
+        GameError::CannotPlayAgainstYourself
+    );
+
+    if clock.unix_timestamp < game.join_locked_until {
+        require!(
+            game.allowed_joiners.contains(&player_b),
+            GameError::JoinLocked
+        );
+    }
+
+    game.player_b = player_b;
+    game.next_seq();
+    set_phase_deadline(game, global_state, clock.unix_timestamp)?;
+    game.set_status(next_status);
+
+    deposit_stake(
+        ctx.accounts.player_b.to_account_info(),
+        &ctx.accounts.escrow,
+        &ctx.accounts.system_program,
+        game.bet_amount,
+    )?;
+
+    emit!(PlayerJoined {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        player_b,
+        tags: game.tags,
+        seq: game.seq,
+    });
+    emit_delisted(game, DelistReason::Joined, clock.unix_timestamp);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct LeaveMatchQueue<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"match_queue".as_ref(), &match_queue.stake.to_le_bytes()],
+        bump = match_queue.bump
+    )]
+    pub match_queue: Account<'info, MatchQueue>,
+
+    #[account(
+        mut,
+        seeds = [b"match_queue_escrow", match_queue.key().as_ref()],
+        bump = match_queue.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the queued players' stakes
+    pub match_queue_escrow: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Leave the queue with the full stake, as long as no bracket has taken the player yet
+pub(crate) fn handler(ctx: Context<LeaveMatchQueue>) -> Result<()> {
+    let match_queue = &mut ctx.accounts.match_queue;
+    let player = ctx.accounts.player.key();
+
+    let index = match_queue
+        .position(&player)
+        .ok_or(GameError::NotInMatchQueue)?;
+    match_queue.remove(index, 1);
+    record_pooled_refund(
+        &mut ctx.accounts.player_stats,
+        match_queue.stake,
+        ctx.accounts.global_state.current_season_id,
+    );
+
+    let queue_key = match_queue.key();
+    transfer_from_escrow(
+        &ctx.accounts.match_queue_escrow,
+        ctx.accounts.player.to_account_info(),
+        &ctx.accounts.system_program,
+        &[&[
+            b"match_queue_escrow",
+            queue_key.as_ref(),
+            &[match_queue.escrow_bump],
+        ]],
+        match_queue.stake,
+    )?;
+
+    emit!(MatchQueueLeft {
+        event_version: EVENT_VERSION,
+        stake: match_queue.stake,
+        player,
+        queue_len: match_queue.len,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct MakeCommitment<'info> {
+    // The player, or their session key when `delegate` is passed
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"delegate", delegate.player.as_ref()],
+        bump = delegate.bump,
+        constraint = delegate.session_key == player.key() @ GameError::InvalidDelegate
+    )]
+    pub delegate: Option<Account<'info, Delegate>>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", acting_player(&player, &delegate).as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Achievements::INIT_SPACE,
+        seeds = [b"achievements", acting_player(&player, &delegate).as_ref()],
+        bump
+    )]
+    pub player_achievements: Account<'info, Achievements>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", acting_player(&player, &delegate).as_ref()],
+        bump
+    )]
+    pub player_history: Box<Account<'info, GameHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<MakeCommitment>, commitment: [u8; 32]) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    // The player may act directly or through a session key
+    let player = acting_player(&ctx.accounts.player, &ctx.accounts.delegate);
+    verify_delegate(ctx.accounts.delegate.as_ref(), game, now)?;
+
+    // Players who joined through join_game_lite get their accounts here
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_stats,
+        player,
+        ctx.bumps.player_stats,
+    );
+    // Also covers players who joined through join_game_lite, which skips stats
+    verify_loss_limit(&ctx.accounts.global_state, &ctx.accounts.player_stats)?;
+    verify_not_frozen(&ctx.accounts.player_stats, now)?;
+    init_achievements_if_needed(
+        &mut ctx.accounts.player_achievements,
+        player,
+        ctx.bumps.player_achievements,
+    );
+    init_game_history_if_needed(
+        &mut ctx.accounts.player_history,
+        player,
+        ctx.bumps.player_history,
+    );
+
+    store_commitment(game, &ctx.accounts.global_state, player, commitment, now)
+}
@@ -0,0 +1,94 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct MakeCommitmentRelayed<'info> {
+    // Submits the player's signed commitment and pays fees and rent
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player.as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + Achievements::INIT_SPACE,
+        seeds = [b"achievements", player.as_ref()],
+        bump
+    )]
+    pub player_achievements: Account<'info, Achievements>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", player.as_ref()],
+        bump
+    )]
+    pub player_history: Box<Account<'info, GameHistory>>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, read to find the player's ed25519 signature
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Submit a commitment for a player who signed it off-chain, so wallets with no
+// SOL for fees can still play. The relayer pays fees and rent; the preceding
+// ed25519 program instruction must carry the player's signature over
+// game || commitment || expiry (i64 LE).
+pub(crate) fn handler(
+    ctx: Context<MakeCommitmentRelayed>,
+    player: Pubkey,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    verify_player_signature(
+        ctx.accounts.game.key(),
+        player,
+        commitment,
+        &ctx.accounts.instructions,
+        now,
+    )?;
+
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_stats,
+        player,
+        ctx.bumps.player_stats,
+    );
+    // Also covers players who joined through join_game_lite, which skips stats
+    verify_loss_limit(&ctx.accounts.global_state, &ctx.accounts.player_stats)?;
+    verify_not_frozen(&ctx.accounts.player_stats, now)?;
+    init_achievements_if_needed(
+        &mut ctx.accounts.player_achievements,
+        player,
+        ctx.bumps.player_achievements,
+    );
+    init_game_history_if_needed(
+        &mut ctx.accounts.player_history,
+        player,
+        ctx.bumps.player_history,
+    );
+
+    store_commitment(game, &ctx.accounts.global_state, player, commitment, now)
+}
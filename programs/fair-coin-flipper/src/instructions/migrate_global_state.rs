@@ -0,0 +1,162 @@
+use crate::*;
+
+// Where GlobalState kept its queued change before v16: after the discriminator,
+// four pubkeys and five 8-byte fields
+const LEGACY_PENDING_CHANGE_OFFSET: usize = 8 + 4 * 32 + 5 * 8;
+
+#[derive(Accounts)]
+pub struct MigrateGlobalState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"global_state"], bump)]
+    /// CHECK: Old layouts can't be deserialized; owner, discriminator, and authority are checked in the handler
+    pub global_state: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"admin_log"], bump = admin_log.bump)]
+    pub admin_log: Option<Box<Account<'info, AdminLog>>>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    /// CHECK: Program-derived fee treasury; funded with its rent-exempt reserve on v3 migration
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Upgrade an older GlobalState layout in place: grow the account, let the
+// appended fields zero-initialize, then fill in non-zero defaults.
+pub(crate) fn handler(ctx: Context<MigrateGlobalState>) -> Result<()> {
+    let global_state_info = ctx.accounts.global_state.to_account_info();
+
+    // Manual checks since the old layout can't be deserialized as-is
+    require!(
+        global_state_info.owner == ctx.program_id,
+        GameError::InvalidGlobalState
+    );
+    {
+        let data = global_state_info.try_borrow_data()?;
+        require!(
+            data.len() >= 40 && data[..8] == GlobalState::DISCRIMINATOR,
+            GameError::InvalidGlobalState
+        );
+        require!(
+            data[8..40] == ctx.accounts.authority.key().to_bytes(),
+            GameError::Unauthorized
+        );
+    }
+
+    // Grow the account and top up rent from the payer if needed
+    let new_len = 8 + GlobalState::INIT_SPACE;
+    if global_state_info.data_len() < new_len {
+        let required = Rent::get()?.minimum_balance(new_len);
+        let shortfall = required.saturating_sub(global_state_info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: global_state_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        global_state_info.realloc(new_len, true)?;
+    }
+
+    // Before v16 the queued change sat in a variable-size Option mid-layout. Cut a
+    // queued one out so the fields after it line up with the one-byte slot left in
+    // its place; it's requeued at the end below. Since v16 the slot reads None, so
+    // this moves nothing.
+    let legacy_pending_change = {
+        let mut data = global_state_info.try_borrow_mut_data()?;
+        let slot = &mut data[LEGACY_PENDING_CHANGE_OFFSET..];
+        let (pending, len) = {
+            let mut rest: &[u8] = slot;
+            let pending = Option::<(ConfigChange, i64)>::deserialize(&mut rest)
+                .map_err(|_| GameError::InvalidGlobalState)?;
+            (pending, slot.len() - rest.len())
+        };
+        slot.copy_within(len.., 1);
+        let end = slot.len() - (len - 1);
+        slot[end..].fill(0);
+        slot[0] = 0;
+        pending
+    };
+
+    let mut global_state = {
+        let data = global_state_info.try_borrow_data()?;
+        GlobalState::try_deserialize(&mut &data[..])?
+    };
+    let from_version = global_state.version.max(1);
+    require!(
+        from_version < CURRENT_GLOBAL_STATE_VERSION,
+        GameError::AlreadyMigrated
+    );
+
+    // v1 -> v2: fields added since v1 need defaults other than zero
+    if global_state.timelock_delay_seconds == 0 {
+        global_state.timelock_delay_seconds = DEFAULT_TIMELOCK_DELAY_SECONDS;
+    }
+    if global_state.cancel_timeout_seconds == 0 {
+        global_state.cancel_timeout_seconds = DEFAULT_CANCEL_TIMEOUT_SECONDS;
+    }
+    // v2 -> v3: fees now accrue in the treasury PDA
+    if from_version < 3 {
+        global_state.treasury_bump = ctx.bumps.treasury;
+        fund_treasury_reserve(
+            &ctx.accounts.payer,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+    }
+    // v3 -> v4: pause_mode zero-initializes to Active
+    // v4 -> v5: attestation_issuer zero-initializes to disabled
+    // v5 -> v6: total_volume starts counting from zero
+    // v6 -> v7: the CPI guard starts disabled
+    // v7 -> v8: no matchmaker until one is configured
+    // v8 -> v9: archiving stays off until a tree is set
+    // v9 -> v10: no resolution hook until one is configured
+    // v10 -> v11: games keep per-game escrows until vault mode is turned on
+    // v11 -> v12: no cooldown between games until one is configured
+    // v12 -> v13: no terms acceptance required until a version is set
+    // v13 -> v14: total_games starts counting from zero
+    // v14 -> v15: golden flips stay off until configured
+    // v15 -> v16: the queued change moves to the end of the layout
+    if let Some((change, eta)) = legacy_pending_change {
+        global_state.pending_change = PendingConfigChange {
+            active: true,
+            change,
+            eta,
+        };
+    }
+    global_state.version = CURRENT_GLOBAL_STATE_VERSION;
+
+    {
+        let mut data = global_state_info.try_borrow_mut_data()?;
+        global_state.try_serialize(&mut &mut data[..])?;
+    }
+
+    if let Some(admin_log) = ctx.accounts.admin_log.as_mut() {
+        log_admin_action(
+            admin_log,
+            AdminLogAction::MigrateGlobalState,
+            ctx.accounts.authority.key(),
+            Pubkey::default(),
+            CURRENT_GLOBAL_STATE_VERSION as u64,
+            Clock::get()?.unix_timestamp,
+        );
+    }
+
+    emit!(GlobalStateMigrated {
+        event_version: EVENT_VERSION,
+        from_version,
+        to_version: CURRENT_GLOBAL_STATE_VERSION,
+        new_len: global_state_info.data_len() as u64,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+pub mod accept_authority;
+pub mod accept_game_ownership;
+pub mod accept_terms;
+pub mod admin_action;
+pub mod archive_game;
+pub mod archive_resolved_game;
+pub mod assert_escrow_invariants;
+pub mod authorize_delegate;
+pub mod auto_pick_side;
+pub mod back_market;
+pub mod buy_ticket;
+pub mod cancel_config_change;
+pub mod cancel_game;
+pub mod cancel_subscription;
+pub mod challenge_room;
+pub mod claim_bracket;
+pub mod claim_lottery;
+pub mod claim_market;
+pub mod claim_referral_fees;
+pub mod create_game;
+pub mod create_games_batch;
+pub mod create_lookup_table;
+pub mod create_room;
+pub mod draw_bracket_round;
+pub mod draw_lottery;
+pub mod emergency_recover;
+pub mod enter_match_queue;
+pub mod execute_config_change;
+pub mod extend_lookup_table;
+pub mod forfeit_bout;
+pub mod freeze_self;
+pub mod fund_promo_vault;
+pub mod get_game_summary;
+pub mod get_quote;
+pub mod initialize_admin_log;
+pub mod initialize_global_state;
+pub mod initialize_recent_games;
+pub mod initialize_referral_leaderboard;
+pub mod join_game;
+pub mod join_game_for_subscription;
+pub mod join_game_lite;
+pub mod leave_match_queue;
+pub mod make_commitment;
+pub mod make_commitment_relayed;
+pub mod migrate_global_state;
+pub mod offer_game_ownership;
+pub mod propose_authority;
+pub mod propose_config_change;
+pub mod register_automation_thread;
+pub mod register_referrer;
+pub mod register_username;
+pub mod resolve_game_manual;
+pub mod resolve_market;
+pub mod retire_champion;
+pub mod reveal_choice;
+pub mod revoke_delegate;
+pub mod schedule_lottery;
+pub mod schedule_market;
+pub mod set_archive_tree;
+pub mod set_attestation_issuer;
+pub mod set_cancel_timeout;
+pub mod set_cpi_guard;
+pub mod set_escrow_mode;
+pub mod set_game_cooldown;
+pub mod set_golden_flip;
+pub mod set_hook_program;
+pub mod set_loss_limit;
+pub mod set_matchmaker;
+pub mod set_operator;
+pub mod set_pause_mode;
+pub mod set_terms_version;
+pub mod settle_bout;
+pub mod start_bracket;
+pub mod start_new_season;
+pub mod subscribe;
+pub mod void_bracket;
+pub mod void_lottery;
+pub mod void_market;
+pub mod withdraw_treasury;
+
+pub use accept_authority::*;
+pub use accept_game_ownership::*;
+pub use accept_terms::*;
+pub use admin_action::*;
+pub use archive_game::*;
+pub use archive_resolved_game::*;
+pub use assert_escrow_invariants::*;
+pub use authorize_delegate::*;
+pub use auto_pick_side::*;
+pub use back_market::*;
+pub use buy_ticket::*;
+pub use cancel_game::*;
+pub use cancel_subscription::*;
+pub use challenge_room::*;
+pub use claim_bracket::*;
+pub use claim_lottery::*;
+pub use claim_market::*;
+pub use claim_referral_fees::*;
+pub use create_game::*;
+pub use create_games_batch::*;
+pub use create_lookup_table::*;
+pub use create_room::*;
+pub use draw_bracket_round::*;
+pub use draw_lottery::*;
+pub use emergency_recover::*;
+pub use enter_match_queue::*;
+pub use forfeit_bout::*;
+pub use freeze_self::*;
+pub use fund_promo_vault::*;
+pub use get_game_summary::*;
+pub use get_quote::*;
+pub use initialize_admin_log::*;
+pub use initialize_global_state::*;
+pub use initialize_recent_games::*;
+pub use initialize_referral_leaderboard::*;
+pub use join_game::*;
+pub use join_game_for_subscription::*;
+pub use join_game_lite::*;
+pub use leave_match_queue::*;
+pub use make_commitment::*;
+pub use make_commitment_relayed::*;
+pub use migrate_global_state::*;
+pub use offer_game_ownership::*;
+pub use register_automation_thread::*;
+pub use register_referrer::*;
+pub use register_username::*;
+pub use resolve_game_manual::*;
+pub use resolve_market::*;
+pub use retire_champion::*;
+pub use reveal_choice::*;
+pub use revoke_delegate::*;
+pub use schedule_lottery::*;
+pub use schedule_market::*;
+pub use set_archive_tree::*;
+pub use set_escrow_mode::*;
+pub use set_loss_limit::*;
+pub use settle_bout::*;
+pub use start_bracket::*;
+pub use subscribe::*;
+pub use void_bracket::*;
+pub use void_lottery::*;
+pub use void_market::*;
+pub use withdraw_treasury::*;
@@ -0,0 +1,42 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct OfferGameOwnership<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+}
+
+// Offer an unjoined game (and its escrowed stake) to another wallet.
+// Passing the default pubkey withdraws a pending offer.
+pub(crate) fn handler(ctx: Context<OfferGameOwnership>, new_owner: Pubkey) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+
+    require!(
+        game.status() == GameStatus::WaitingForPlayer,
+        GameError::InvalidGameStatus
+    );
+    require!(
+        ctx.accounts.owner.key() == game.player_a,
+        GameError::NotGameOwner
+    );
+    require!(new_owner != game.player_a, GameError::InvalidNewOwner);
+
+    game.pending_owner = new_owner;
+    game.next_seq();
+
+    emit!(OwnershipOffered {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        owner: game.player_a,
+        pending_owner: new_owner,
+        seq: game.seq,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::*;
+
+// Start handing the authority role to a new key (e.g. a multisig vault PDA)
+pub(crate) fn handler(ctx: Context<AdminAction>, new_authority: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    global_state.pending_authority = new_authority;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::ProposeAuthority,
+        ctx.accounts.authority.key(),
+        new_authority,
+        0,
+        clock.unix_timestamp,
+    );
+
+    emit!(AuthorityProposed {
+        event_version: EVENT_VERSION,
+        authority: global_state.authority,
+        pending_authority: new_authority,
+    });
+
+    Ok(())
+}
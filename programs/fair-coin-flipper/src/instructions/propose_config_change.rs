@@ -0,0 +1,41 @@
+use crate::*;
+
+// Queue a fee/limit change; it can only be executed after the timelock delay
+pub(crate) fn handler(ctx: Context<AdminAction>, change: ConfigChange) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    require!(
+        !global_state.pending_change.active,
+        GameError::ConfigChangePending
+    );
+    validate_config_change(&change)?;
+
+    let eta = clock
+        .unix_timestamp
+        .checked_add(global_state.timelock_delay_seconds)
+        .ok_or(GameError::MathOverflow)?;
+    global_state.pending_change = PendingConfigChange {
+        active: true,
+        change: change.clone(),
+        eta,
+    };
+
+    let (target, value) = config_change_summary(&change);
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::ProposeConfigChange,
+        ctx.accounts.authority.key(),
+        target,
+        value,
+        clock.unix_timestamp,
+    );
+
+    emit!(ConfigChangeProposed {
+        event_version: EVENT_VERSION,
+        change,
+        eta
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RegisterAutomationThread<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.load()?.creator.as_ref(), &game.load()?.game_id.to_le_bytes()],
+        bump = game.load()?.bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+}
+
+// Register an automation thread for a game, typically in the same transaction
+// as create_game. The thread signs cancel_game/resolve_game_manual on schedule
+// (at phase_deadline) so the game settles even if neither player returns.
+// Passing the default pubkey removes it.
+pub(crate) fn handler(ctx: Context<RegisterAutomationThread>, thread: Pubkey) -> Result<()> {
+    let game = &mut ctx.accounts.game.load_mut()?;
+
+    require!(
+        ctx.accounts.owner.key() == game.player_a,
+        GameError::NotGameOwner
+    );
+    require!(!is_settled(&game.status()), GameError::AlreadySettled);
+
+    game.automation_thread = thread;
+    game.next_seq();
+
+    emit!(AutomationThreadRegistered {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        thread,
+        phase_deadline: game.phase_deadline,
+        seq: game.seq,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(username: String)]
+pub struct RegisterUsername<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Profile::INIT_SPACE,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + UsernameRecord::INIT_SPACE,
+        seeds = [b"username", username.as_bytes()],
+        bump
+    )]
+    pub username_record: Account<'info, UsernameRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Register a unique username; the name PDA makes a second claim fail
+pub(crate) fn handler(ctx: Context<RegisterUsername>, username: String) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let username_record = &mut ctx.accounts.username_record;
+    let clock = Clock::get()?;
+
+    require!(is_valid_username(&username), GameError::InvalidUsername);
+
+    let mut name = [0u8; MAX_USERNAME_LENGTH];
+    name[..username.len()].copy_from_slice(username.as_bytes());
+
+    profile.owner = ctx.accounts.owner.key();
+    profile.username = name;
+    profile.username_len = username.len() as u8;
+    profile.created_at = clock.unix_timestamp;
+    profile.bump = ctx.bumps.profile;
+
+    username_record.owner = ctx.accounts.owner.key();
+    username_record.bump = ctx.bumps.username_record;
+
+    emit!(UsernameRegistered {
+        event_version: EVENT_VERSION,
+        owner: profile.owner,
+        username,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveGameManual<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // A raw account rather than an AccountLoader: Anchor's exit would write the
+    // Game discriminator back over the ResolvedSummary this instruction shrinks the
+    // account to
+    #[account(
+        mut,
+        seeds = [b"game", load_game(&game)?.creator.as_ref(), &load_game(&game)?.game_id.to_le_bytes()],
+        bump = load_game(&game)?.bump
+    )]
+    /// CHECK: Owner and discriminator are checked by load_game
+    pub game: AccountInfo<'info>,
+
+    #[account(mut, address = load_game(&game)?.player_a)]
+    /// CHECK: Player A account for transfers
+    pub player_a: AccountInfo<'info>,
+
+    #[account(mut, address = load_game(&game)?.player_b)]
+    /// CHECK: Player B account for transfers
+    pub player_b: AccountInfo<'info>,
+
+    #[account(mut, address = load_game(&game)?.sponsor_a)]
+    /// CHECK: Player A's sponsor, required only when the stake was sponsored
+    pub sponsor_a: Option<AccountInfo<'info>>,
+
+    #[account(mut, address = load_game(&game)?.sponsor_b)]
+    /// CHECK: Player B's sponsor, required only when the stake was sponsored
+    pub sponsor_b: Option<AccountInfo<'info>>,
+
+    #[account(mut, seeds = [b"treasury"], bump = global_state.treasury_bump)]
+    /// CHECK: Program-derived fee treasury; holds lamports only
+    pub treasury: AccountInfo<'info>,
+
+    #[account(mut, address = load_game(&game)?.creator)]
+    /// CHECK: Original creator; receives the escrow's rent reserve when it closes
+    pub creator: AccountInfo<'info>,
+
+    #[account(mut, address = escrow_address(&*load_game(&game)?)?)]
+    /// CHECK: The game's escrow PDA, or the shared escrow vault for vault-mode games
+    pub escrow: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", load_game(&game)?.player_a.as_ref()],
+        bump = player_a_stats.bump
+    )]
+    pub player_a_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", load_game(&game)?.player_b.as_ref()],
+        bump = player_b_stats.bump
+    )]
+    pub player_b_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"achievements", load_game(&game)?.player_a.as_ref()],
+        bump = player_a_achievements.bump
+    )]
+    pub player_a_achievements: Account<'info, Achievements>,
+
+    #[account(
+        mut,
+        seeds = [b"achievements", load_game(&game)?.player_b.as_ref()],
+        bump = player_b_achievements.bump
+    )]
+    pub player_b_achievements: Account<'info, Achievements>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [b"daily_stats".as_ref(), &current_unix_day()?.to_le_bytes()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    #[account(
+        mut,
+        seeds = [b"game_history", load_game(&game)?.player_a.as_ref()],
+        bump = player_a_history.bump
+    )]
+    pub player_a_history: Box<Account<'info, GameHistory>>,
+
+    #[account(
+        mut,
+        seeds = [b"game_history", load_game(&game)?.player_b.as_ref()],
+        bump = player_b_history.bump
+    )]
+    pub player_b_history: Box<Account<'info, GameHistory>>,
+
+    #[account(mut, seeds = [b"recent_games"], bump = recent_games.bump)]
+    pub recent_games: Box<Account<'info, RecentGames>>,
+
+    #[account(mut, constraint = player_a_referrer.referrer == player_a_stats.referrer)]
+    pub player_a_referrer: Option<Account<'info, Referrer>>,
+
+    #[account(mut, constraint = player_b_referrer.referrer == player_b_stats.referrer)]
+    pub player_b_referrer: Option<Account<'info, Referrer>>,
+
+    #[account(mut, seeds = [b"referral_leaderboard"], bump = referral_leaderboard.bump)]
+    pub referral_leaderboard: Box<Account<'info, ReferralLeaderboard>>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, only needed while the CPI guard is enabled
+    pub instructions: Option<AccountInfo<'info>>,
+
+    #[account(address = MEMO_PROGRAM_ID)]
+    /// CHECK: SPL Memo program, only needed to write a settlement receipt
+    pub memo_program: Option<AccountInfo<'info>>,
+
+    // Durable receipt, written only when passed
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [b"receipt", load_game(&game)?.creator.as_ref(), &load_game(&game)?.game_id.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Option<Box<Account<'info, Receipt>>>,
+
+    #[account(address = global_state.hook_program)]
+    /// CHECK: Plugin program notified after resolution, required once configured
+    pub hook_program: Option<AccountInfo<'info>>,
+
+    #[account(seeds = [b"hook_authority"], bump)]
+    /// CHECK: PDA that signs the hook CPI
+    pub hook_authority: Option<AccountInfo<'info>>,
+
+    #[account(mut, seeds = [b"promo_vault"], bump)]
+    /// CHECK: Lamport-only PDA paying golden flip bonuses, required once they're on
+    pub promo_vault: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Manual resolution fallback
+pub(crate) fn handler(ctx: Context<ResolveGameManual>) -> Result<()> {
+    let mut game = load_game_mut(&ctx.accounts.game)?;
+    let clock = Clock::get()?;
+
+    // Only the players or the operator may pick the resolution slot
+    require!(
+        is_player_or_operator(
+            ctx.accounts.resolver.key(),
+            &game,
+            &ctx.accounts.global_state,
+        ),
+        GameError::NotPlayerOrOperator
+    );
+    verify_cpi_caller(
+        &ctx.accounts.global_state,
+        ctx.accounts.instructions.as_ref(),
+    )?;
+
+    // Validate both players have revealed
+    require!(
+        game.choice_a().is_some() && game.choice_b().is_some(),
+        GameError::NotReadyForResolution
+    );
+
+    // Prevent double settlement
+    require!(!is_settled(&game.status()), GameError::AlreadySettled);
+
+    game.next_seq();
+
+    // Pay nothing from an escrow that doesn't hold exactly what the game owes
+    if !audit_escrow(&mut game, &ctx.accounts.escrow, clock.unix_timestamp)? {
+        return Ok(());
+    }
+
+    let resolved_by = ctx.accounts.resolver.key();
+    let resolved = settle(
+        game,
+        SettlementAccounts {
+            game: &ctx.accounts.game,
+            player_a: &ctx.accounts.player_a,
+            player_b: &ctx.accounts.player_b,
+            sponsor_a: ctx.accounts.sponsor_a.as_ref(),
+            sponsor_b: ctx.accounts.sponsor_b.as_ref(),
+            treasury: &ctx.accounts.treasury,
+            creator: &ctx.accounts.creator,
+            escrow: &ctx.accounts.escrow,
+            global_state: &mut ctx.accounts.global_state,
+            player_a_stats: &mut ctx.accounts.player_a_stats,
+            player_b_stats: &mut ctx.accounts.player_b_stats,
+            player_a_achievements: &mut ctx.accounts.player_a_achievements,
+            player_b_achievements: &mut ctx.accounts.player_b_achievements,
+            daily_stats: &mut ctx.accounts.daily_stats,
+            player_a_history: &mut ctx.accounts.player_a_history,
+            player_b_history: &mut ctx.accounts.player_b_history,
+            recent_games: &mut ctx.accounts.recent_games,
+            player_a_referrer: &mut ctx.accounts.player_a_referrer,
+            player_b_referrer: &mut ctx.accounts.player_b_referrer,
+            referral_leaderboard: &mut ctx.accounts.referral_leaderboard,
+            memo_program: ctx.accounts.memo_program.as_ref(),
+            receipt: ctx.accounts.receipt.as_deref_mut(),
+            hook_program: ctx.accounts.hook_program.as_ref(),
+            hook_authority: ctx.accounts.hook_authority.as_ref(),
+            promo_vault: ctx.accounts.promo_vault.as_ref(),
+            system_program: &ctx.accounts.system_program,
+            daily_stats_bump: ctx.bumps.daily_stats,
+            receipt_bump: ctx.bumps.receipt,
+            hook_authority_bump: ctx.bumps.hook_authority,
+            promo_vault_bump: ctx.bumps.promo_vault,
+        },
+        resolved_by,
+        &clock,
+    )?;
+    emit_cpi!(resolved);
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    // Anyone may draw a market
+    pub cranker: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump = market.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the market's stakes
+    pub market_escrow: AccountInfo<'info>,
+
+    #[account(mut, address = market.operator)]
+    /// CHECK: Gets the escrow's reserve back if no one backed the result
+    pub operator: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump = global_state.treasury_bump)]
+    /// CHECK: Program-derived fee treasury; holds lamports only
+    pub treasury: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    /// CHECK: SlotHashes sysvar, read raw by slot_hash_at
+    pub slot_hashes: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Draw the market from its draw slot's hash, mixed with the market id so markets
+// sharing a draw slot don't all land the same way. The house fee comes off the
+// whole pot and the rest is shared pro rata by the winning side. A result nobody
+// backed voids the market instead. A draw slot that was skipped, or that no one
+// drew within the window, leaves the market for void_market.
+pub(crate) fn handler(ctx: Context<ResolveMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    require!(
+        market.status == MarketStatus::Open,
+        GameError::AlreadySettled
+    );
+    let slot_hash = drawn_slot_hash(market.draw_slot, &ctx.accounts.slot_hashes)?;
+    let randomness = draw_randomness(market.market_id, &slot_hash);
+    let result = coin_side(&randomness);
+    market.result = result;
+    market.randomness = randomness;
+
+    if market.side_total(result) == 0 {
+        void_open_market(market, ctx.accounts.cranker.key());
+        return close_market_escrow_if_done(
+            market,
+            &ctx.accounts.market_escrow,
+            &ctx.accounts.operator,
+            &ctx.accounts.system_program,
+        );
+    }
+
+    let pot = market
+        .heads_total
+        .checked_add(market.tails_total)
+        .ok_or(GameError::MathOverflow)?;
+    let house_fee = bps_of(pot, market.house_fee_bps)?;
+    market.payout_pool = pot - house_fee;
+    market.status = MarketStatus::Resolved;
+
+    let market_key = market.key();
+    transfer_from_escrow(
+        &ctx.accounts.market_escrow,
+        ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.system_program,
+        &[&[b"market_escrow", market_key.as_ref(), &[market.escrow_bump]]],
+        house_fee,
+    )?;
+
+    emit!(MarketResolved {
+        event_version: EVENT_VERSION,
+        market_id: market.market_id,
+        result,
+        slot_hash,
+        randomness,
+        heads_total: market.heads_total,
+        tails_total: market.tails_total,
+        house_fee,
+        payout_pool: market.payout_pool,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RetireChampion<'info> {
+    #[account(mut, address = room.champion @ GameError::Unauthorized)]
+    pub champion: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"room", room.creator.as_ref(), &room.room_id.to_le_bytes()],
+        bump = room.bump
+    )]
+    pub room: Account<'info, Room>,
+
+    #[account(
+        mut,
+        seeds = [b"room_escrow", room.key().as_ref()],
+        bump = room.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the champion's and challengers' stakes
+    pub room_escrow: AccountInfo<'info>,
+
+    #[account(mut, address = room.creator)]
+    /// CHECK: Room creator; gets the room's rent and the escrow's reserve back
+    pub creator: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The champion walks away with their stake and the room closes. Only possible
+// with an empty queue, so a champion can't dodge a challenger already waiting.
+pub(crate) fn handler(ctx: Context<RetireChampion>) -> Result<()> {
+    let room = &ctx.accounts.room;
+    require!(room.queue_len == 0, GameError::RoomNotEmpty);
+
+    let room_key = room.key();
+    let seeds: &[&[u8]] = &[b"room_escrow", room_key.as_ref(), &[room.escrow_bump]];
+    transfer_from_escrow(
+        &ctx.accounts.room_escrow,
+        ctx.accounts.champion.to_account_info(),
+        &ctx.accounts.system_program,
+        &[seeds],
+        room.stake,
+    )?;
+    close_escrow(
+        &ctx.accounts.room_escrow,
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+        &[seeds],
+    )?;
+
+    emit!(ChampionRetired {
+        event_version: EVENT_VERSION,
+        room_id: room.room_id,
+        creator: room.creator,
+        champion: room.champion,
+        streak: room.streak,
+        winnings: room.winnings,
+        bouts: room.bouts,
+    });
+
+    Ok(())
+}
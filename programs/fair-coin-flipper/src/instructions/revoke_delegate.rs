@@ -0,0 +1,26 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"delegate", player.key().as_ref()],
+        bump = delegate.bump,
+        close = player
+    )]
+    pub delegate: Account<'info, Delegate>,
+}
+
+// Revoke the player's session key and reclaim the delegate account's rent
+pub(crate) fn handler(ctx: Context<RevokeDelegate>) -> Result<()> {
+    emit!(DelegateRevoked {
+        event_version: EVENT_VERSION,
+        player: ctx.accounts.player.key(),
+        session_key: ctx.accounts.delegate.session_key,
+    });
+
+    Ok(())
+}
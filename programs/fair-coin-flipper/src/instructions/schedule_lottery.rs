@@ -0,0 +1,98 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(lottery_id: u64)]
+pub struct ScheduleLottery<'info> {
+    #[account(
+        mut,
+        constraint = operator.key() == global_state.operator @ GameError::Unauthorized
+    )]
+    pub operator: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Lottery::INIT_SPACE,
+        seeds = [b"lottery".as_ref(), &lottery_id.to_le_bytes()],
+        bump
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(mut, seeds = [b"lottery_escrow", lottery.key().as_ref()], bump)]
+    /// CHECK: System-owned PDA holding the lottery's ticket sales
+    pub lottery_escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Open a raffle drawn the same way as a market (see schedule_market). The ticket
+// price is held to the game bet limits and sales stop at `cutoff_slot` or once
+// `max_entries` tickets are sold, whichever comes first.
+pub(crate) fn handler(
+    ctx: Context<ScheduleLottery>,
+    lottery_id: u64,
+    secret_commitment: [u8; 32],
+    ticket_price: u64,
+    max_entries: u32,
+    cutoff_slot: u64,
+    draw_slot: u64,
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let slot = Clock::get()?.slot;
+
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    require!(
+        ticket_price >= global_state.min_bet_amount
+            && ticket_price <= global_state.max_bet_amount
+            && max_entries > 0
+            && max_entries <= MAX_LOTTERY_ENTRIES,
+        GameError::InvalidLotteryConfig
+    );
+    verify_draw_schedule(slot, cutoff_slot, draw_slot)?;
+
+    ctx.accounts.lottery.set_inner(Lottery {
+        lottery_id,
+        operator: ctx.accounts.operator.key(),
+        secret_commitment,
+        ticket_price,
+        max_entries,
+        entries: 0,
+        claimed_tickets: 0,
+        cutoff_slot,
+        draw_slot,
+        house_fee_bps: global_state.house_fee_bps,
+        status: LotteryStatus::Open,
+        winning_ticket: 0,
+        prize: 0,
+        randomness: [0; 32],
+        escrow_bump: ctx.bumps.lottery_escrow,
+        bump: ctx.bumps.lottery,
+    });
+
+    // Returned to the operator once every ticket has claimed
+    fund_draw_escrow(
+        &ctx.accounts.operator,
+        &ctx.accounts.lottery_escrow,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(LotteryScheduled {
+        event_version: EVENT_VERSION,
+        lottery_id,
+        operator: ctx.accounts.operator.key(),
+        secret_commitment,
+        ticket_price,
+        max_entries,
+        cutoff_slot,
+        draw_slot,
+        house_fee_bps: global_state.house_fee_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ScheduleMarket<'info> {
+    #[account(
+        mut,
+        constraint = operator.key() == global_state.operator @ GameError::Unauthorized
+    )]
+    pub operator: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market".as_ref(), &market_id.to_le_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut, seeds = [b"market_escrow", market.key().as_ref()], bump)]
+    /// CHECK: System-owned PDA holding the market's stakes
+    pub market_escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Open a public flip drawn from `draw_slot`'s hash alone, so once it's scheduled
+// no one, the operator included, holds anything that decides the result. Stakes
+// are taken until `cutoff_slot`, which must come before the draw slot so no one
+// backs a side after its hash is known.
+pub(crate) fn handler(
+    ctx: Context<ScheduleMarket>,
+    market_id: u64,
+    cutoff_slot: u64,
+    draw_slot: u64,
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let slot = Clock::get()?.slot;
+
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    verify_draw_schedule(slot, cutoff_slot, draw_slot)?;
+
+    ctx.accounts.market.set_inner(Market {
+        market_id,
+        operator: ctx.accounts.operator.key(),
+        cutoff_slot,
+        draw_slot,
+        house_fee_bps: global_state.house_fee_bps,
+        heads_total: 0,
+        tails_total: 0,
+        positions: 0,
+        claimed_positions: 0,
+        status: MarketStatus::Open,
+        result: CoinSide::Heads,
+        payout_pool: 0,
+        randomness: [0; 32],
+        escrow_bump: ctx.bumps.market_escrow,
+        bump: ctx.bumps.market,
+    });
+
+    // Returned to the operator once every position has claimed
+    fund_draw_escrow(
+        &ctx.accounts.operator,
+        &ctx.accounts.market_escrow,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(MarketScheduled {
+        event_version: EVENT_VERSION,
+        market_id,
+        operator: ctx.accounts.operator.key(),
+        cutoff_slot,
+        draw_slot,
+        house_fee_bps: global_state.house_fee_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetArchiveTree<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ GameError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, seeds = [b"admin_log"], bump = admin_log.bump)]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+
+    #[account(mut, owner = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    /// CHECK: Zeroed tree account; its size and header are validated by the compression program
+    pub merkle_tree: AccountInfo<'info>,
+
+    #[account(seeds = [b"archive_authority"], bump)]
+    /// CHECK: PDA that signs as the tree authority
+    pub archive_authority: AccountInfo<'info>,
+
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    /// CHECK: SPL account compression program
+    pub compression_program: AccountInfo<'info>,
+
+    #[account(address = NOOP_PROGRAM_ID)]
+    /// CHECK: SPL noop program used as the compression log wrapper
+    pub noop_program: AccountInfo<'info>,
+}
+
+// Point archiving at a fresh concurrent merkle tree. The client allocates the
+// zeroed tree account owned by the compression program; this initializes it
+// with the archive authority PDA as the only writer.
+pub(crate) fn handler(
+    ctx: Context<SetArchiveTree>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let mut args = max_depth.to_le_bytes().to_vec();
+    args.extend_from_slice(&max_buffer_size.to_le_bytes());
+    invoke_compression(
+        "init_empty_merkle_tree",
+        args,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.archive_authority,
+        &ctx.accounts.noop_program,
+        &ctx.accounts.compression_program,
+        ctx.bumps.archive_authority,
+    )?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_tree = global_state.archive_tree;
+    global_state.archive_tree = ctx.accounts.merkle_tree.key();
+    global_state.archived_games = 0;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetArchiveTree,
+        ctx.accounts.authority.key(),
+        global_state.archive_tree,
+        max_depth as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(ArchiveTreeUpdated {
+        event_version: EVENT_VERSION,
+        previous_tree,
+        new_tree: global_state.archive_tree,
+        max_depth,
+        max_buffer_size,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::*;
+
+// Require players to hold an attestation from this issuer before creating or
+// joining games; Pubkey::default() turns the requirement off
+pub(crate) fn handler(ctx: Context<AdminAction>, issuer: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let previous_issuer = global_state.attestation_issuer;
+    global_state.attestation_issuer = issuer;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetAttestationIssuer,
+        ctx.accounts.authority.key(),
+        issuer,
+        0,
+        clock.unix_timestamp,
+    );
+
+    emit!(AttestationIssuerUpdated {
+        event_version: EVENT_VERSION,
+        previous_issuer,
+        new_issuer: issuer,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::*;
+
+// Operational setting: how long a game must sit before it can be cancelled
+pub(crate) fn handler(ctx: Context<AdminAction>, seconds: i64) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    require!(
+        (MIN_CANCEL_TIMEOUT_SECONDS..=MAX_CANCEL_TIMEOUT_SECONDS).contains(&seconds),
+        GameError::InvalidConfigValue
+    );
+
+    let previous_seconds = global_state.cancel_timeout_seconds;
+    global_state.cancel_timeout_seconds = seconds;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetCancelTimeout,
+        ctx.accounts.authority.key(),
+        Pubkey::default(),
+        seconds as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(CancelTimeoutUpdated {
+        event_version: EVENT_VERSION,
+        previous_seconds,
+        new_seconds: seconds,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::*;
+
+// Turn the CPI guard on or off and replace the allowlist of calling programs
+pub(crate) fn handler(
+    ctx: Context<AdminAction>,
+    enabled: bool,
+    allowed_callers: Vec<Pubkey>,
+) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    require!(
+        allowed_callers.len() <= MAX_ALLOWED_CPI_CALLERS,
+        GameError::TooManyAllowedCallers
+    );
+
+    global_state.cpi_guard_enabled = enabled;
+    global_state.allowed_cpi_callers = [Pubkey::default(); MAX_ALLOWED_CPI_CALLERS];
+    global_state.allowed_cpi_callers[..allowed_callers.len()].copy_from_slice(&allowed_callers);
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetCpiGuard,
+        ctx.accounts.authority.key(),
+        Pubkey::default(),
+        enabled as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(CpiGuardUpdated {
+        event_version: EVENT_VERSION,
+        enabled,
+        allowed_callers,
+    });
+
+    Ok(())
+}
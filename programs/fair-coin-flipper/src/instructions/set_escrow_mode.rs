@@ -0,0 +1,64 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetEscrowMode<'info> {
+    pub authority: Signer<'info>,
+
+    // Funds the vault's rent the first time vault mode is turned on
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ GameError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + EscrowVault::INIT_SPACE,
+        seeds = [b"escrow_vault"],
+        bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+
+    #[account(mut, seeds = [b"admin_log"], bump = admin_log.bump)]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Choose where new games keep their stakes: the shared escrow vault, or a fresh
+// escrow PDA per game. Games already open keep the escrow they were created
+// with, so the mode can be switched at any time.
+pub(crate) fn handler(ctx: Context<SetEscrowMode>, vault_escrow: bool) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let escrow_vault = &mut ctx.accounts.escrow_vault;
+    escrow_vault.bump = ctx.bumps.escrow_vault;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.vault_escrow = vault_escrow;
+    global_state.escrow_vault_bump = escrow_vault.bump;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetEscrowMode,
+        ctx.accounts.authority.key(),
+        escrow_vault.key(),
+        vault_escrow as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(EscrowModeUpdated {
+        event_version: EVENT_VERSION,
+        vault_escrow,
+        vault: escrow_vault.key(),
+        liabilities: escrow_vault.liabilities,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::*;
+
+// Minimum gap between a player's resolved game and their next create or join
+pub(crate) fn handler(ctx: Context<AdminAction>, seconds: i64) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    require!(
+        (0..=MAX_GAME_COOLDOWN_SECONDS).contains(&seconds),
+        GameError::InvalidConfigValue
+    );
+
+    let previous_seconds = global_state.game_cooldown_seconds;
+    global_state.game_cooldown_seconds = seconds;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetGameCooldown,
+        ctx.accounts.authority.key(),
+        Pubkey::default(),
+        seconds as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(GameCooldownUpdated {
+        event_version: EVENT_VERSION,
+        previous_seconds,
+        new_seconds: seconds,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::*;
+
+// Chance and size of golden flips. A resolution is golden 1 in `odds` (0 turns
+// them off) and pays the winner `bonus_bps` of the pot from the promo vault.
+pub(crate) fn handler(ctx: Context<AdminAction>, odds: u32, bonus_bps: u64) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    require!(
+        bonus_bps <= MAX_GOLDEN_FLIP_BONUS_BPS,
+        GameError::InvalidConfigValue
+    );
+
+    let previous_odds = global_state.golden_flip_odds;
+    let previous_bonus_bps = global_state.golden_flip_bonus_bps;
+    global_state.golden_flip_odds = odds;
+    global_state.golden_flip_bonus_bps = bonus_bps;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetGoldenFlip,
+        ctx.accounts.authority.key(),
+        Pubkey::default(),
+        odds as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(GoldenFlipUpdated {
+        event_version: EVENT_VERSION,
+        previous_odds,
+        new_odds: odds,
+        previous_bonus_bps,
+        new_bonus_bps: bonus_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::*;
+
+// Subscribe a plugin program to resolutions; Pubkey::default() unsubscribes
+pub(crate) fn handler(ctx: Context<AdminAction>, hook_program: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let previous_hook_program = global_state.hook_program;
+    global_state.hook_program = hook_program;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetHookProgram,
+        ctx.accounts.authority.key(),
+        hook_program,
+        0,
+        clock.unix_timestamp,
+    );
+
+    emit!(HookProgramUpdated {
+        event_version: EVENT_VERSION,
+        previous_hook_program,
+        new_hook_program: hook_program,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetLossLimit<'info> {
+    pub player: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Set the caller's own limit on net losses per season; 0 removes it. A limit can
+// be tightened at any time, but once the player has played in the current season
+// raising or removing it waits for the next season.
+pub(crate) fn handler(ctx: Context<SetLossLimit>, season_loss_limit: u64) -> Result<()> {
+    let stats = &mut ctx.accounts.player_stats;
+    let player = ctx.accounts.player.key();
+    init_player_stats_if_needed(stats, player, ctx.bumps.player_stats);
+
+    let current = stats.season_loss_limit;
+    let loosens = current != 0 && (season_loss_limit == 0 || season_loss_limit > current);
+    let played_this_season = stats.season.season_id == ctx.accounts.global_state.current_season_id
+        && stats.season.games_played > 0;
+    require!(!(loosens && played_this_season), GameError::LossLimitLocked);
+
+    stats.season_loss_limit = season_loss_limit;
+
+    emit!(LossLimitUpdated {
+        event_version: EVENT_VERSION,
+        player,
+        season_loss_limit,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::*;
+
+// Require joins to carry a ticket signed by this matchmaking service;
+// Pubkey::default() opens joining back up
+pub(crate) fn handler(ctx: Context<AdminAction>, matchmaker: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let previous_matchmaker = global_state.matchmaker;
+    global_state.matchmaker = matchmaker;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetMatchmaker,
+        ctx.accounts.authority.key(),
+        matchmaker,
+        0,
+        clock.unix_timestamp,
+    );
+
+    emit!(MatchmakerUpdated {
+        event_version: EVENT_VERSION,
+        previous_matchmaker,
+        new_matchmaker: matchmaker,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::*;
+
+// Assign the crank operator; it can settle games but never touch config or funds
+pub(crate) fn handler(ctx: Context<AdminAction>, operator: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let previous_operator = global_state.operator;
+    global_state.operator = operator;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetOperator,
+        ctx.accounts.authority.key(),
+        operator,
+        0,
+        clock.unix_timestamp,
+    );
+
+    emit!(OperatorUpdated {
+        event_version: EVENT_VERSION,
+        previous_operator,
+        new_operator: operator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::*;
+
+// Stop new games from starting without touching games already in flight:
+// selections, reveals, resolutions, and cancellations stay available.
+pub(crate) fn handler(ctx: Context<AdminAction>, mode: PauseMode) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let previous_mode = global_state.pause_mode;
+    global_state.pause_mode = mode;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetPauseMode,
+        ctx.accounts.authority.key(),
+        Pubkey::default(),
+        mode as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(PauseModeUpdated {
+        event_version: EVENT_VERSION,
+        previous_mode,
+        new_mode: mode,
+    });
+    if previous_mode == PauseMode::Active && mode != PauseMode::Active {
+        emit!(Paused {
+            event_version: EVENT_VERSION,
+            mode,
+            paused_by: ctx.accounts.authority.key(),
+            paused_at: clock.unix_timestamp,
+        });
+    } else if previous_mode != PauseMode::Active && mode == PauseMode::Active {
+        emit!(Unpaused {
+            event_version: EVENT_VERSION,
+            unpaused_by: ctx.accounts.authority.key(),
+            unpaused_at: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::*;
+
+// Terms version players must accept before creating or joining games; 0 turns
+// the requirement off. Bumping it makes everyone accept the new version.
+pub(crate) fn handler(ctx: Context<AdminAction>, version: u32) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let previous_version = global_state.terms_version;
+    global_state.terms_version = version;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::SetTermsVersion,
+        ctx.accounts.authority.key(),
+        Pubkey::default(),
+        version as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(TermsVersionUpdated {
+        event_version: EVENT_VERSION,
+        previous_version,
+        new_version: version,
+    });
+
+    Ok(())
+}
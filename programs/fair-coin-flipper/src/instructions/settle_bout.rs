@@ -0,0 +1,121 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SettleBout<'info> {
+    #[account(mut, address = room.champion @ GameError::Unauthorized)]
+    pub champion: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"room", room.creator.as_ref(), &room.room_id.to_le_bytes()],
+        bump = room.bump
+    )]
+    pub room: Account<'info, Room>,
+
+    #[account(
+        mut,
+        seeds = [b"room_escrow", room.key().as_ref()],
+        bump = room.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the champion's and challengers' stakes
+    pub room_escrow: AccountInfo<'info>,
+
+    #[account(mut, address = room.head().ok_or(GameError::NoBoutPending)?.player)]
+    /// CHECK: The challenger at the head of the queue; paid if they win
+    pub challenger: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", room.champion.as_ref()],
+        bump = champion_stats.bump
+    )]
+    pub champion_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [b"player_stats", challenger.key().as_ref()],
+        bump = challenger_stats.bump
+    )]
+    pub challenger_stats: Account<'info, PlayerStats>,
+
+    #[account(mut, seeds = [b"treasury"], bump = global_state.treasury_bump)]
+    /// CHECK: Program-derived fee treasury; holds lamports only
+    pub treasury: AccountInfo<'info>,
+
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    /// CHECK: SlotHashes sysvar, read raw by slot_hash_at
+    pub slot_hashes: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Draw the head challenger's bout by revealing the champion's secret, and commit
+// to the secret for the next defence. The challenger wins if the coin lands on
+// the side they called. The champion learns the result before revealing, but
+// holding back only lets the challenger claim the bout with forfeit_bout.
+pub(crate) fn handler(
+    ctx: Context<SettleBout>,
+    secret: u64,
+    next_commitment: [u8; 32],
+) -> Result<()> {
+    let room = &mut ctx.accounts.room;
+    let challenger = *room.head().ok_or(GameError::NoBoutPending)?;
+    let (slot_hash, randomness) = reveal_draw(
+        &room.champion_commitment,
+        secret,
+        room.bout_draw_slot,
+        &ctx.accounts.slot_hashes,
+    )?;
+    let result = coin_side(&randomness);
+    let challenger_won = result == challenger.side;
+    let champion = room.champion;
+
+    let winner = if challenger_won {
+        ctx.accounts.challenger.to_account_info()
+    } else {
+        ctx.accounts.champion.to_account_info()
+    };
+    let (house_fee, winnings) = pay_bout(
+        room,
+        &ctx.accounts.room_escrow,
+        winner,
+        &ctx.accounts.treasury,
+        &ctx.accounts.system_program,
+    )?;
+    let clock = Clock::get()?;
+    record_bout(
+        &mut ctx.accounts.champion_stats,
+        &mut ctx.accounts.challenger_stats,
+        challenger_won,
+        room.stake,
+        winnings,
+        ctx.accounts.global_state.current_season_id,
+        clock.unix_timestamp,
+    )?;
+    finish_bout(room, challenger_won, winnings, clock.slot)?;
+    if !challenger_won {
+        room.champion_commitment = next_commitment;
+    }
+
+    emit!(BoutSettled {
+        event_version: EVENT_VERSION,
+        room_id: room.room_id,
+        creator: room.creator,
+        champion,
+        challenger: challenger.player,
+        challenger_side: challenger.side,
+        result,
+        winner: room.champion,
+        secret,
+        slot_hash,
+        randomness,
+        house_fee,
+        winnings,
+        streak: room.streak,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(bracket_id: u64)]
+pub struct StartBracket<'info> {
+    #[account(
+        mut,
+        constraint = operator.key() == global_state.operator @ GameError::Unauthorized
+    )]
+    pub operator: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"match_queue".as_ref(), &match_queue.stake.to_le_bytes()],
+        bump = match_queue.bump
+    )]
+    pub match_queue: Account<'info, MatchQueue>,
+
+    #[account(
+        mut,
+        seeds = [b"match_queue_escrow", match_queue.key().as_ref()],
+        bump = match_queue.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the queued players' stakes
+    pub match_queue_escrow: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Bracket::INIT_SPACE,
+        seeds = [b"bracket".as_ref(), &bracket_id.to_le_bytes()],
+        bump
+    )]
+    pub bracket: Box<Account<'info, Bracket>>,
+
+    #[account(mut, seeds = [b"bracket_escrow", bracket.key().as_ref()], bump)]
+    /// CHECK: System-owned PDA holding the bracket's stakes
+    pub bracket_escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Snapshot the front of a match queue into a new bracket: the largest power of two
+// players waiting, in the order they joined, with their stakes moved over. Anyone
+// left over keeps their place for the next bracket. `secret_commitment` is the
+// draw_commitment of the secret for round one.
+pub(crate) fn handler(
+    ctx: Context<StartBracket>,
+    bracket_id: u64,
+    secret_commitment: [u8; 32],
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let match_queue = &mut ctx.accounts.match_queue;
+    let slot = Clock::get()?.slot;
+
+    require!(
+        global_state.pause_mode == PauseMode::Active,
+        GameError::NewGamesPaused
+    );
+    let size = bracket_size(match_queue.len as usize).ok_or(GameError::BracketTooSmall)?;
+    let mut players = [Pubkey::default(); MAX_BRACKET_PLAYERS];
+    players[..size].copy_from_slice(&match_queue.players[..size]);
+    match_queue.remove(0, size);
+
+    let round_draw_slot = slot.saturating_add(BRACKET_ROUND_DELAY_SLOTS);
+    ctx.accounts.bracket.set_inner(Bracket {
+        bracket_id,
+        operator: ctx.accounts.operator.key(),
+        stake: match_queue.stake,
+        house_fee_bps: global_state.house_fee_bps,
+        players,
+        player_count: size as u8,
+        alive: players,
+        alive_count: size as u8,
+        round: 0,
+        secret_commitment,
+        round_draw_slot,
+        status: BracketStatus::Running,
+        champion: Pubkey::default(),
+        prize: 0,
+        claimed: 0,
+        escrow_bump: ctx.bumps.bracket_escrow,
+        bump: ctx.bumps.bracket,
+    });
+
+    // Returned to the operator once the pot or every refund is claimed
+    fund_draw_escrow(
+        &ctx.accounts.operator,
+        &ctx.accounts.bracket_escrow,
+        &ctx.accounts.system_program,
+    )?;
+    let queue_key = match_queue.key();
+    transfer_from_escrow(
+        &ctx.accounts.match_queue_escrow,
+        ctx.accounts.bracket_escrow.clone(),
+        &ctx.accounts.system_program,
+        &[&[
+            b"match_queue_escrow",
+            queue_key.as_ref(),
+            &[match_queue.escrow_bump],
+        ]],
+        match_queue
+            .stake
+            .checked_mul(size as u64)
+            .ok_or(GameError::MathOverflow)?,
+    )?;
+
+    emit!(BracketStarted {
+        event_version: EVENT_VERSION,
+        bracket_id,
+        operator: ctx.accounts.operator.key(),
+        stake: match_queue.stake,
+        players: players[..size].to_vec(),
+        round_draw_slot,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::*;
+
+// Roll over to a new season; per-season stats reset lazily on next play
+pub(crate) fn handler(ctx: Context<AdminAction>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
+
+    let previous_season_id = global_state.current_season_id;
+    global_state.current_season_id = previous_season_id
+        .checked_add(1)
+        .ok_or(GameError::MathOverflow)?;
+    global_state.season_started_at = clock.unix_timestamp;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::StartNewSeason,
+        ctx.accounts.authority.key(),
+        Pubkey::default(),
+        global_state.current_season_id as u64,
+        clock.unix_timestamp,
+    );
+
+    emit!(SeasonStarted {
+        event_version: EVENT_VERSION,
+        season_id: global_state.current_season_id,
+        previous_season_id,
+        started_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
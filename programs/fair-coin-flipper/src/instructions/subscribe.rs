@@ -0,0 +1,132 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Pays rent for anything created here; may be a sponsor or the fee payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(seeds = [b"terms", player.key().as_ref()], bump = terms_acceptance.bump)]
+    pub terms_acceptance: Option<Account<'info, TermsAcceptance>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", player.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut, seeds = [b"subscription_escrow", subscription.key().as_ref()], bump)]
+    /// CHECK: System-owned PDA holding the subscription's budget
+    pub subscription_escrow: AccountInfo<'info>,
+
+    // Created now so joins made for the player don't have to pay for them
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Achievements::INIT_SPACE,
+        seeds = [b"achievements", player.key().as_ref()],
+        bump
+    )]
+    pub player_achievements: Account<'info, Achievements>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", player.key().as_ref()],
+        bump
+    )]
+    pub player_history: Box<Account<'info, GameHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Deposit a budget and let join_game_for_subscription join up to `max_games`
+// games at `stake` for the player, optionally only against `opponent`
+pub(crate) fn handler(
+    ctx: Context<Subscribe>,
+    stake: u64,
+    max_games: u32,
+    opponent: Pubkey,
+    budget: u64,
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let player = ctx.accounts.player.key();
+
+    require!(stake >= global_state.min_bet_amount, GameError::BetTooLow);
+    require!(stake <= global_state.max_bet_amount, GameError::BetTooHigh);
+    require!(
+        max_games > 0 && budget >= stake && opponent != player,
+        GameError::InvalidSubscription
+    );
+    verify_terms(global_state, ctx.accounts.terms_acceptance.as_ref())?;
+
+    init_player_stats_if_needed(
+        &mut ctx.accounts.player_stats,
+        player,
+        ctx.bumps.player_stats,
+    );
+    init_achievements_if_needed(
+        &mut ctx.accounts.player_achievements,
+        player,
+        ctx.bumps.player_achievements,
+    );
+    init_game_history_if_needed(
+        &mut ctx.accounts.player_history,
+        player,
+        ctx.bumps.player_history,
+    );
+
+    ctx.accounts.subscription.set_inner(Subscription {
+        player,
+        stake,
+        max_games,
+        games_joined: 0,
+        opponent,
+        escrow_bump: ctx.bumps.subscription_escrow,
+        bump: ctx.bumps.subscription,
+    });
+
+    // Returned to the player with the rest of the budget on cancellation. Tops up
+    // only what's missing, since refunds may have arrived after a cancellation.
+    fund_rent_reserve(
+        &ctx.accounts.payer,
+        &ctx.accounts.subscription_escrow,
+        &ctx.accounts.system_program,
+    )?;
+    deposit_stake(
+        ctx.accounts.player.to_account_info(),
+        &ctx.accounts.subscription_escrow,
+        &ctx.accounts.system_program,
+        budget,
+    )?;
+
+    emit!(Subscribed {
+        event_version: EVENT_VERSION,
+        player,
+        stake,
+        max_games,
+        opponent,
+        budget,
+    });
+
+    Ok(())
+}
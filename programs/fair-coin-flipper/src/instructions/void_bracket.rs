@@ -0,0 +1,27 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct VoidBracket<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bracket".as_ref(), &bracket.bracket_id.to_le_bytes()],
+        bump = bracket.bump
+    )]
+    pub bracket: Box<Account<'info, Bracket>>,
+}
+
+// Anyone can void a bracket whose current round wasn't drawn before its window
+// closed, after which every player claims back their stake without a fee. Rounds
+// already drawn don't count; the pot is only ever paid to a champion.
+pub(crate) fn handler(ctx: Context<VoidBracket>) -> Result<()> {
+    let bracket = &mut ctx.accounts.bracket;
+    require!(
+        bracket.status == BracketStatus::Running
+            && is_past_draw_window(bracket.round_draw_slot, Clock::get()?.slot),
+        GameError::NotVoidable
+    );
+    void_running_bracket(bracket, ctx.accounts.caller.key());
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct VoidLottery<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery".as_ref(), &lottery.lottery_id.to_le_bytes()],
+        bump = lottery.bump
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery_escrow", lottery.key().as_ref()],
+        bump = lottery.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the lottery's ticket sales
+    pub lottery_escrow: AccountInfo<'info>,
+
+    #[account(mut, address = lottery.operator)]
+    /// CHECK: Gets the escrow's reserve back if no tickets were sold
+    pub operator: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can void a lottery that wasn't drawn before its window closed, after
+// which every ticket claims back its price without a fee
+pub(crate) fn handler(ctx: Context<VoidLottery>) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    require!(
+        lottery.status == LotteryStatus::Open
+            && is_past_draw_window(lottery.draw_slot, Clock::get()?.slot),
+        GameError::NotVoidable
+    );
+
+    void_open_lottery(lottery, ctx.accounts.caller.key());
+    close_lottery_escrow_if_done(
+        lottery,
+        &ctx.accounts.lottery_escrow,
+        &ctx.accounts.operator,
+        &ctx.accounts.system_program,
+    )
+}
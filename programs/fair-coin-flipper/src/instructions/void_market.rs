@@ -0,0 +1,46 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct VoidMarket<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market".as_ref(), &market.market_id.to_le_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_escrow", market.key().as_ref()],
+        bump = market.escrow_bump
+    )]
+    /// CHECK: System-owned PDA holding the market's stakes
+    pub market_escrow: AccountInfo<'info>,
+
+    #[account(mut, address = market.operator)]
+    /// CHECK: Gets the escrow's reserve back if no one backed the market
+    pub operator: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can void a market that wasn't drawn before its window closed, after which
+// every position claims back its stakes without a fee
+pub(crate) fn handler(ctx: Context<VoidMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    require!(
+        market.status == MarketStatus::Open
+            && is_past_draw_window(market.draw_slot, Clock::get()?.slot),
+        GameError::NotVoidable
+    );
+
+    void_open_market(market, ctx.accounts.caller.key());
+    close_market_escrow_if_done(
+        market,
+        &ctx.accounts.market_escrow,
+        &ctx.accounts.operator,
+        &ctx.accounts.system_program,
+    )
+}
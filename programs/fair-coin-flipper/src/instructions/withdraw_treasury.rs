@@ -0,0 +1,76 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ GameError::Unauthorized,
+        has_one = house_wallet
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, seeds = [b"treasury"], bump = global_state.treasury_bump)]
+    /// CHECK: Program-derived fee treasury; holds lamports only
+    pub treasury: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: Withdrawal destination; tied to GlobalState by has_one
+    pub house_wallet: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"admin_log"], bump = admin_log.bump)]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Move accrued fees from the treasury PDA to the configured house wallet
+pub(crate) fn handler(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // The treasury always keeps its rent-exempt reserve so small fees can land
+    let balance = ctx.accounts.treasury.lamports();
+    let remaining = balance
+        .checked_sub(amount)
+        .ok_or(GameError::InsufficientTreasuryBalance)?;
+    require!(
+        remaining >= Rent::get()?.minimum_balance(0),
+        GameError::InsufficientTreasuryBalance
+    );
+
+    let seeds = &[
+        b"treasury".as_ref(),
+        &[ctx.accounts.global_state.treasury_bump],
+    ];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.house_wallet.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+
+    log_admin_action(
+        &mut ctx.accounts.admin_log,
+        AdminLogAction::WithdrawTreasury,
+        ctx.accounts.authority.key(),
+        ctx.accounts.house_wallet.key(),
+        amount,
+        clock.unix_timestamp,
+    );
+
+    emit!(TreasuryWithdrawn {
+        event_version: EVENT_VERSION,
+        destination: ctx.accounts.house_wallet.key(),
+        amount,
+        remaining,
+    });
+
+    Ok(())
+}
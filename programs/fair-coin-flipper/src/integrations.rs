@@ -0,0 +1,161 @@
+use crate::*;
+
+// Accounts shared by every settlement, in a stable order. Only accounts that are
+// never signers belong here; lookup tables can't supply signatures.
+pub(crate) fn shared_lookup_addresses(global_state: &GlobalState) -> Vec<Pubkey> {
+    let mut addresses = vec![
+        Pubkey::find_program_address(&[b"global_state"], &crate::ID).0,
+        Pubkey::find_program_address(&[b"treasury"], &crate::ID).0,
+        Pubkey::find_program_address(&[b"recent_games"], &crate::ID).0,
+        Pubkey::find_program_address(&[b"referral_leaderboard"], &crate::ID).0,
+        Pubkey::find_program_address(&[b"__event_authority"], &crate::ID).0,
+        crate::ID,
+        system_program::ID,
+        anchor_lang::solana_program::sysvar::instructions::ID,
+        MEMO_PROGRAM_ID,
+    ];
+    if global_state.hook_program != Pubkey::default() {
+        addresses.push(global_state.hook_program);
+        addresses.push(Pubkey::find_program_address(&[b"hook_authority"], &crate::ID).0);
+    }
+    if global_state.archive_tree != Pubkey::default() {
+        addresses.push(global_state.archive_tree);
+        addresses.push(Pubkey::find_program_address(&[b"archive_authority"], &crate::ID).0);
+        addresses.push(ACCOUNT_COMPRESSION_PROGRAM_ID);
+        addresses.push(NOOP_PROGRAM_ID);
+    }
+    if global_state.golden_flip_odds != 0 {
+        addresses.push(Pubkey::find_program_address(&[b"promo_vault"], &crate::ID).0);
+    }
+    addresses
+}
+
+// ExtendLookupTable { new_addresses }, bincode-encoded like every lookup table instruction
+pub(crate) fn append_lookup_addresses(
+    accounts: &ManageLookupTable,
+    addresses: &[Pubkey],
+    operator: Pubkey,
+    authority_bump: u8,
+) -> Result<()> {
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&(addresses.len() as u64).to_le_bytes());
+    for address in addresses {
+        data.extend_from_slice(address.as_ref());
+    }
+    invoke_lookup_table(accounts, data, operator, authority_bump)
+}
+
+// CPI into the address lookup table program with the per-operator authority PDA
+// signing and the operator paying for the table's rent
+pub(crate) fn invoke_lookup_table(
+    accounts: &ManageLookupTable,
+    data: Vec<u8>,
+    operator: Pubkey,
+    authority_bump: u8,
+) -> Result<()> {
+    invoke_signed(
+        &Instruction {
+            program_id: ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(accounts.lookup_table.key(), false),
+                AccountMeta::new_readonly(accounts.lookup_authority.key(), true),
+                AccountMeta::new(operator, true),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        },
+        &[
+            accounts.lookup_table.to_account_info(),
+            accounts.lookup_authority.to_account_info(),
+            accounts.operator.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.address_lookup_table_program.to_account_info(),
+        ],
+        &[&[b"lookup_authority", operator.as_ref(), &[authority_bump]]],
+    )?;
+    Ok(())
+}
+
+// CPI into the account compression program with the archive authority PDA signing.
+// Its instructions are Anchor-encoded, so the data is the global sighash plus args.
+pub(crate) fn invoke_compression<'info>(
+    name: &str,
+    args: Vec<u8>,
+    merkle_tree: &AccountInfo<'info>,
+    archive_authority: &AccountInfo<'info>,
+    noop_program: &AccountInfo<'info>,
+    compression_program: &AccountInfo<'info>,
+    archive_authority_bump: u8,
+) -> Result<()> {
+    let mut data = hash(format!("global:{}", name).as_bytes()).to_bytes()[..8].to_vec();
+    data.extend_from_slice(&args);
+
+    invoke_signed(
+        &Instruction {
+            program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(merkle_tree.key(), false),
+                AccountMeta::new_readonly(archive_authority.key(), true),
+                AccountMeta::new_readonly(noop_program.key(), false),
+            ],
+            data,
+        },
+        &[
+            merkle_tree.clone(),
+            archive_authority.clone(),
+            noop_program.clone(),
+            compression_program.clone(),
+        ],
+        &[&[b"archive_authority", &[archive_authority_bump]]],
+    )?;
+    Ok(())
+}
+
+// Log a settled game's summary through the noop program so indexers can rebuild
+// the leaf, append the leaf to the archive tree, and emit GameArchived
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn append_archive_leaf<'info>(
+    archived: &ArchivedGame,
+    global_state: &mut Account<'info, GlobalState>,
+    merkle_tree: &AccountInfo<'info>,
+    archive_authority: &AccountInfo<'info>,
+    noop_program: &AccountInfo<'info>,
+    compression_program: &AccountInfo<'info>,
+    archive_authority_bump: u8,
+    seq: u64,
+) -> Result<()> {
+    let archived_bytes = archived.try_to_vec()?;
+    let leaf = hash(&archived_bytes).to_bytes();
+
+    invoke(
+        &Instruction {
+            program_id: NOOP_PROGRAM_ID,
+            accounts: vec![],
+            data: archived_bytes,
+        },
+        &[noop_program.to_account_info()],
+    )?;
+    invoke_compression(
+        "append",
+        leaf.to_vec(),
+        merkle_tree,
+        archive_authority,
+        noop_program,
+        compression_program,
+        archive_authority_bump,
+    )?;
+
+    let leaf_index = global_state.archived_games;
+    global_state.archived_games = leaf_index.checked_add(1).ok_or(GameError::MathOverflow)?;
+
+    emit!(GameArchived {
+        event_version: EVENT_VERSION,
+        game_id: archived.game_id,
+        creator: archived.creator,
+        tree: global_state.archive_tree,
+        leaf_index,
+        leaf,
+        seq,
+    });
+    Ok(())
+}
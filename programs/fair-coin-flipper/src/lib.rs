@@ -0,0 +1,505 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{
+    get_stack_height, AccountMeta, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT,
+};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::pubkey;
+use anchor_lang::solana_program::sysvar::instructions::get_instruction_relative;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+#[cfg(not(feature = "no-entrypoint"))]
+use default_env::default_env;
+#[cfg(not(feature = "no-entrypoint"))]
+use solana_security_txt::security_txt;
+
+// Development-only logging. Release builds log nothing beyond the typed events, so
+// instructions don't spend compute formatting strings; build with `debug-logs` to
+// get these lines back.
+#[cfg(feature = "debug-logs")]
+macro_rules! debug_log {
+    ($($arg:tt)*) => {
+        msg!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "debug-logs"))]
+macro_rules! debug_log {
+    ($($arg:tt)*) => {};
+}
+
+// Layout: `instructions` holds one handler and its accounts context per file,
+// `state` the account and data types, and the remaining modules the helpers
+// shared between instructions. Everything is re-exported from the crate root so
+// modules import it with `use crate::*`.
+pub mod constants;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+pub mod transitions;
+
+mod admin;
+mod brackets;
+mod draws;
+mod fairness;
+mod guards;
+mod integrations;
+mod lifecycle;
+mod lotteries;
+mod markets;
+mod referrals;
+mod rooms;
+mod settlement;
+mod stats;
+
+pub use constants::*;
+pub use errors::*;
+pub use events::*;
+pub use instructions::*;
+pub use state::*;
+
+use admin::*;
+pub use brackets::*;
+pub use draws::*;
+pub use fairness::*;
+use guards::*;
+use integrations::*;
+use lifecycle::*;
+use lotteries::*;
+pub use markets::*;
+use referrals::*;
+pub use rooms::*;
+pub use settlement::*;
+pub use stats::*;
+use transitions::{advance, GameEvent};
+
+declare_id!("7CCbhfJx5fUPXZGRu9bqvztBiQHpYPaNL1rGFy9hrcf6");
+
+// Contact and source info explorers read from the deployed binary. The source
+// revision is stamped from CI so a verifiable build can be matched to a commit.
+#[cfg(not(feature = "no-entrypoint"))]
+security_txt! {
+    name: "Fair Coin Flipper",
+    project_url: "https://github.com/allouf/flipCoinFull",
+    contacts: "link:https://github.com/allouf/flipCoinFull/security/advisories/new",
+    policy: "https://github.com/allouf/flipCoinFull/blob/main/SECURITY.md",
+    preferred_languages: "en",
+    source_code: "https://github.com/allouf/flipCoinFull",
+    source_revision: default_env!("GITHUB_SHA", ""),
+    source_release: default_env!("GITHUB_REF_NAME", ""),
+    auditors: "None"
+}
+
+#[program]
+pub mod fair_coin_flipper {
+    use super::*;
+
+    pub fn create_game(
+        ctx: Context<CreateGame>,
+        game_id: u64,
+        bet_amount: u64,
+        allowed_joiners: Vec<Pubkey>,
+        join_lock_seconds: i64,
+        tags: u8,
+        repay_sponsor: bool,
+    ) -> Result<()> {
+        instructions::create_game::handler(
+            ctx,
+            game_id,
+            bet_amount,
+            allowed_joiners,
+            join_lock_seconds,
+            tags,
+            repay_sponsor,
+        )
+    }
+
+    pub fn create_games_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateGamesBatch<'info>>,
+        first_game_id: u64,
+        bet_amounts: Vec<u64>,
+        tags: u8,
+    ) -> Result<()> {
+        instructions::create_games_batch::handler(ctx, first_game_id, bet_amounts, tags)
+    }
+
+    pub fn join_game(ctx: Context<JoinGame>, repay_sponsor: bool, auto_pick: bool) -> Result<()> {
+        instructions::join_game::handler(ctx, repay_sponsor, auto_pick)
+    }
+
+    pub fn join_game_lite(ctx: Context<JoinGameLite>) -> Result<()> {
+        instructions::join_game_lite::handler(ctx)
+    }
+
+    pub fn subscribe(
+        ctx: Context<Subscribe>,
+        stake: u64,
+        max_games: u32,
+        opponent: Pubkey,
+        budget: u64,
+    ) -> Result<()> {
+        instructions::subscribe::handler(ctx, stake, max_games, opponent, budget)
+    }
+
+    pub fn join_game_for_subscription(ctx: Context<JoinGameForSubscription>) -> Result<()> {
+        instructions::join_game_for_subscription::handler(ctx)
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        instructions::cancel_subscription::handler(ctx)
+    }
+
+    pub fn offer_game_ownership(ctx: Context<OfferGameOwnership>, new_owner: Pubkey) -> Result<()> {
+        instructions::offer_game_ownership::handler(ctx, new_owner)
+    }
+
+    pub fn accept_game_ownership(ctx: Context<AcceptGameOwnership>) -> Result<()> {
+        instructions::accept_game_ownership::handler(ctx)
+    }
+
+    pub fn make_commitment(ctx: Context<MakeCommitment>, commitment: [u8; 32]) -> Result<()> {
+        instructions::make_commitment::handler(ctx, commitment)
+    }
+
+    pub fn make_commitment_relayed(
+        ctx: Context<MakeCommitmentRelayed>,
+        player: Pubkey,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::make_commitment_relayed::handler(ctx, player, commitment)
+    }
+
+    pub fn auto_pick_side(ctx: Context<AutoPickSide>) -> Result<()> {
+        instructions::auto_pick_side::handler(ctx)
+    }
+
+    pub fn reveal_choice(ctx: Context<RevealChoice>, choice: CoinSide, secret: u64) -> Result<()> {
+        instructions::reveal_choice::handler(ctx, choice, secret)
+    }
+
+    pub fn resolve_game_manual(ctx: Context<ResolveGameManual>) -> Result<()> {
+        instructions::resolve_game_manual::handler(ctx)
+    }
+
+    pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+        instructions::cancel_game::handler(ctx)
+    }
+
+    pub fn initialize_global_state(
+        ctx: Context<InitializeGlobalState>,
+        authority: Pubkey,
+        house_wallet: Pubkey,
+    ) -> Result<()> {
+        instructions::initialize_global_state::handler(ctx, authority, house_wallet)
+    }
+
+    pub fn start_new_season(ctx: Context<AdminAction>) -> Result<()> {
+        instructions::start_new_season::handler(ctx)
+    }
+
+    pub fn register_username(ctx: Context<RegisterUsername>, username: String) -> Result<()> {
+        instructions::register_username::handler(ctx, username)
+    }
+
+    pub fn initialize_recent_games(ctx: Context<InitializeRecentGames>) -> Result<()> {
+        instructions::initialize_recent_games::handler(ctx)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer::handler(ctx)
+    }
+
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        instructions::claim_referral_fees::handler(ctx)
+    }
+
+    pub fn initialize_referral_leaderboard(
+        ctx: Context<InitializeReferralLeaderboard>,
+    ) -> Result<()> {
+        instructions::initialize_referral_leaderboard::handler(ctx)
+    }
+
+    pub fn propose_authority(ctx: Context<AdminAction>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    pub fn propose_config_change(ctx: Context<AdminAction>, change: ConfigChange) -> Result<()> {
+        instructions::propose_config_change::handler(ctx, change)
+    }
+
+    pub fn execute_config_change(ctx: Context<AdminAction>) -> Result<()> {
+        instructions::execute_config_change::handler(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<AdminAction>) -> Result<()> {
+        instructions::cancel_config_change::handler(ctx)
+    }
+
+    pub fn emergency_recover(ctx: Context<EmergencyRecover>) -> Result<()> {
+        instructions::emergency_recover::handler(ctx)
+    }
+
+    pub fn set_cancel_timeout(ctx: Context<AdminAction>, seconds: i64) -> Result<()> {
+        instructions::set_cancel_timeout::handler(ctx, seconds)
+    }
+
+    pub fn set_operator(ctx: Context<AdminAction>, operator: Pubkey) -> Result<()> {
+        instructions::set_operator::handler(ctx, operator)
+    }
+
+    pub fn migrate_global_state(ctx: Context<MigrateGlobalState>) -> Result<()> {
+        instructions::migrate_global_state::handler(ctx)
+    }
+
+    pub fn initialize_admin_log(ctx: Context<InitializeAdminLog>) -> Result<()> {
+        instructions::initialize_admin_log::handler(ctx)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury::handler(ctx, amount)
+    }
+
+    pub fn set_pause_mode(ctx: Context<AdminAction>, mode: PauseMode) -> Result<()> {
+        instructions::set_pause_mode::handler(ctx, mode)
+    }
+
+    pub fn set_attestation_issuer(ctx: Context<AdminAction>, issuer: Pubkey) -> Result<()> {
+        instructions::set_attestation_issuer::handler(ctx, issuer)
+    }
+
+    pub fn set_cpi_guard(
+        ctx: Context<AdminAction>,
+        enabled: bool,
+        allowed_callers: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_cpi_guard::handler(ctx, enabled, allowed_callers)
+    }
+
+    pub fn set_matchmaker(ctx: Context<AdminAction>, matchmaker: Pubkey) -> Result<()> {
+        instructions::set_matchmaker::handler(ctx, matchmaker)
+    }
+
+    pub fn set_hook_program(ctx: Context<AdminAction>, hook_program: Pubkey) -> Result<()> {
+        instructions::set_hook_program::handler(ctx, hook_program)
+    }
+
+    pub fn set_escrow_mode(ctx: Context<SetEscrowMode>, vault_escrow: bool) -> Result<()> {
+        instructions::set_escrow_mode::handler(ctx, vault_escrow)
+    }
+
+    pub fn set_game_cooldown(ctx: Context<AdminAction>, seconds: i64) -> Result<()> {
+        instructions::set_game_cooldown::handler(ctx, seconds)
+    }
+
+    pub fn set_terms_version(ctx: Context<AdminAction>, version: u32) -> Result<()> {
+        instructions::set_terms_version::handler(ctx, version)
+    }
+
+    pub fn set_golden_flip(ctx: Context<AdminAction>, odds: u32, bonus_bps: u64) -> Result<()> {
+        instructions::set_golden_flip::handler(ctx, odds, bonus_bps)
+    }
+
+    pub fn fund_promo_vault(ctx: Context<FundPromoVault>, amount: u64) -> Result<()> {
+        instructions::fund_promo_vault::handler(ctx, amount)
+    }
+
+    pub fn set_loss_limit(ctx: Context<SetLossLimit>, season_loss_limit: u64) -> Result<()> {
+        instructions::set_loss_limit::handler(ctx, season_loss_limit)
+    }
+
+    pub fn freeze_self(ctx: Context<FreezeSelf>, until: i64) -> Result<()> {
+        instructions::freeze_self::handler(ctx, until)
+    }
+
+    pub fn accept_terms(ctx: Context<AcceptTerms>, version: u32) -> Result<()> {
+        instructions::accept_terms::handler(ctx, version)
+    }
+
+    pub fn assert_escrow_invariants(ctx: Context<AssertEscrowInvariants>) -> Result<()> {
+        instructions::assert_escrow_invariants::handler(ctx)
+    }
+
+    pub fn register_automation_thread(
+        ctx: Context<RegisterAutomationThread>,
+        thread: Pubkey,
+    ) -> Result<()> {
+        instructions::register_automation_thread::handler(ctx, thread)
+    }
+
+    pub fn get_quote(ctx: Context<GetQuote>) -> Result<PayoutQuote> {
+        instructions::get_quote::handler(ctx)
+    }
+
+    pub fn get_game_summary(ctx: Context<GetGameSummary>) -> Result<LiveGameSummary> {
+        instructions::get_game_summary::handler(ctx)
+    }
+
+    pub fn set_archive_tree(
+        ctx: Context<SetArchiveTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        instructions::set_archive_tree::handler(ctx, max_depth, max_buffer_size)
+    }
+
+    pub fn archive_game(ctx: Context<ArchiveGame>) -> Result<()> {
+        instructions::archive_game::handler(ctx)
+    }
+
+    pub fn archive_resolved_game(ctx: Context<ArchiveResolvedGame>) -> Result<()> {
+        instructions::archive_resolved_game::handler(ctx)
+    }
+
+    pub fn create_lookup_table(ctx: Context<ManageLookupTable>, recent_slot: u64) -> Result<()> {
+        instructions::create_lookup_table::handler(ctx, recent_slot)
+    }
+
+    pub fn extend_lookup_table(ctx: Context<ManageLookupTable>) -> Result<()> {
+        instructions::extend_lookup_table::handler(ctx)
+    }
+
+    pub fn authorize_delegate(
+        ctx: Context<AuthorizeDelegate>,
+        session_key: Pubkey,
+        expires_at: i64,
+        max_stake: u64,
+    ) -> Result<()> {
+        instructions::authorize_delegate::handler(ctx, session_key, expires_at, max_stake)
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        instructions::revoke_delegate::handler(ctx)
+    }
+
+    pub fn schedule_market(
+        ctx: Context<ScheduleMarket>,
+        market_id: u64,
+        cutoff_slot: u64,
+        draw_slot: u64,
+    ) -> Result<()> {
+        instructions::schedule_market::handler(ctx, market_id, cutoff_slot, draw_slot)
+    }
+
+    pub fn back_market(ctx: Context<BackMarket>, side: CoinSide, amount: u64) -> Result<()> {
+        instructions::back_market::handler(ctx, side, amount)
+    }
+
+    pub fn resolve_market(ctx: Context<ResolveMarket>) -> Result<()> {
+        instructions::resolve_market::handler(ctx)
+    }
+
+    pub fn void_market(ctx: Context<VoidMarket>) -> Result<()> {
+        instructions::void_market::handler(ctx)
+    }
+
+    pub fn claim_market(ctx: Context<ClaimMarket>) -> Result<()> {
+        instructions::claim_market::handler(ctx)
+    }
+
+    pub fn schedule_lottery(
+        ctx: Context<ScheduleLottery>,
+        lottery_id: u64,
+        secret_commitment: [u8; 32],
+        ticket_price: u64,
+        max_entries: u32,
+        cutoff_slot: u64,
+        draw_slot: u64,
+    ) -> Result<()> {
+        instructions::schedule_lottery::handler(
+            ctx,
+            lottery_id,
+            secret_commitment,
+            ticket_price,
+            max_entries,
+            cutoff_slot,
+            draw_slot,
+        )
+    }
+
+    pub fn buy_ticket(ctx: Context<BuyTicket>) -> Result<()> {
+        instructions::buy_ticket::handler(ctx)
+    }
+
+    pub fn draw_lottery(ctx: Context<DrawLottery>, secret: u64) -> Result<()> {
+        instructions::draw_lottery::handler(ctx, secret)
+    }
+
+    pub fn void_lottery(ctx: Context<VoidLottery>) -> Result<()> {
+        instructions::void_lottery::handler(ctx)
+    }
+
+    pub fn claim_lottery(ctx: Context<ClaimLottery>) -> Result<()> {
+        instructions::claim_lottery::handler(ctx)
+    }
+
+    pub fn create_room(
+        ctx: Context<CreateRoom>,
+        room_id: u64,
+        stake: u64,
+        champion_commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::create_room::handler(ctx, room_id, stake, champion_commitment)
+    }
+
+    pub fn challenge_room(
+        ctx: Context<ChallengeRoom>,
+        side: CoinSide,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::challenge_room::handler(ctx, side, commitment)
+    }
+
+    pub fn settle_bout(
+        ctx: Context<SettleBout>,
+        secret: u64,
+        next_commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::settle_bout::handler(ctx, secret, next_commitment)
+    }
+
+    pub fn forfeit_bout(ctx: Context<ForfeitBout>) -> Result<()> {
+        instructions::forfeit_bout::handler(ctx)
+    }
+
+    pub fn retire_champion(ctx: Context<RetireChampion>) -> Result<()> {
+        instructions::retire_champion::handler(ctx)
+    }
+
+    pub fn enter_match_queue(ctx: Context<EnterMatchQueue>, stake: u64) -> Result<()> {
+        instructions::enter_match_queue::handler(ctx, stake)
+    }
+
+    pub fn leave_match_queue(ctx: Context<LeaveMatchQueue>) -> Result<()> {
+        instructions::leave_match_queue::handler(ctx)
+    }
+
+    pub fn start_bracket(
+        ctx: Context<StartBracket>,
+        bracket_id: u64,
+        secret_commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::start_bracket::handler(ctx, bracket_id, secret_commitment)
+    }
+
+    pub fn draw_bracket_round(
+        ctx: Context<DrawBracketRound>,
+        secret: u64,
+        next_commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::draw_bracket_round::handler(ctx, secret, next_commitment)
+    }
+
+    pub fn void_bracket(ctx: Context<VoidBracket>) -> Result<()> {
+        instructions::void_bracket::handler(ctx)
+    }
+
+    pub fn claim_bracket(ctx: Context<ClaimBracket>) -> Result<()> {
+        instructions::claim_bracket::handler(ctx)
+    }
+}
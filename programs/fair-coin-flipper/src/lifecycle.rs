@@ -0,0 +1,225 @@
+use crate::*;
+
+// Record a player's commitment, moving the game on once both players have committed
+pub(crate) fn store_commitment(
+    game: &mut Game,
+    global_state: &GlobalState,
+    player: Pubkey,
+    commitment: [u8; 32],
+    now: i64,
+) -> Result<()> {
+    // Validate game status
+    game.transition(GameEvent::Commit)?;
+
+    // Once a game can be cancelled it only moves through the cancel flow
+    require!(!is_cancellable(game, now), GameError::GameExpired);
+
+    // Security: Prevent zero/empty commitments
+    require!(commitment != [0; 32], GameError::InvalidCommitment);
+
+    // Determine if this is Player A or B
+    let is_player_a = player == game.player_a;
+    let is_player_b = player == game.player_b;
+
+    require!(is_player_a || is_player_b, GameError::NotAPlayer);
+
+    // Store commitment
+    if is_player_a {
+        require!(game.commitment_a == [0; 32], GameError::AlreadyCommitted);
+        game.commitment_a = commitment;
+    } else {
+        require!(game.commitment_b == [0; 32], GameError::AlreadyCommitted);
+        game.commitment_b = commitment;
+    }
+
+    // Check if both players have committed
+    if game.commitment_a != [0; 32] && game.commitment_b != [0; 32] {
+        game.set_flag(FLAG_COMMITMENTS_COMPLETE, true);
+        game.transition(GameEvent::CommitmentsComplete)?;
+        set_phase_deadline(game, global_state, now)?;
+    }
+    game.next_seq();
+
+    emit!(CommitmentMade {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        player,
+        commitment,
+        seq: game.seq,
+    });
+
+    Ok(())
+}
+
+// Fill in a new game's terms. Commitment, revelation and result data start zeroed,
+// which reads as empty, and so do the sponsor, joiner and pending owner keys
+pub(crate) fn init_game(
+    game: &mut Game,
+    global_state: &GlobalState,
+    game_id: u64,
+    creator: Pubkey,
+    bet_amount: u64,
+    tags: u8,
+    now: i64,
+) -> Result<()> {
+    game.game_id = game_id;
+    game.creator = creator;
+    game.player_a = creator;
+    game.bet_amount = bet_amount;
+    game.tags = tags;
+
+    // Fees are locked in at creation so later config changes never apply retroactively
+    game.house_fee_bps = global_state.house_fee_bps;
+    game.cancellation_fee_bps = global_state.cancellation_fee_bps;
+
+    game.set_status(GameStatus::WaitingForPlayer);
+    game.set_flag(FLAG_VAULT_ESCROW, global_state.vault_escrow);
+    game.created_at = now;
+    game.next_seq();
+    set_phase_deadline(game, global_state, now)
+}
+
+// Create a program-owned PDA the way Anchor's `init` does, including when someone
+// has already sent lamports to the address
+pub(crate) fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(space);
+    let current = target.lamports();
+    if current == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+                &[seeds],
+            ),
+            required,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    if current < required {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            required - current,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: target.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: target.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+// Every phase change (create, join, both committed, first reveal) restarts the
+// cancel timeout from that moment
+pub(crate) fn set_phase_deadline(
+    game: &mut Game,
+    global_state: &GlobalState,
+    now: i64,
+) -> Result<()> {
+    game.phase_deadline = now
+        .checked_add(global_state.cancel_timeout_seconds)
+        .ok_or(GameError::MathOverflow)?;
+    Ok(())
+}
+
+// Every game goes on the lobby feed, since an allowlist only holds others off
+// until join_locked_until. Lobby clients follow program logs over a websocket, so
+// these are log events rather than CPI events.
+pub(crate) fn emit_listed(game: &Game, now: i64) {
+    emit!(GameListed {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        creator: game.creator,
+        bet_amount: game.bet_amount,
+        tags: game.tags,
+        listed_at: now,
+        seq: game.seq,
+        join_locked_until: game.join_locked_until,
+    });
+}
+
+// Called once by whatever moves a game out of WaitingForPlayer
+pub(crate) fn emit_delisted(game: &Game, reason: DelistReason, now: i64) {
+    emit!(GameDelisted {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        creator: game.creator,
+        reason,
+        delisted_at: now,
+        seq: game.seq,
+    });
+}
+
+// Games past their phase deadline stop accepting commitments and reveals
+pub(crate) fn is_cancellable(game: &Game, now: i64) -> bool {
+    now > game.phase_deadline
+}
+
+// Cranks may be run by either player, the configured operator, or the game's
+// registered automation thread
+pub(crate) fn is_player_or_operator(
+    signer: Pubkey,
+    game: &Game,
+    global_state: &GlobalState,
+) -> bool {
+    signer == game.player_a
+        || (signer == game.player_b && game.player_b != Pubkey::default())
+        || (signer == global_state.operator && global_state.operator != Pubkey::default())
+        || (signer == game.automation_thread && game.automation_thread != Pubkey::default())
+}
+
+// The player an instruction acts for: the delegating player when a session key
+// signs, otherwise the signer itself
+pub(crate) fn acting_player(signer: &Signer, delegate: &Option<Account<Delegate>>) -> Pubkey {
+    delegate
+        .as_ref()
+        .map_or(signer.key(), |delegate| delegate.player)
+}
+
+// A session key may only act before its grant expires and in games within its stake cap
+pub(crate) fn verify_delegate(
+    delegate: Option<&Account<Delegate>>,
+    game: &Game,
+    now: i64,
+) -> Result<()> {
+    let Some(delegate) = delegate else {
+        return Ok(());
+    };
+    require!(now < delegate.expires_at, GameError::DelegateExpired);
+    require!(
+        game.bet_amount <= delegate.max_stake,
+        GameError::DelegateStakeExceeded
+    );
+    Ok(())
+}
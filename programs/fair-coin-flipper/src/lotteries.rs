@@ -0,0 +1,35 @@
+use crate::*;
+
+pub(crate) fn void_open_lottery(lottery: &mut Lottery, voided_by: Pubkey) {
+    lottery.status = LotteryStatus::Voided;
+    emit!(LotteryVoided {
+        event_version: EVENT_VERSION,
+        lottery_id: lottery.lottery_id,
+        voided_by,
+        entries: lottery.entries,
+    });
+}
+
+// Once every ticket has claimed, return the lottery escrow's reserve to the
+// operator who funded it
+pub(crate) fn close_lottery_escrow_if_done<'info>(
+    lottery: &Account<'info, Lottery>,
+    escrow: &AccountInfo<'info>,
+    operator: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if lottery.status == LotteryStatus::Open || lottery.claimed_tickets < lottery.entries {
+        return Ok(());
+    }
+    let lottery_key = lottery.key();
+    close_escrow(
+        escrow,
+        operator,
+        system_program,
+        &[&[
+            b"lottery_escrow",
+            lottery_key.as_ref(),
+            &[lottery.escrow_bump],
+        ]],
+    )
+}
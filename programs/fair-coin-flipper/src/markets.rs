@@ -0,0 +1,41 @@
+use crate::*;
+
+// A winning position's share of a resolved market's payout pool, rounded down.
+// The rounding dust stays in the escrow and goes back to the operator with the
+// reserve once every position has claimed.
+pub fn market_payout(stake: u64, winning_total: u64, payout_pool: u64) -> Result<u64> {
+    require!(winning_total > 0, GameError::MathOverflow);
+    let share = stake as u128 * payout_pool as u128 / winning_total as u128;
+    u64::try_from(share).map_err(|_| error!(GameError::MathOverflow))
+}
+
+pub(crate) fn void_open_market(market: &mut Market, voided_by: Pubkey) {
+    market.status = MarketStatus::Voided;
+    emit!(MarketVoided {
+        event_version: EVENT_VERSION,
+        market_id: market.market_id,
+        voided_by,
+        heads_total: market.heads_total,
+        tails_total: market.tails_total,
+    });
+}
+
+// Once every position has claimed, return the market escrow's reserve and any
+// rounding dust to the operator who funded the reserve
+pub(crate) fn close_market_escrow_if_done<'info>(
+    market: &Account<'info, Market>,
+    escrow: &AccountInfo<'info>,
+    operator: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if market.status == MarketStatus::Open || market.claimed_positions < market.positions {
+        return Ok(());
+    }
+    let market_key = market.key();
+    close_escrow(
+        escrow,
+        operator,
+        system_program,
+        &[&[b"market_escrow", market_key.as_ref(), &[market.escrow_bump]]],
+    )
+}
@@ -0,0 +1,79 @@
+use crate::*;
+
+// Attach a referrer to a player the first time they play with one
+pub(crate) fn set_referrer_if_needed(
+    stats: &mut PlayerStats,
+    referrer: &mut Option<Account<Referrer>>,
+) {
+    if let Some(referrer) = referrer {
+        if stats.referrer == Pubkey::default() && referrer.referrer != stats.player {
+            stats.referrer = referrer.referrer;
+            referrer.referred_players = referrer.referred_players.saturating_add(1);
+        }
+    }
+}
+
+// Pay one player's referral share from escrow into their Referrer account.
+// Returns the lamports paid so the house fee transfer can be reduced.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_referral<'info>(
+    stats: &PlayerStats,
+    referrer: &mut Option<Account<'info, Referrer>>,
+    leaderboard: &mut ReferralLeaderboard,
+    escrow: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer_seeds: &[&[&[u8]]],
+    bet_amount: u64,
+    fee_share: u64,
+) -> Result<u64> {
+    if stats.referrer == Pubkey::default() {
+        return Ok(0);
+    }
+    let referrer = referrer.as_mut().ok_or(GameError::MissingReferrerAccount)?;
+
+    referrer.referred_volume = referrer.referred_volume.saturating_add(bet_amount);
+    referrer.fees_earned = referrer.fees_earned.saturating_add(fee_share);
+    update_referral_leaderboard(leaderboard, referrer.referrer, referrer.fees_earned);
+
+    if fee_share > 0 {
+        transfer_from_escrow(
+            escrow,
+            referrer.to_account_info(),
+            system_program,
+            signer_seeds,
+            fee_share,
+        )?;
+    }
+
+    Ok(fee_share)
+}
+
+// Keep the leaderboard sorted by lifetime fees, replacing the lowest entry when beaten
+pub(crate) fn update_referral_leaderboard(
+    leaderboard: &mut ReferralLeaderboard,
+    referrer: Pubkey,
+    fees_earned: u64,
+) {
+    let existing = leaderboard
+        .entries
+        .iter()
+        .position(|entry| entry.referrer == referrer);
+    let slot = existing.or_else(|| {
+        let (lowest, entry) = leaderboard
+            .entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| entry.fees_earned)?;
+        (fees_earned > entry.fees_earned).then_some(lowest)
+    });
+
+    if let Some(slot) = slot {
+        leaderboard.entries[slot] = ReferralEntry {
+            referrer,
+            fees_earned,
+        };
+        leaderboard
+            .entries
+            .sort_by_key(|entry| std::cmp::Reverse(entry.fees_earned));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::*;
+
+// Split of a settled bout into (house_fee, winnings). The fee comes off both
+// stakes as in a game; the winner's own stake stays in the room, so only the
+// loser's stake less the fee is paid out.
+pub fn bout_amounts(stake: u64, house_fee_bps: u64) -> Result<(u64, u64)> {
+    let (_, house_fee, winner_payout) = settlement_amounts(stake, house_fee_bps)?;
+    let winnings = winner_payout
+        .checked_sub(stake)
+        .ok_or(GameError::MathOverflow)?;
+    Ok((house_fee, winnings))
+}
+
+// Give the challenger now at the head of the queue their bout, drawn from a slot
+// whose hash isn't known yet
+pub(crate) fn schedule_next_bout(room: &mut Room, slot: u64) {
+    room.bout_draw_slot = if room.queue_len > 0 {
+        slot.saturating_add(ROOM_BOUT_DELAY_SLOTS)
+    } else {
+        0
+    };
+}
+
+// Pay a bout's winnings and house fee out of the room escrow
+pub(crate) fn pay_bout<'info>(
+    room: &Account<'info, Room>,
+    escrow: &AccountInfo<'info>,
+    winner: AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<(u64, u64)> {
+    let (house_fee, winnings) = bout_amounts(room.stake, room.house_fee_bps)?;
+    let room_key = room.key();
+    let seeds: &[&[u8]] = &[b"room_escrow", room_key.as_ref(), &[room.escrow_bump]];
+    transfer_from_escrow(escrow, winner, system_program, &[seeds], winnings)?;
+    transfer_from_escrow(
+        escrow,
+        treasury.clone(),
+        system_program,
+        &[seeds],
+        house_fee,
+    )?;
+    Ok((house_fee, winnings))
+}
+
+// A bout is a flip between two players, so it counts on their stats like a game:
+// both staked `stake` and the winner got it back along with the winnings
+pub(crate) fn record_bout(
+    champion_stats: &mut PlayerStats,
+    challenger_stats: &mut PlayerStats,
+    challenger_won: bool,
+    stake: u64,
+    winnings: u64,
+    season_id: u32,
+    resolved_at: i64,
+) -> Result<()> {
+    let (winner_stats, loser_stats) = if challenger_won {
+        (challenger_stats, champion_stats)
+    } else {
+        (champion_stats, challenger_stats)
+    };
+    let winner_payout = stake.checked_add(winnings).ok_or(GameError::MathOverflow)?;
+    record_game_result(
+        winner_stats,
+        loser_stats,
+        stake,
+        winner_payout,
+        season_id,
+        resolved_at,
+    );
+    Ok(())
+}
+
+// Take the head challenger off the queue and record the bout. A challenger who
+// wins takes over the room with a fresh streak and defends with the secret they
+// committed to when queuing.
+pub(crate) fn finish_bout(
+    room: &mut Room,
+    challenger_won: bool,
+    winnings: u64,
+    slot: u64,
+) -> Result<RoomChallenger> {
+    let challenger = room.pop_challenger().ok_or(GameError::NoBoutPending)?;
+    if challenger_won {
+        room.champion = challenger.player;
+        room.champion_commitment = challenger.commitment;
+        room.streak = 1;
+        room.winnings = winnings;
+    } else {
+        room.streak = room.streak.checked_add(1).ok_or(GameError::MathOverflow)?;
+        room.winnings = room
+            .winnings
+            .checked_add(winnings)
+            .ok_or(GameError::MathOverflow)?;
+    }
+    room.bouts = room.bouts.checked_add(1).ok_or(GameError::MathOverflow)?;
+    schedule_next_bout(room, slot);
+    Ok(challenger)
+}
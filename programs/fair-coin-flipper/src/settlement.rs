@@ -0,0 +1,847 @@
+use crate::*;
+use std::cell::{Ref, RefMut};
+
+// Lamports a game's escrow should hold in each status: the stakes deposited so far
+// plus the rent reserve, or nothing once the game has settled and closed it
+pub fn expected_escrow_balance(status: &GameStatus, bet_amount: u64, reserve: u64) -> Result<u64> {
+    let stakes = match status {
+        GameStatus::WaitingForPlayer => bet_amount,
+        GameStatus::PlayersReady | GameStatus::CommitmentsReady | GameStatus::RevealingPhase => {
+            bet_amount.checked_mul(2).ok_or(GameError::MathOverflow)?
+        }
+        GameStatus::Resolved | GameStatus::Cancelled => return Ok(0),
+    };
+    stakes
+        .checked_add(reserve)
+        .ok_or_else(|| error!(GameError::MathOverflow))
+}
+
+// Lamports the account holding a game's stakes should and does hold, and whether
+// they reconcile. A game's own escrow must hold exactly expected_escrow_balance.
+// The shared vault must cover every vault game's ledger entry plus its own rent
+// reserve; it may hold more, since anyone can send it lamports.
+pub(crate) fn check_escrow(game: &Game, escrow: &AccountInfo) -> Result<(u64, u64, bool)> {
+    let actual = escrow.lamports();
+    if is_vault(escrow) {
+        let expected = load_vault(escrow)?
+            .liabilities
+            .checked_add(Rent::get()?.minimum_balance(escrow.data_len()))
+            .ok_or(GameError::MathOverflow)?;
+        return Ok((expected, actual, actual >= expected));
+    }
+    let expected = expected_escrow_balance(
+        &game.status(),
+        game.bet_amount,
+        Rent::get()?.minimum_balance(0),
+    )?;
+    Ok((expected, actual, actual == expected))
+}
+
+// Check the escrow with check_escrow before any payout. On a mismatch the game is
+// halted (only emergency_recover can settle it) and Ok(false) is returned so the
+// transaction still lands with the discrepancy event.
+pub(crate) fn audit_escrow(game: &mut Game, escrow: &AccountInfo, now: i64) -> Result<bool> {
+    require!(
+        !game.flag(FLAG_SETTLEMENT_HALTED),
+        GameError::SettlementHalted
+    );
+
+    let (expected, actual, reconciled) = check_escrow(game, escrow)?;
+    if reconciled {
+        return Ok(true);
+    }
+
+    debug_log!(
+        "game {} escrow holds {} lamports, expected {}",
+        game.game_id,
+        actual,
+        expected
+    );
+    game.set_flag(FLAG_SETTLEMENT_HALTED, true);
+    emit!(EscrowDiscrepancy {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        status: game.status(),
+        expected,
+        actual,
+        detected_at: now,
+        seq: game.seq,
+    });
+    Ok(false)
+}
+
+// Resolved and Cancelled games have paid out; no instruction may move their funds again
+pub fn is_settled(status: &GameStatus) -> bool {
+    matches!(status, GameStatus::Resolved | GameStatus::Cancelled)
+}
+
+// Basis-point share of an amount, computed in u128 so large stakes can't overflow
+pub fn bps_of(amount: u64, bps: u64) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(GameError::MathOverflow)?
+        / 10000;
+    u64::try_from(share).map_err(|_| error!(GameError::MathOverflow))
+}
+
+// Split a resolved game's pot into (total_pot, house_fee, winner_payout)
+pub fn settlement_amounts(bet_amount: u64, house_fee_bps: u64) -> Result<(u64, u64, u64)> {
+    let total_pot = bet_amount.checked_mul(2).ok_or(GameError::MathOverflow)?;
+    let house_fee = bps_of(total_pot, house_fee_bps)?;
+    let winner_payout = total_pot
+        .checked_sub(house_fee)
+        .ok_or(GameError::MathOverflow)?;
+    Ok((total_pot, house_fee, winner_payout))
+}
+
+// Split one cancelled stake into (cancellation_fee, refund_amount)
+pub fn cancellation_amounts(bet_amount: u64, cancellation_fee_bps: u64) -> Result<(u64, u64)> {
+    let cancellation_fee = bps_of(bet_amount, cancellation_fee_bps)?;
+    let refund_amount = bet_amount
+        .checked_sub(cancellation_fee)
+        .ok_or(GameError::MathOverflow)?;
+    Ok((cancellation_fee, refund_amount))
+}
+
+// Portion of the winner's payout owed back to a sponsor who asked to be repaid
+pub(crate) fn sponsor_repayment(game: &Game, winner: Pubkey, winner_payout: u64) -> u64 {
+    let repay = if winner == game.player_a {
+        FLAG_REPAY_SPONSOR_A
+    } else {
+        FLAG_REPAY_SPONSOR_B
+    };
+    if game.flag(repay) {
+        game.bet_amount.min(winner_payout)
+    } else {
+        0
+    }
+}
+
+// Account that should receive a refunded stake: the sponsor if there was one
+pub(crate) fn stake_refund_account<'info>(
+    player: &AccountInfo<'info>,
+    sponsor: &Option<AccountInfo<'info>>,
+    sponsor_key: Pubkey,
+) -> Result<AccountInfo<'info>> {
+    if sponsor_key == Pubkey::default() {
+        return Ok(player.to_account_info());
+    }
+    sponsor
+        .as_ref()
+        .map(|sponsor| sponsor.to_account_info())
+        .ok_or_else(|| error!(GameError::MissingSponsorAccount))
+}
+
+// Seed the treasury with its rent-exempt reserve so fees below that amount can be received
+pub(crate) fn fund_treasury_reserve<'info>(
+    payer: &Signer<'info>,
+    treasury: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    fund_rent_reserve(payer, treasury, system_program)
+}
+
+// Top a system-owned PDA up to the rent-exempt minimum for an empty account, so
+// transfers into it of less than that amount don't fail
+pub(crate) fn fund_rent_reserve<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let reserve = Rent::get()?.minimum_balance(0);
+    let shortfall = reserve.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.to_account_info(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    Ok(())
+}
+
+// Account that holds a game's stakes: the shared vault for games created in vault
+// mode, otherwise the game's own escrow PDA
+pub(crate) fn escrow_address(game: &Game) -> Result<Pubkey> {
+    let game_id = game.game_id.to_le_bytes();
+    let bump = [game.escrow_bump];
+    let seeds: &[&[u8]] = if game.flag(FLAG_VAULT_ESCROW) {
+        &[b"escrow_vault", &bump]
+    } else {
+        &[b"escrow", game.creator.as_ref(), &game_id, &bump]
+    };
+    Pubkey::create_program_address(seeds, &crate::ID)
+        .map_err(|_| error!(GameError::InvalidEscrowAccount))
+}
+
+// Escrow address and bump for a game being created, following the current mode
+pub(crate) fn new_game_escrow(
+    global_state: &GlobalState,
+    creator: Pubkey,
+    game_id: u64,
+) -> Result<(Pubkey, u8)> {
+    if !global_state.vault_escrow {
+        return Ok(Pubkey::find_program_address(
+            &[b"escrow", creator.as_ref(), &game_id.to_le_bytes()],
+            &crate::ID,
+        ));
+    }
+    let bump = global_state.escrow_vault_bump;
+    let vault = Pubkey::create_program_address(&[b"escrow_vault", &[bump]], &crate::ID)
+        .map_err(|_| error!(GameError::InvalidEscrowAccount))?;
+    Ok((vault, bump))
+}
+
+// Per-game escrows are system-owned PDAs; only the shared vault is owned by the program
+pub(crate) fn is_vault(escrow: &AccountInfo) -> bool {
+    escrow.owner == &crate::ID
+}
+
+pub(crate) fn load_vault(vault: &AccountInfo) -> Result<EscrowVault> {
+    let data = vault.try_borrow_data()?;
+    EscrowVault::try_deserialize(&mut &data[..])
+}
+
+pub(crate) fn store_vault(vault: &AccountInfo, ledger: &EscrowVault) -> Result<()> {
+    let mut data = vault.try_borrow_mut_data()?;
+    ledger.try_serialize(&mut &mut data[..])
+}
+
+// Take an amount off the vault ledger once it has been paid out or written off
+pub(crate) fn release_vault_liability(vault: &AccountInfo, amount: u64) -> Result<()> {
+    let mut ledger = load_vault(vault)?;
+    ledger.liabilities = ledger
+        .liabilities
+        .checked_sub(amount)
+        .ok_or(GameError::InsufficientEscrowBalance)?;
+    store_vault(vault, &ledger)
+}
+
+// Add an amount to the vault ledger once it has been deposited
+pub(crate) fn add_vault_liability(vault: &AccountInfo, amount: u64) -> Result<()> {
+    let mut ledger = load_vault(vault)?;
+    ledger.liabilities = ledger
+        .liabilities
+        .checked_add(amount)
+        .ok_or(GameError::MathOverflow)?;
+    store_vault(vault, &ledger)
+}
+
+// Move a stake into the account holding a game's stakes, adding it to the vault
+// ledger when that account is the shared vault
+pub(crate) fn deposit_stake<'info>(
+    from: AccountInfo<'info>,
+    escrow: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from,
+                to: escrow.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    if is_vault(escrow) {
+        add_vault_liability(escrow, amount)?;
+    }
+    Ok(())
+}
+
+// Move lamports out of a game escrow while keeping its rent-exempt reserve.
+// Every settlement, cancellation, and recovery payout goes through here. The
+// shared vault is program-owned, so it is debited directly and its ledger
+// reduced; per-game escrows pay out through the system program.
+pub(crate) fn transfer_from_escrow<'info>(
+    escrow: &AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let remaining = escrow
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(GameError::InsufficientEscrowBalance)?;
+    require!(
+        remaining >= Rent::get()?.minimum_balance(escrow.data_len()),
+        GameError::InsufficientEscrowBalance
+    );
+
+    if is_vault(escrow) {
+        release_vault_liability(escrow, amount)?;
+        **escrow.try_borrow_mut_lamports()? -= amount;
+        **to.try_borrow_mut_lamports()? += amount;
+        return Ok(());
+    }
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: escrow.to_account_info(),
+                to,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+// Drain whatever is left in a game escrow (its rent reserve plus any dust) back
+// to the creator who funded the reserve. A zero-lamport account is garbage-collected.
+// The shared vault stays open for other games and has no reserve to return.
+pub(crate) fn close_escrow<'info>(
+    escrow: &AccountInfo<'info>,
+    creator: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let remaining = escrow.lamports();
+    if remaining == 0 || is_vault(escrow) {
+        return Ok(());
+    }
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: escrow.to_account_info(),
+                to: creator.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        remaining,
+    )
+}
+
+// Pay a golden flip's bonus from the promo vault. The bonus is a share of the pot,
+// capped at what the vault holds above its rent reserve, so a drained vault pays
+// less rather than failing the settlement. Returns the amount paid.
+pub(crate) fn pay_golden_flip_bonus<'info>(
+    global_state: &GlobalState,
+    promo_vault: Option<&AccountInfo<'info>>,
+    promo_vault_bump: u8,
+    winner: AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    randomness: &[u8; 32],
+    total_pot: u64,
+) -> Result<u64> {
+    if global_state.golden_flip_odds == 0 {
+        return Ok(0);
+    }
+    // Required whether or not this flip is golden, so leaving it out can't
+    // depend on the outcome
+    let promo_vault = promo_vault.ok_or(GameError::MissingPromoVault)?;
+    if !is_golden_flip(randomness, global_state.golden_flip_odds) {
+        return Ok(0);
+    }
+
+    let available = promo_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    let bonus = bps_of(total_pot, global_state.golden_flip_bonus_bps)?.min(available);
+    transfer_from_escrow(
+        promo_vault,
+        winner,
+        system_program,
+        &[&[b"promo_vault", &[promo_vault_bump]]],
+        bonus,
+    )?;
+    Ok(bonus)
+}
+
+// Lamports as SOL with two decimals, e.g. 1_940_000_000 -> "1.94"
+pub(crate) fn format_sol(lamports: u64) -> String {
+    format!(
+        "{}.{:02}",
+        lamports / 1_000_000_000,
+        lamports % 1_000_000_000 / 10_000_000
+    )
+}
+
+// Log a receipt such as "game 42: A wins 1.94 SOL, fee 0.06" through the SPL Memo
+// program so wallets and explorers show it. Skipped when no memo program is passed.
+pub(crate) fn write_settlement_receipt(
+    memo_program: Option<&AccountInfo>,
+    game_id: u64,
+    player_a_won: bool,
+    winner_payout: u64,
+    house_fee: u64,
+) -> Result<()> {
+    let Some(memo_program) = memo_program else {
+        return Ok(());
+    };
+    let receipt = format!(
+        "game {}: {} wins {} SOL, fee {}",
+        game_id,
+        if player_a_won { "A" } else { "B" },
+        format_sol(winner_payout),
+        format_sol(house_fee),
+    );
+    invoke(
+        &Instruction {
+            program_id: MEMO_PROGRAM_ID,
+            accounts: vec![],
+            data: receipt.into_bytes(),
+        },
+        &[memo_program.to_account_info()],
+    )?;
+    Ok(())
+}
+
+// Fill in the receipt PDA when the resolver passed one. Resolution only happens
+// once and the seeds tie the receipt to the game, so it is written exactly once.
+pub(crate) fn write_receipt_account(
+    receipt: Option<&mut Account<Receipt>>,
+    receipt_bump: u8,
+    game: &Game,
+    resolved: &GameResolved,
+) -> Result<()> {
+    let Some(receipt) = receipt else {
+        return Ok(());
+    };
+    receipt.set_inner(Receipt {
+        game_id: game.game_id,
+        player_a: game.player_a,
+        player_b: game.player_b,
+        winner: resolved.winner,
+        coin_result: resolved.coin_result,
+        winner_payout: resolved.winner_payout,
+        house_fee: resolved.house_fee,
+        resolution_slot: resolved.resolution_slot,
+        bump: receipt_bump,
+    });
+    Ok(())
+}
+
+// Notify the configured hook program of a resolution. The hook receives an
+// Anchor-style `on_game_resolved` instruction whose data is the GameResolved
+// payload, with the hook authority PDA as signer (proving the call came from
+// this program) followed by the game account. Once configured the hook is
+// mandatory, so a resolver can't skip it by leaving the accounts out.
+pub(crate) fn invoke_resolution_hook<'info>(
+    global_state: &GlobalState,
+    hook_program: Option<&AccountInfo<'info>>,
+    hook_authority: Option<&AccountInfo<'info>>,
+    game: &AccountInfo<'info>,
+    resolved: &GameResolved,
+    hook_authority_bump: u8,
+) -> Result<()> {
+    if global_state.hook_program == Pubkey::default() {
+        return Ok(());
+    }
+    let hook_program = hook_program.ok_or(GameError::MissingHookProgram)?;
+    let hook_authority = hook_authority.ok_or(GameError::MissingHookProgram)?;
+
+    let mut data = ON_GAME_RESOLVED_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&resolved.try_to_vec()?);
+
+    invoke_signed(
+        &Instruction {
+            program_id: hook_program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(hook_authority.key(), true),
+                AccountMeta::new_readonly(game.key(), false),
+            ],
+            data,
+        },
+        &[hook_authority.clone(), game.clone(), hook_program.clone()],
+        &[&[b"hook_authority", &[hook_authority_bump]]],
+    )?;
+    Ok(())
+}
+
+// The checks AccountLoader makes, for the resolving instructions that take the game
+// as a raw account
+fn check_game(info: &AccountInfo) -> Result<()> {
+    require_keys_eq!(
+        *info.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 + std::mem::size_of::<Game>() && data[..8] == Game::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(())
+}
+
+pub(crate) fn load_game<'a>(info: &'a AccountInfo) -> Result<Ref<'a, Game>> {
+    check_game(info)?;
+    Ok(Ref::map(info.try_borrow_data()?, |data| {
+        bytemuck::from_bytes(&data[8..8 + std::mem::size_of::<Game>()])
+    }))
+}
+
+pub(crate) fn load_game_mut<'a>(info: &'a AccountInfo) -> Result<RefMut<'a, Game>> {
+    require!(info.is_writable, ErrorCode::AccountNotMutable);
+    check_game(info)?;
+    Ok(RefMut::map(info.try_borrow_mut_data()?, |data| {
+        bytemuck::from_bytes_mut(&mut data[8..8 + std::mem::size_of::<Game>()])
+    }))
+}
+
+// Realloc a resolved game down to its ResolvedSummary and split the rent it no longer
+// needs between the two players, player A taking the odd lamport. Runs after the
+// resolution hook so the hook still sees the full game.
+pub(crate) fn shrink_resolved_game<'info>(
+    game_info: &AccountInfo<'info>,
+    player_a: &AccountInfo<'info>,
+    player_b: &AccountInfo<'info>,
+) -> Result<()> {
+    let summary = ResolvedSummary::from_game(&*load_game(game_info)?)?;
+    let new_len = 8 + ResolvedSummary::INIT_SPACE;
+    {
+        let mut data = game_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data[..new_len];
+        summary.try_serialize(&mut writer)?;
+    }
+    game_info.realloc(new_len, false)?;
+
+    let refund = game_info
+        .lamports()
+        .checked_sub(Rent::get()?.minimum_balance(new_len))
+        .ok_or(GameError::MathOverflow)?;
+    let refund_b = refund / 2;
+    **game_info.try_borrow_mut_lamports()? -= refund;
+    **player_a.try_borrow_mut_lamports()? += refund - refund_b;
+    **player_b.try_borrow_mut_lamports()? += refund_b;
+
+    emit!(GameCompacted {
+        event_version: EVENT_VERSION,
+        game_id: summary.game_id,
+        size: new_len as u64,
+        rent_refunded: refund,
+        seq: summary.seq,
+    });
+    Ok(())
+}
+
+// The accounts reveal_choice and resolve_game_manual both pass to settle, with the
+// bumps of the ones that sign or are created there
+pub(crate) struct SettlementAccounts<'a, 'info> {
+    pub game: &'a AccountInfo<'info>,
+    pub player_a: &'a AccountInfo<'info>,
+    pub player_b: &'a AccountInfo<'info>,
+    pub sponsor_a: Option<&'a AccountInfo<'info>>,
+    pub sponsor_b: Option<&'a AccountInfo<'info>>,
+    pub treasury: &'a AccountInfo<'info>,
+    pub creator: &'a AccountInfo<'info>,
+    pub escrow: &'a AccountInfo<'info>,
+    pub global_state: &'a mut GlobalState,
+    pub player_a_stats: &'a mut PlayerStats,
+    pub player_b_stats: &'a mut PlayerStats,
+    pub player_a_achievements: &'a mut Achievements,
+    pub player_b_achievements: &'a mut Achievements,
+    pub daily_stats: &'a mut DailyStats,
+    pub player_a_history: &'a mut GameHistory,
+    pub player_b_history: &'a mut GameHistory,
+    pub recent_games: &'a mut RecentGames,
+    pub player_a_referrer: &'a mut Option<Account<'info, Referrer>>,
+    pub player_b_referrer: &'a mut Option<Account<'info, Referrer>>,
+    pub referral_leaderboard: &'a mut ReferralLeaderboard,
+    pub memo_program: Option<&'a AccountInfo<'info>>,
+    pub receipt: Option<&'a mut Account<'info, Receipt>>,
+    pub hook_program: Option<&'a AccountInfo<'info>>,
+    pub hook_authority: Option<&'a AccountInfo<'info>>,
+    pub promo_vault: Option<&'a AccountInfo<'info>>,
+    pub system_program: &'a Program<'info, System>,
+    pub daily_stats_bump: u8,
+    pub receipt_bump: u8,
+    pub hook_authority_bump: u8,
+    pub promo_vault_bump: u8,
+}
+
+// Flip the coin for a fully revealed game whose escrow has passed its audit, pay
+// everyone out, record the result and shrink the game to its ResolvedSummary.
+// Returns the GameResolved event for the caller to emit.
+pub(crate) fn settle(
+    mut game: RefMut<Game>,
+    accounts: SettlementAccounts,
+    resolved_by: Pubkey,
+    clock: &Clock,
+) -> Result<GameResolved> {
+    let choice_a = game.choice_a().ok_or(GameError::NotReadyForResolution)?;
+    let secret_a = game.secret_a;
+    let choice_b = game.choice_b().ok_or(GameError::NotReadyForResolution)?;
+    let secret_b = game.secret_b;
+
+    // Generate random coin flip
+    let randomness = flip_randomness(secret_a, secret_b, clock.slot, clock.unix_timestamp);
+    let coin_result = coin_side(&randomness);
+
+    // Determine winner
+    let winner = determine_winner(
+        Revealed {
+            player: game.player_a,
+            choice: choice_a,
+            secret: secret_a,
+        },
+        Revealed {
+            player: game.player_b,
+            choice: choice_b,
+            secret: secret_b,
+        },
+        coin_result,
+        clock.slot,
+    );
+    debug_log!("game {} resolved, winner {}", game.game_id, winner);
+
+    // Calculate payouts
+    let (total_pot, house_fee, winner_payout) =
+        settlement_amounts(game.bet_amount, game.house_fee_bps)?;
+
+    // Update game state
+    game.set_coin_result(coin_result);
+    game.winner = winner;
+    game.house_fee = house_fee;
+    game.transition(GameEvent::Resolve)?;
+    game.resolved_at = clock.unix_timestamp;
+    game.resolved_by = resolved_by;
+
+    // Update player stats and achievements from the actual result
+    let player_a_won = winner == game.player_a;
+    let (winner_stats, loser_stats, winner_achievements, loser_achievements) = if player_a_won {
+        (
+            accounts.player_a_stats,
+            accounts.player_b_stats,
+            accounts.player_a_achievements,
+            accounts.player_b_achievements,
+        )
+    } else {
+        (
+            accounts.player_b_stats,
+            accounts.player_a_stats,
+            accounts.player_b_achievements,
+            accounts.player_a_achievements,
+        )
+    };
+    let rating_delta = record_game_result(
+        winner_stats,
+        loser_stats,
+        game.bet_amount,
+        winner_payout,
+        accounts.global_state.current_season_id,
+        game.resolved_at,
+    );
+    unlock_achievements(
+        winner_achievements,
+        winner_stats,
+        winner_payout,
+        game.game_id,
+        clock.unix_timestamp,
+    );
+    unlock_achievements(
+        loser_achievements,
+        loser_stats,
+        0,
+        game.game_id,
+        clock.unix_timestamp,
+    );
+
+    // Append to both players' recent history
+    push_game_history(
+        accounts.player_a_history,
+        GameSummary {
+            game_id: game.game_id,
+            opponent: game.player_b,
+            bet_amount: game.bet_amount,
+            coin_result,
+            won: player_a_won,
+            resolved_at: clock.unix_timestamp,
+        },
+    );
+    push_game_history(
+        accounts.player_b_history,
+        GameSummary {
+            game_id: game.game_id,
+            opponent: game.player_a,
+            bet_amount: game.bet_amount,
+            coin_result,
+            won: !player_a_won,
+            resolved_at: clock.unix_timestamp,
+        },
+    );
+
+    // Global live feed
+    push_recent_game(
+        accounts.recent_games,
+        RecentGame {
+            game_id: game.game_id,
+            pot: total_pot,
+            winner,
+            coin_result,
+            resolved_at: clock.unix_timestamp,
+        },
+    );
+
+    // Hall of fame records and lifetime volume
+    update_records(
+        accounts.global_state,
+        winner,
+        game.game_id,
+        total_pot,
+        winner_stats.current_streak,
+        clock.unix_timestamp - game.created_at,
+    );
+
+    // Aggregate into today's stats
+    record_daily_stats(
+        accounts.daily_stats,
+        accounts.daily_stats_bump,
+        clock.unix_timestamp,
+        total_pot,
+        house_fee,
+    );
+
+    // Transfer funds using PDA signer
+    let seeds = &[
+        b"escrow",
+        game.creator.as_ref(),
+        &game.game_id.to_le_bytes(),
+        &[game.escrow_bump],
+    ];
+
+    // Repay the winner's sponsor first if they asked for it
+    let (winner_account, winner_sponsor) = if player_a_won {
+        (accounts.player_a, accounts.sponsor_a)
+    } else {
+        (accounts.player_b, accounts.sponsor_b)
+    };
+    let sponsor_repayment = sponsor_repayment(&game, winner, winner_payout);
+    if sponsor_repayment > 0 {
+        let sponsor_account = winner_sponsor.ok_or(GameError::MissingSponsorAccount)?;
+        transfer_from_escrow(
+            accounts.escrow,
+            sponsor_account.to_account_info(),
+            accounts.system_program,
+            &[seeds],
+            sponsor_repayment,
+        )?;
+    }
+
+    // Transfer winner payout
+    transfer_from_escrow(
+        accounts.escrow,
+        winner_account.to_account_info(),
+        accounts.system_program,
+        &[seeds],
+        winner_payout
+            .checked_sub(sponsor_repayment)
+            .ok_or(GameError::MathOverflow)?,
+    )?;
+
+    // Referrers earn a share of the house fee on their players' stakes
+    let referral_fee_per_player = bps_of(house_fee / 2, REFERRAL_FEE_SHARE_BPS)?;
+    let mut referral_fees: u64 = 0;
+    let (player_a_stats, player_b_stats) = if player_a_won {
+        (&*winner_stats, &*loser_stats)
+    } else {
+        (&*loser_stats, &*winner_stats)
+    };
+    for (stats, referrer) in [
+        (player_a_stats, accounts.player_a_referrer),
+        (player_b_stats, accounts.player_b_referrer),
+    ] {
+        let paid = pay_referral(
+            stats,
+            referrer,
+            accounts.referral_leaderboard,
+            accounts.escrow,
+            accounts.system_program,
+            &[seeds],
+            game.bet_amount,
+            referral_fee_per_player,
+        )?;
+        referral_fees = referral_fees
+            .checked_add(paid)
+            .ok_or(GameError::MathOverflow)?;
+    }
+
+    // Transfer house fee
+    transfer_from_escrow(
+        accounts.escrow,
+        accounts.treasury.to_account_info(),
+        accounts.system_program,
+        &[seeds],
+        house_fee
+            .checked_sub(referral_fees)
+            .ok_or(GameError::MathOverflow)?,
+    )?;
+
+    // Nothing is owed from the escrow any more; close it
+    close_escrow(
+        accounts.escrow,
+        accounts.creator,
+        accounts.system_program,
+        &[seeds],
+    )?;
+
+    // Golden flips pay the winner a bonus on top, from the promo vault
+    let golden_flip_bonus = pay_golden_flip_bonus(
+        accounts.global_state,
+        accounts.promo_vault,
+        accounts.promo_vault_bump,
+        winner_account.to_account_info(),
+        accounts.system_program,
+        &randomness,
+        total_pot,
+    )?;
+
+    // Optional human-readable receipt next to the transfers
+    write_settlement_receipt(
+        accounts.memo_program,
+        game.game_id,
+        player_a_won,
+        winner_payout,
+        house_fee,
+    )?;
+
+    let resolved = GameResolved {
+        event_version: EVENT_VERSION,
+        game_id: game.game_id,
+        winner,
+        coin_result,
+        winner_payout,
+        house_fee,
+        sponsor_repayment,
+        referral_fees,
+        resolved_at: clock.unix_timestamp,
+        winner_rating: winner_stats.rating,
+        loser_rating: loser_stats.rating,
+        rating_delta,
+        resolution_slot: clock.slot,
+        secret_a,
+        secret_b,
+        randomness,
+        seq: game.seq,
+        resolved_by,
+        golden_flip_odds: accounts.global_state.golden_flip_odds,
+        golden_flip_bonus,
+    };
+    write_receipt_account(accounts.receipt, accounts.receipt_bump, &game, &resolved)?;
+    // The hook reads the game account, so release it before the CPI
+    drop(game);
+    invoke_resolution_hook(
+        accounts.global_state,
+        accounts.hook_program,
+        accounts.hook_authority,
+        accounts.game,
+        &resolved,
+        accounts.hook_authority_bump,
+    )?;
+    shrink_resolved_game(accounts.game, accounts.player_a, accounts.player_b)?;
+    Ok(resolved)
+}
@@ -0,0 +1,51 @@
+use crate::*;
+
+// Aggregate activity for a single unix day
+#[account]
+#[derive(InitSpace)]
+pub struct DailyStats {
+    pub day: u64,
+    pub games_resolved: u64,
+    pub volume: u64,
+    pub fees: u64,
+    pub bump: u8,
+}
+
+// Global ring buffer of the most recently resolved games; `head` is the next slot to write
+#[account]
+#[derive(InitSpace)]
+pub struct RecentGames {
+    pub entries: [RecentGame; RECENT_GAMES_LENGTH],
+    pub head: u8,
+    pub count: u8,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct RecentGame {
+    pub game_id: u64,
+    pub pot: u64,
+    pub winner: Pubkey,
+    pub coin_result: CoinSide,
+    pub resolved_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MilestoneKind {
+    GamesResolved,
+    VolumeSettled, // Lamports
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DelistReason {
+    Joined,
+    Expired,   // Cancelled by cancel_game after the join timeout
+    Recovered, // Refunded by emergency_recover
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RecordKind {
+    LargestPot,
+    LongestStreak,
+    FastestResolution,
+}
@@ -0,0 +1,48 @@
+use crate::*;
+
+// Ring buffer of admin operations; `head` is the next slot to write
+#[account]
+#[derive(InitSpace)]
+pub struct AdminLog {
+    pub entries: [AdminLogEntry; ADMIN_LOG_LENGTH],
+    pub head: u8,
+    pub count: u8,
+    pub total_actions: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum AdminLogAction {
+    #[default]
+    None,
+    StartNewSeason,
+    ProposeAuthority,
+    AcceptAuthority,
+    ProposeConfigChange,
+    ExecuteConfigChange,
+    CancelConfigChange,
+    EmergencyRecover,
+    SetCancelTimeout,
+    SetOperator,
+    MigrateGlobalState,
+    WithdrawTreasury,
+    SetPauseMode,
+    SetAttestationIssuer,
+    SetCpiGuard,
+    SetMatchmaker,
+    SetArchiveTree,
+    SetHookProgram,
+    SetEscrowMode,
+    SetGameCooldown,
+    SetTermsVersion,
+    SetGoldenFlip,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct AdminLogEntry {
+    pub action: AdminLogAction,
+    pub actor: Pubkey,
+    pub target: Pubkey, // Affected account or key, if any
+    pub value: u64,     // Action-specific value (new fee, season id, ...)
+    pub timestamp: i64,
+}
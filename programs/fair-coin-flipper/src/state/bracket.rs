@@ -0,0 +1,89 @@
+use crate::*;
+
+// Players waiting for a bracket at one stake. Stakes sit in a system-owned escrow PDA
+// [b"match_queue_escrow", match_queue] until start_bracket moves them into a bracket.
+// Seeds: [b"match_queue", stake].
+#[account]
+#[derive(InitSpace)]
+pub struct MatchQueue {
+    pub stake: u64,
+    pub players: [Pubkey; MAX_BRACKET_PLAYERS], // In the order they joined
+    pub len: u8,
+    pub escrow_bump: u8,
+    pub bump: u8,
+}
+
+impl MatchQueue {
+    pub fn position(&self, player: &Pubkey) -> Option<usize> {
+        self.players[..self.len as usize]
+            .iter()
+            .position(|queued| queued == player)
+    }
+
+    pub fn push(&mut self, player: Pubkey) -> Result<()> {
+        let len = self.len as usize;
+        require!(len < MAX_BRACKET_PLAYERS, GameError::MatchQueueFull);
+        self.players[len] = player;
+        self.len += 1;
+        Ok(())
+    }
+
+    // Remove `count` players starting at `index`, keeping the rest in order
+    pub fn remove(&mut self, index: usize, count: usize) {
+        let len = self.len as usize;
+        self.players.copy_within(index + count..len, index);
+        self.players[len - count..len].fill(Pubkey::default());
+        self.len -= count as u8;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BracketStatus {
+    Running,  // Rounds are being drawn
+    Finished, // One player left; the champion claims the pot
+    Voided,   // A round wasn't drawn in time; every player claims their stake back
+}
+
+// Single-elimination bracket snapshotted from a match queue. Round one pairs the
+// players in queue order, and each round's winners are paired the same way until
+// one is left. Rounds are drawn like markets (see draws.rs), with a fresh operator
+// commitment per round. Stakes sit in a system-owned escrow PDA
+// [b"bracket_escrow", bracket]. Seeds: [b"bracket", bracket_id].
+#[account]
+#[derive(InitSpace)]
+pub struct Bracket {
+    pub bracket_id: u64,
+    pub operator: Pubkey,
+    pub stake: u64,
+    pub house_fee_bps: u64,                     // Locked in at the start
+    pub players: [Pubkey; MAX_BRACKET_PLAYERS], // Everyone who entered, in seeding order
+    pub player_count: u8,                       // A power of two
+    pub alive: [Pubkey; MAX_BRACKET_PLAYERS],   // Players still in, paired (0, 1), (2, 3), ...
+    pub alive_count: u8,
+    pub round: u8,                   // Rounds drawn so far
+    pub secret_commitment: [u8; 32], // draw_commitment of the secret for the next round
+    pub round_draw_slot: u64,
+    pub status: BracketStatus,
+    pub champion: Pubkey, // Default until finished
+    pub prize: u64,       // Pot minus the house fee
+    pub claimed: u16,     // Bit per entry in `players` that has claimed
+    pub escrow_bump: u8,
+    pub bump: u8,
+}
+
+impl Bracket {
+    pub fn position(&self, player: &Pubkey) -> Option<usize> {
+        self.players[..self.player_count as usize]
+            .iter()
+            .position(|entered| entered == player)
+    }
+
+    // The champion has claimed the pot, or every player their refund
+    pub fn is_fully_claimed(&self) -> bool {
+        match self.status {
+            BracketStatus::Running => false,
+            BracketStatus::Finished => self.claimed != 0,
+            BracketStatus::Voided => self.claimed as u32 == (1u32 << self.player_count) - 1,
+        }
+    }
+}
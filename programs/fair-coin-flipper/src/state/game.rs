@@ -0,0 +1,366 @@
+use crate::*;
+
+// Zero-copy so settlement reads and writes fields in place instead of
+// deserializing the whole account. The layout is fixed and must stay free of
+// implicit padding: 8-byte fields first, then keys and hashes, then single bytes,
+// with explicit padding after them. Fields added later go after the padding so
+// existing offsets never move. Optional values use sentinels and booleans and
+// small enums are bit-packed; the accessors below decode them. The full layout is
+// in docs/account-layout.md.
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct Game {
+    pub game_id: u64,
+    pub bet_amount: u64,
+    pub house_fee_bps: u64,
+    pub cancellation_fee_bps: u64,
+    pub join_locked_until: i64, // Anti-snipe lock
+    pub secret_a: u64,          // 0 until revealed; valid secrets are always > 1
+    pub secret_b: u64,
+    pub house_fee: u64,
+    pub created_at: i64,
+    pub phase_deadline: i64, // Cancellable once this passes; reset on each phase change
+    pub resolved_at: i64,    // 0 until resolved
+
+    pub creator: Pubkey, // Original creator, used for PDA seeds
+    pub player_a: Pubkey,
+    pub pending_owner: Pubkey,
+    pub player_b: Pubkey,
+
+    // Third-party sponsors funding each stake (default pubkey = self-funded)
+    pub sponsor_a: Pubkey,
+    pub sponsor_b: Pubkey,
+
+    // Challenged/allowlisted opponents while the anti-snipe lock holds
+    pub allowed_joiners: [Pubkey; MAX_ALLOWED_JOINERS],
+
+    // Automation thread (e.g. a Clockwork thread PDA) allowed to crank cancel/resolve
+    pub automation_thread: Pubkey,
+
+    pub winner: Pubkey, // Default until resolved
+
+    // Commitment Phase
+    pub commitment_a: [u8; 32],
+    pub commitment_b: [u8; 32],
+
+    pub tags: u8,
+    pub flags: u8, // FLAG_* bits plus the GameStatus code
+    pub sides: u8, // choice_a, choice_b and coin_result as 2-bit CoinSide codes
+
+    // PDAs
+    pub bump: u8,
+    pub escrow_bump: u8,
+
+    pub padding: [u8; 3],
+
+    // Bumped once by every instruction that changes the game and carried by the
+    // events it emits, so indexers can spot missed or reordered deliveries
+    pub seq: u64,
+
+    // Signer of the instruction that settled the game: the revealing player (or their
+    // session key), or whoever called resolve_game_manual. Default until resolved.
+    pub resolved_by: Pubkey,
+}
+
+impl Game {
+    pub fn status(&self) -> GameStatus {
+        match (self.flags & STATUS_MASK) >> STATUS_SHIFT {
+            0 => GameStatus::WaitingForPlayer,
+            1 => GameStatus::PlayersReady,
+            2 => GameStatus::CommitmentsReady,
+            3 => GameStatus::RevealingPhase,
+            4 => GameStatus::Resolved,
+            _ => GameStatus::Cancelled,
+        }
+    }
+
+    pub fn set_status(&mut self, status: GameStatus) {
+        self.flags = (self.flags & !STATUS_MASK) | ((status as u8) << STATUS_SHIFT);
+    }
+
+    pub fn flag(&self, bit: u8) -> bool {
+        self.flags & bit != 0
+    }
+
+    pub fn set_flag(&mut self, bit: u8, on: bool) {
+        if on {
+            self.flags |= bit;
+        } else {
+            self.flags &= !bit;
+        }
+    }
+
+    // Apply an event through the transition table
+    pub fn transition(&mut self, event: GameEvent) -> Result<()> {
+        let next = advance(&self.status(), event)?;
+        self.set_status(next);
+        Ok(())
+    }
+
+    pub fn choice_a(&self) -> Option<CoinSide> {
+        self.side(CHOICE_A_SHIFT)
+    }
+
+    pub fn choice_b(&self) -> Option<CoinSide> {
+        self.side(CHOICE_B_SHIFT)
+    }
+
+    pub fn coin_result(&self) -> Option<CoinSide> {
+        self.side(COIN_RESULT_SHIFT)
+    }
+
+    pub fn set_choice_a(&mut self, side: CoinSide) {
+        self.set_side(CHOICE_A_SHIFT, side);
+    }
+
+    pub fn set_choice_b(&mut self, side: CoinSide) {
+        self.set_side(CHOICE_B_SHIFT, side);
+    }
+
+    pub fn set_coin_result(&mut self, side: CoinSide) {
+        self.set_side(COIN_RESULT_SHIFT, side);
+    }
+
+    pub fn auto_pick_b(&self) -> bool {
+        self.sides & SIDES_AUTO_PICK_B != 0
+    }
+
+    pub fn set_auto_pick_b(&mut self, on: bool) {
+        if on {
+            self.sides |= SIDES_AUTO_PICK_B;
+        } else {
+            self.sides &= !SIDES_AUTO_PICK_B;
+        }
+    }
+
+    // Player B asked for auto-pick and is the only one who hasn't committed. Once
+    // the deadline passes, auto_pick_side closes the commit phase instead of a refund.
+    pub fn auto_pick_due(&self) -> bool {
+        self.status() == GameStatus::PlayersReady
+            && self.auto_pick_b()
+            && self.commitment_a != [0; 32]
+            && self.commitment_b == [0; 32]
+    }
+
+    // The commit phase was closed without player B and player A has since revealed,
+    // so auto_pick_side can draw B's side
+    pub fn auto_pick_draw_due(&self) -> bool {
+        self.status() == GameStatus::RevealingPhase
+            && self.auto_pick_b()
+            && self.commitment_b == [0; 32]
+            && self.choice_a().is_some()
+    }
+
+    fn side(&self, shift: u8) -> Option<CoinSide> {
+        side_from_code((self.sides >> shift) & 0b11)
+    }
+
+    fn set_side(&mut self, shift: u8, side: CoinSide) {
+        self.sides = (self.sides & !(0b11 << shift)) | (side_code(side) << shift);
+    }
+
+    pub fn winner(&self) -> Option<Pubkey> {
+        (self.winner != Pubkey::default()).then_some(self.winner)
+    }
+
+    pub fn resolved_at(&self) -> Option<i64> {
+        (self.resolved_at != 0).then_some(self.resolved_at)
+    }
+
+    pub fn next_seq(&mut self) -> u64 {
+        self.seq = self.seq.wrapping_add(1);
+        self.seq
+    }
+}
+
+// Stored CoinSide codes; 0 is reserved for "not set"
+pub fn side_code(side: CoinSide) -> u8 {
+    side as u8 + 1
+}
+
+pub fn side_from_code(code: u8) -> Option<CoinSide> {
+    match code {
+        1 => Some(CoinSide::Heads),
+        2 => Some(CoinSide::Tails),
+        _ => None,
+    }
+}
+
+// Enums
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum GameStatus {
+    WaitingForPlayer,
+    PlayersReady,
+    CommitmentsReady,
+    RevealingPhase,
+    Resolved,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum CoinSide {
+    Heads,
+    Tails,
+}
+
+impl From<CoinSide> for coin_flipper_core::CoinSide {
+    fn from(side: CoinSide) -> Self {
+        match side {
+            CoinSide::Heads => coin_flipper_core::CoinSide::Heads,
+            CoinSide::Tails => coin_flipper_core::CoinSide::Tails,
+        }
+    }
+}
+
+impl From<coin_flipper_core::CoinSide> for CoinSide {
+    fn from(side: coin_flipper_core::CoinSide) -> Self {
+        match side {
+            coin_flipper_core::CoinSide::Heads => CoinSide::Heads,
+            coin_flipper_core::CoinSide::Tails => CoinSide::Tails,
+        }
+    }
+}
+
+// Compact game view returned by get_game_summary, for live and resolved games alike.
+// The runtime drops trailing zero bytes from return data, and a live game's view
+// ends in zeroes (unrevealed flags, no result, no winner), so clients zero-pad what
+// they receive to MAX_RETURN_DATA before decoding and ignore the unread tail.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LiveGameSummary {
+    pub game_id: u64,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub bet_amount: u64,
+    pub status: GameStatus,
+    pub phase_deadline: i64,
+    pub committed_a: bool,
+    pub committed_b: bool,
+    pub revealed_a: bool,
+    pub revealed_b: bool,
+    pub coin_result: Option<CoinSide>,
+    pub winner: Option<Pubkey>,
+}
+
+// Settlement summary stored as a compressed leaf by archive_game; the leaf is
+// the sha256 of its borsh encoding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ArchivedGame {
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub bet_amount: u64,
+    pub status: GameStatus,
+    pub coin_result: Option<CoinSide>,
+    pub winner: Option<Pubkey>,
+    pub house_fee: u64,
+    pub created_at: i64,
+    pub resolved_at: Option<i64>,
+}
+
+// What a game account holds once it resolves. Settlement reallocs the game PDA down
+// to this layout and refunds the freed rent to the players; the address and seeds
+// stay the same, so the result remains readable until archive_resolved_game closes it.
+#[account]
+#[derive(InitSpace)]
+pub struct ResolvedSummary {
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub winner: Pubkey,
+    pub bet_amount: u64,
+    pub house_fee: u64,
+    pub coin_result: CoinSide,
+    pub created_at: i64,
+    pub resolved_at: i64,
+    pub seq: u64, // The game's seq at resolution
+    pub resolved_by: Pubkey,
+    pub bump: u8,
+}
+
+impl ResolvedSummary {
+    // Only called on a resolved game, where the coin result is always set
+    pub fn from_game(game: &Game) -> Result<Self> {
+        Ok(Self {
+            game_id: game.game_id,
+            creator: game.creator,
+            player_a: game.player_a,
+            player_b: game.player_b,
+            winner: game.winner,
+            bet_amount: game.bet_amount,
+            house_fee: game.house_fee,
+            coin_result: game.coin_result().ok_or(GameError::InvalidGameStatus)?,
+            created_at: game.created_at,
+            resolved_at: game.resolved_at,
+            seq: game.seq,
+            resolved_by: game.resolved_by,
+            bump: game.bump,
+        })
+    }
+
+    // Resolution needs both reveals, so every commit and reveal flag is set; no
+    // phase is running any more, so there is no deadline
+    pub fn live_summary(&self) -> LiveGameSummary {
+        LiveGameSummary {
+            game_id: self.game_id,
+            player_a: self.player_a,
+            player_b: self.player_b,
+            bet_amount: self.bet_amount,
+            status: GameStatus::Resolved,
+            phase_deadline: 0,
+            committed_a: true,
+            committed_b: true,
+            revealed_a: true,
+            revealed_b: true,
+            coin_result: Some(self.coin_result),
+            winner: Some(self.winner),
+        }
+    }
+
+    pub fn archived(&self) -> ArchivedGame {
+        ArchivedGame {
+            game_id: self.game_id,
+            creator: self.creator,
+            player_a: self.player_a,
+            player_b: self.player_b,
+            bet_amount: self.bet_amount,
+            status: GameStatus::Resolved,
+            coin_result: Some(self.coin_result),
+            winner: Some(self.winner),
+            house_fee: self.house_fee,
+            created_at: self.created_at,
+            resolved_at: Some(self.resolved_at),
+        }
+    }
+}
+
+// Optional record of a resolution, written when the resolver passes it. Unlike the
+// game account it is never closed, so it outlives archiving as a cheap artifact for
+// disputes and tax reporting. Seeds: [b"receipt", creator, game_id].
+#[account]
+#[derive(InitSpace)]
+pub struct Receipt {
+    pub game_id: u64,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub winner: Pubkey,
+    pub coin_result: CoinSide,
+    pub winner_payout: u64,
+    pub house_fee: u64,
+    pub resolution_slot: u64,
+    pub bump: u8,
+}
+
+// Settlement preview returned by get_quote. Decode it like LiveGameSummary: the
+// high bytes of the last amount are zero, and the runtime trims them off.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PayoutQuote {
+    pub total_pot: u64,
+    pub winner_payout: u64, // Includes any sponsor repayment taken out of it
+    pub house_fee: u64,
+    pub referral_fees: u64,    // Part of house_fee paid to referrers
+    pub treasury_fee: u64,     // Part of house_fee kept by the treasury
+    pub rent_refund: u64,      // Escrow rent reserve returned to the creator; 0 in vault mode
+    pub game_rent_refund: u64, // Freed by shrinking the game, split between the players
+}
@@ -0,0 +1,115 @@
+use crate::*;
+
+// Program-wide configuration
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalState {
+    pub authority: Pubkey, // Keypair or multisig PDA; admin instructions only need its signature
+    pub pending_authority: Pubkey,
+    pub operator: Pubkey, // Hot key for cranks; cannot change config or move funds
+
+    // Economics, changeable only through the timelock
+    pub house_wallet: Pubkey, // Destination for treasury withdrawals
+    pub house_fee_bps: u64,
+    pub cancellation_fee_bps: u64,
+    pub min_bet_amount: u64,
+    pub max_bet_amount: u64,
+    pub timelock_delay_seconds: i64,
+    pub retired_pending_change: u8, // Held the queued change until v16; always 0 (None) since
+
+    // Operational timeouts
+    pub cancel_timeout_seconds: i64,
+
+    // Seasons
+    pub current_season_id: u32,
+    pub season_started_at: i64,
+
+    // Hall of fame records
+    pub largest_pot: u64,
+    pub largest_pot_holder: Pubkey,
+    pub longest_streak: u32,
+    pub longest_streak_holder: Pubkey,
+    pub fastest_resolution_seconds: i64,
+    pub fastest_resolution_holder: Pubkey,
+
+    pub bump: u8,
+
+    // Layout version. New fields must be appended below this line so that
+    // migrate_global_state can zero-extend older accounts in place.
+    pub version: u8,
+    pub treasury_bump: u8,                                      // v3
+    pub pause_mode: PauseMode,                                  // v4
+    pub attestation_issuer: Pubkey, // v5; default disables the attestation requirement
+    pub total_volume: u128,         // v6; lifetime lamports settled, wide enough never to overflow
+    pub cpi_guard_enabled: bool,    // v7
+    pub allowed_cpi_callers: [Pubkey; MAX_ALLOWED_CPI_CALLERS], // v7; default entries are unused
+    pub matchmaker: Pubkey,         // v8; when set, joins need its signed match ticket
+    pub archive_tree: Pubkey,       // v9; default disables archive_game
+    pub archived_games: u64,        // v9; leaf index of the next archived game
+    pub hook_program: Pubkey,       // v10; called after every resolution when set
+    pub vault_escrow: bool,         // v11; new games keep their stakes in the shared escrow vault
+    pub escrow_vault_bump: u8,      // v11; set when the vault is created
+    pub game_cooldown_seconds: i64, // v12; wait after a player's last resolved game, 0 = off
+    pub terms_version: u32,         // v13; terms players must accept to create or join, 0 = none
+    pub total_games: u64,           // v14; games resolved since the v14 migration
+    pub golden_flip_odds: u32,      // v15; a resolution is golden 1 in this many, 0 = off
+    pub golden_flip_bonus_bps: u64, // v15; bonus paid from the promo vault, in bps of the pot
+    pub pending_change: PendingConfigChange, // v16; fixed size, so clearing it never moves a field
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum PauseMode {
+    #[default]
+    Active,
+    NoNewGames, // Create/join blocked; in-flight games can still settle or refund
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ConfigChange {
+    HouseFee { bps: u64 },
+    CancellationFee { bps: u64 },
+    BetLimits { min_bet: u64, max_bet: u64 },
+    TimelockDelay { seconds: i64 },
+    HouseWallet { wallet: Pubkey },
+}
+
+// Change queued behind the timelock. Always present and serialized at full size,
+// with the change padded to its largest variant, so queuing or clearing one
+// leaves the rest of GlobalState where it is.
+#[derive(Clone, InitSpace)]
+pub struct PendingConfigChange {
+    pub active: bool,
+    pub change: ConfigChange,
+    pub eta: i64, // Earliest execution time
+}
+
+impl PendingConfigChange {
+    pub const NONE: Self = Self {
+        active: false,
+        change: ConfigChange::HouseFee { bps: 0 },
+        eta: 0,
+    };
+}
+
+impl AnchorSerialize for PendingConfigChange {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.active.serialize(writer)?;
+        let mut change = [0u8; <ConfigChange as Space>::INIT_SPACE];
+        self.change.serialize(&mut &mut change[..])?;
+        writer.write_all(&change)?;
+        self.eta.serialize(writer)
+    }
+}
+
+impl AnchorDeserialize for PendingConfigChange {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let active = bool::deserialize_reader(reader)?;
+        let mut change = [0u8; <ConfigChange as Space>::INIT_SPACE];
+        reader.read_exact(&mut change)?;
+        Ok(Self {
+            active,
+            change: ConfigChange::deserialize(&mut &change[..])?,
+            eta: i64::deserialize_reader(reader)?,
+        })
+    }
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum LotteryStatus {
+    Open,   // Selling tickets until cutoff_slot or max_entries
+    Drawn,  // Winning ticket picked; its holder claims the prize
+    Voided, // Not drawn in time, or no tickets sold; every ticket is refunded
+}
+
+// Operator-scheduled raffle: players buy numbered tickets at a fixed price and one
+// ticket takes the pot minus the house fee. Drawn like a market (see draws.rs), with
+// stakes in a system-owned escrow PDA [b"lottery_escrow", lottery].
+// Seeds: [b"lottery", lottery_id].
+#[account]
+#[derive(InitSpace)]
+pub struct Lottery {
+    pub lottery_id: u64,
+    pub operator: Pubkey,
+    pub secret_commitment: [u8; 32], // draw_commitment of the operator's secret
+    pub ticket_price: u64,
+    pub max_entries: u32,
+    pub entries: u32,         // Tickets sold, numbered from 0
+    pub claimed_tickets: u32, // The escrow closes when the last ticket is claimed
+    pub cutoff_slot: u64,
+    pub draw_slot: u64,
+    pub house_fee_bps: u64, // Locked in at scheduling
+    pub status: LotteryStatus,
+    pub winning_ticket: u32, // Meaningful once drawn
+    pub prize: u64,          // Pot minus the house fee
+    pub randomness: [u8; 32],
+    pub escrow_bump: u8,
+    pub bump: u8,
+}
+
+// One ticket. Seeds: [b"lottery_ticket", lottery, ticket_number].
+#[account]
+#[derive(InitSpace)]
+pub struct LotteryTicket {
+    pub lottery: Pubkey,
+    pub player: Pubkey,
+    pub number: u32,
+    pub bump: u8,
+}
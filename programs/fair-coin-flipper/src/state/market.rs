@@ -0,0 +1,51 @@
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MarketStatus {
+    Open,     // Taking stakes until cutoff_slot
+    Resolved, // Result drawn; winners claim their share
+    Voided,   // Not drawn in time, or nobody backed the result; everyone claims a refund
+}
+
+// Operator-scheduled public flip that any number of players back pari-mutuel.
+// Stakes sit in a system-owned escrow PDA [b"market_escrow", market] and pay out
+// through the same escrow helpers as games. Seeds: [b"market", market_id].
+#[account]
+#[derive(InitSpace)]
+pub struct Market {
+    pub market_id: u64,
+    pub operator: Pubkey,   // Scheduled it and gets the reserve back
+    pub cutoff_slot: u64,   // Backing closes at this slot
+    pub draw_slot: u64,     // The flip uses this slot's hash; after cutoff_slot
+    pub house_fee_bps: u64, // Locked in at scheduling, like a game's fee
+    pub heads_total: u64,
+    pub tails_total: u64,
+    pub positions: u32,         // Positions opened
+    pub claimed_positions: u32, // The escrow closes when the last one claims
+    pub status: MarketStatus,
+    pub result: CoinSide,     // Meaningful once resolved
+    pub payout_pool: u64,     // Pot minus the house fee, shared by the winning side
+    pub randomness: [u8; 32], // Zero until resolved
+    pub escrow_bump: u8,
+    pub bump: u8,
+}
+
+impl Market {
+    pub fn side_total(&self, side: CoinSide) -> u64 {
+        match side {
+            CoinSide::Heads => self.heads_total,
+            CoinSide::Tails => self.tails_total,
+        }
+    }
+}
+
+// One player's stakes in a market. Seeds: [b"market_position", market, player].
+#[account]
+#[derive(InitSpace)]
+pub struct MarketPosition {
+    pub market: Pubkey,
+    pub player: Pubkey,
+    pub heads: u64,
+    pub tails: u64,
+    pub bump: u8,
+}
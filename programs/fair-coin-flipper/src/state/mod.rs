@@ -0,0 +1,25 @@
+pub mod activity;
+pub mod admin_log;
+pub mod bracket;
+pub mod game;
+pub mod global_state;
+pub mod lottery;
+pub mod market;
+pub mod player;
+pub mod referral;
+pub mod room;
+pub mod subscription;
+pub mod vault;
+
+pub use activity::*;
+pub use admin_log::*;
+pub use bracket::*;
+pub use game::*;
+pub use global_state::*;
+pub use lottery::*;
+pub use market::*;
+pub use player::*;
+pub use referral::*;
+pub use room::*;
+pub use subscription::*;
+pub use vault::*;
@@ -0,0 +1,128 @@
+use crate::*;
+
+// Per-player counter used as the game_id of the next created game
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerNonce {
+    pub player: Pubkey,
+    pub next_game_id: u64,
+    pub bump: u8,
+}
+
+// Lifetime results per player, updated as games, bouts and cancels settle
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerStats {
+    pub player: Pubkey,
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub total_wagered: u64,
+    pub total_winnings: u64,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub rating: u32,
+    pub referrer: Pubkey, // Set once, the first time the player joins with a referrer
+    pub season: SeasonStats,
+    pub last_season: SeasonStats, // Snapshot of the previous season played
+    pub last_resolved_at: i64,    // 0 until the player's first resolved game
+    pub season_loss_limit: u64,   // Set by the player; max net loss per season, 0 = none
+    pub frozen_until: i64,        // Set by freeze_self; no wagering before this time
+    pub bump: u8,
+}
+
+impl PlayerStats {
+    // Stakes lost minus payouts won in the given season, floored at zero. Season
+    // counters roll lazily, so stats from an older season count as no loss.
+    pub fn season_net_loss(&self, season_id: u32) -> u64 {
+        if self.season.season_id != season_id {
+            return 0;
+        }
+        self.season.wagered.saturating_sub(self.season.winnings)
+    }
+
+    pub fn is_frozen(&self, now: i64) -> bool {
+        now < self.frozen_until
+    }
+}
+
+// Per-season counters kept alongside lifetime totals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct SeasonStats {
+    pub season_id: u32,
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub wagered: u64,
+    pub winnings: u64,
+}
+
+// A player's latest terms-of-service acceptance; each acceptance also emits TermsAccepted
+#[account]
+#[derive(InitSpace)]
+pub struct TermsAcceptance {
+    pub player: Pubkey,
+    pub version: u32,
+    pub accepted_at: i64,
+    pub bump: u8,
+}
+
+// Bitset of unlocked achievements per player
+#[account]
+#[derive(InitSpace)]
+pub struct Achievements {
+    pub player: Pubkey,
+    pub unlocked: u64,
+    pub bump: u8,
+}
+
+// Public player profile
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    pub owner: Pubkey,
+    pub username: [u8; MAX_USERNAME_LENGTH],
+    pub username_len: u8,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+// Name PDA reserving a username for a single owner
+#[account]
+#[derive(InitSpace)]
+pub struct UsernameRecord {
+    pub owner: Pubkey,
+    pub bump: u8,
+}
+
+// Ring buffer of a player's most recent games; `head` is the next slot to write
+#[account]
+#[derive(InitSpace)]
+pub struct GameHistory {
+    pub player: Pubkey,
+    pub entries: [GameSummary; GAME_HISTORY_LENGTH],
+    pub head: u8,
+    pub count: u8,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct GameSummary {
+    pub game_id: u64,
+    pub opponent: Pubkey,
+    pub bet_amount: u64,
+    pub coin_result: CoinSide,
+    pub won: bool,
+    pub resolved_at: i64,
+}
+
+// Session key allowed to commit and reveal for a player within time and stake bounds
+#[account]
+#[derive(InitSpace)]
+pub struct Delegate {
+    pub player: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub max_stake: u64, // Largest bet_amount the session key may play
+    pub bump: u8,
+}
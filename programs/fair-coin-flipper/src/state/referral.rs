@@ -0,0 +1,27 @@
+use crate::*;
+
+// Lifetime affiliate counters; also holds accrued fee shares as lamports
+#[account]
+#[derive(InitSpace)]
+pub struct Referrer {
+    pub referrer: Pubkey,
+    pub referred_players: u64,
+    pub referred_volume: u64,
+    pub fees_earned: u64,
+    pub fees_claimed: u64,
+    pub bump: u8,
+}
+
+// Top referrers by lifetime fees earned, highest first
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralLeaderboard {
+    pub entries: [ReferralEntry; REFERRAL_LEADERBOARD_LENGTH],
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct ReferralEntry {
+    pub referrer: Pubkey,
+    pub fees_earned: u64,
+}
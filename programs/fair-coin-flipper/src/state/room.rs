@@ -0,0 +1,71 @@
+use crate::*;
+
+// A queued challenger. `commitment` is the draw_commitment of the secret they will
+// defend with if they take the room.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct RoomChallenger {
+    pub player: Pubkey,
+    pub side: CoinSide, // The challenger wins the bout if the coin lands on this side
+    pub commitment: [u8; 32],
+}
+
+impl RoomChallenger {
+    pub const EMPTY: Self = Self {
+        player: Pubkey::new_from_array([0; 32]),
+        side: CoinSide::Heads,
+        commitment: [0; 32],
+    };
+}
+
+// King-of-the-hill room. The champion's stake stays in a system-owned escrow PDA
+// [b"room_escrow", room] together with the stakes of every queued challenger, and
+// challengers flip against the champion one at a time in queue order. Bouts are
+// drawn like markets (see draws.rs), with the champion revealing the secret.
+// Seeds: [b"room", creator, room_id].
+#[account]
+#[derive(InitSpace)]
+pub struct Room {
+    pub room_id: u64,
+    pub creator: Pubkey, // Opened the room, funded the escrow reserve and gets it back
+    pub stake: u64,      // Staked by the champion and by every challenger
+    pub house_fee_bps: u64, // Locked in at creation
+    pub champion: Pubkey,
+    pub champion_commitment: [u8; 32], // draw_commitment of the champion's next secret
+    pub streak: u32,                   // Bouts the current champion has won
+    pub winnings: u64,                 // Won by the current champion, after fees
+    pub bouts: u64,                    // Bouts settled in this room
+    pub bout_draw_slot: u64,           // Draw slot of the head challenger's bout; 0 with no queue
+    pub queue: [RoomChallenger; MAX_ROOM_QUEUE],
+    pub queue_len: u8,
+    pub escrow_bump: u8,
+    pub bump: u8,
+}
+
+impl Room {
+    pub fn head(&self) -> Option<&RoomChallenger> {
+        self.queue[..self.queue_len as usize].first()
+    }
+
+    pub fn is_queued(&self, player: &Pubkey) -> bool {
+        self.queue[..self.queue_len as usize]
+            .iter()
+            .any(|challenger| challenger.player == *player)
+    }
+
+    pub fn push_challenger(&mut self, challenger: RoomChallenger) -> Result<()> {
+        let len = self.queue_len as usize;
+        require!(len < MAX_ROOM_QUEUE, GameError::RoomQueueFull);
+        self.queue[len] = challenger;
+        self.queue_len += 1;
+        Ok(())
+    }
+
+    pub fn pop_challenger(&mut self) -> Option<RoomChallenger> {
+        let head = *self.head()?;
+        let len = self.queue_len as usize;
+        self.queue.copy_within(1..len, 0);
+        self.queue[len - 1] = RoomChallenger::EMPTY;
+        self.queue_len -= 1;
+        Some(head)
+    }
+}
@@ -0,0 +1,24 @@
+use crate::*;
+
+// A player's standing order to join games for them. Stakes are paid from the
+// subscription escrow (["subscription_escrow", subscription]), which is the
+// joined games' sponsor: refunds land back in it, and so does the stake of every
+// game won, so wins keep rebetting until max_games or the budget runs out.
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub player: Pubkey,
+    pub stake: u64,
+    pub max_games: u32,
+    pub games_joined: u32,
+    pub opponent: Pubkey, // Only join games this player created; default joins anyone's
+    pub escrow_bump: u8,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub fn matches(&self, game: &Game) -> bool {
+        game.bet_amount == self.stake
+            && (self.opponent == Pubkey::default() || game.player_a == self.opponent)
+    }
+}
@@ -0,0 +1,13 @@
+use crate::*;
+
+// Shared escrow for games created while GlobalState::vault_escrow is on, so
+// high-volume operators skip a fresh escrow PDA and its rent per game. Each vault
+// game's ledger entry is what a per-game escrow would hold for it, minus the rent
+// reserve (expected_escrow_balance with no reserve); `liabilities` is the sum of
+// those entries and moves with every deposit and payout.
+#[account]
+#[derive(InitSpace)]
+pub struct EscrowVault {
+    pub liabilities: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,327 @@
+use crate::*;
+
+// Set up a freshly created stats account
+pub(crate) fn init_player_stats_if_needed(stats: &mut PlayerStats, player: Pubkey, bump: u8) {
+    if stats.player == Pubkey::default() {
+        stats.player = player;
+        stats.rating = INITIAL_RATING;
+        stats.bump = bump;
+    }
+}
+
+// Record a resolved game on both players' stats
+pub(crate) fn record_game_result(
+    winner_stats: &mut PlayerStats,
+    loser_stats: &mut PlayerStats,
+    bet_amount: u64,
+    winner_payout: u64,
+    season_id: u32,
+    resolved_at: i64,
+) -> u32 {
+    roll_season(winner_stats, season_id);
+    roll_season(loser_stats, season_id);
+    winner_stats.last_resolved_at = resolved_at;
+    loser_stats.last_resolved_at = resolved_at;
+
+    winner_stats.games_played = winner_stats.games_played.saturating_add(1);
+    winner_stats.wins = winner_stats.wins.saturating_add(1);
+    winner_stats.total_wagered = winner_stats.total_wagered.saturating_add(bet_amount);
+    winner_stats.total_winnings = winner_stats.total_winnings.saturating_add(winner_payout);
+    winner_stats.current_streak = winner_stats.current_streak.saturating_add(1);
+    winner_stats.best_streak = winner_stats.best_streak.max(winner_stats.current_streak);
+
+    loser_stats.games_played = loser_stats.games_played.saturating_add(1);
+    loser_stats.losses = loser_stats.losses.saturating_add(1);
+    loser_stats.total_wagered = loser_stats.total_wagered.saturating_add(bet_amount);
+    loser_stats.current_streak = 0;
+
+    winner_stats.season.games_played = winner_stats.season.games_played.saturating_add(1);
+    winner_stats.season.wins = winner_stats.season.wins.saturating_add(1);
+    winner_stats.season.wagered = winner_stats.season.wagered.saturating_add(bet_amount);
+    winner_stats.season.winnings = winner_stats.season.winnings.saturating_add(winner_payout);
+
+    loser_stats.season.games_played = loser_stats.season.games_played.saturating_add(1);
+    loser_stats.season.losses = loser_stats.season.losses.saturating_add(1);
+    loser_stats.season.wagered = loser_stats.season.wagered.saturating_add(bet_amount);
+
+    let rating_delta = compute_rating_delta(winner_stats.rating, loser_stats.rating, bet_amount);
+    winner_stats.rating = winner_stats.rating.saturating_add(rating_delta);
+    loser_stats.rating = loser_stats
+        .rating
+        .saturating_sub(rating_delta)
+        .max(MIN_RATING);
+    rating_delta
+}
+
+// Markets, lotteries and brackets pay out per claim, and their losers may never
+// claim (a bracket's can't), so a stake counts toward the season totals when it's
+// placed rather than when it settles
+pub(crate) fn record_pooled_stake(stats: &mut PlayerStats, stake: u64, season_id: u32) {
+    roll_season(stats, season_id);
+    stats.total_wagered = stats.total_wagered.saturating_add(stake);
+    stats.season.wagered = stats.season.wagered.saturating_add(stake);
+}
+
+// Record a claimed payout; losing market positions and lottery tickets claim 0
+pub(crate) fn record_pooled_payout(
+    stats: &mut PlayerStats,
+    payout: u64,
+    season_id: u32,
+    resolved_at: i64,
+) {
+    roll_season(stats, season_id);
+    stats.last_resolved_at = resolved_at;
+    stats.total_winnings = stats.total_winnings.saturating_add(payout);
+    stats.season.winnings = stats.season.winnings.saturating_add(payout);
+}
+
+// A refunded stake never counted as wagered
+pub(crate) fn record_pooled_refund(stats: &mut PlayerStats, stake: u64, season_id: u32) {
+    roll_season(stats, season_id);
+    stats.total_wagered = stats.total_wagered.saturating_sub(stake);
+    stats.season.wagered = stats.season.wagered.saturating_sub(stake);
+}
+
+// A cancelled game refunds each player's stake less the cancellation fee, so only
+// the fee counts as wagered (and lost)
+pub(crate) fn record_cancellation(stats: &mut PlayerStats, fee: u64, season_id: u32) {
+    roll_season(stats, season_id);
+    stats.total_wagered = stats.total_wagered.saturating_add(fee);
+    stats.season.wagered = stats.season.wagered.saturating_add(fee);
+}
+
+// Elo-style rating change for the winner (and loss for the loser).
+// Uses a linear approximation of the expected score to stay in integer math,
+// with a K-factor that grows with the stake.
+pub fn compute_rating_delta(winner_rating: u32, loser_rating: u32, bet_amount: u64) -> u32 {
+    let k_factor: u64 = if bet_amount >= 1_000_000_000 {
+        40
+    } else if bet_amount >= 100_000_000 {
+        32
+    } else {
+        24
+    };
+
+    // Expected score of the winner in basis points, clamped to [5%, 95%]
+    let rating_diff = (winner_rating as i64 - loser_rating as i64).clamp(-400, 400);
+    let expected_bps = (5_000 + rating_diff * 4_500 / 400) as u64;
+
+    let delta = k_factor * (10_000 - expected_bps) / 10_000;
+    delta.max(1) as u32
+}
+
+// Snapshot the finished season and start fresh counters if a new season began
+pub(crate) fn roll_season(stats: &mut PlayerStats, season_id: u32) {
+    if stats.season.season_id != season_id {
+        stats.last_season = stats.season;
+        stats.season = SeasonStats {
+            season_id,
+            ..SeasonStats::default()
+        };
+    }
+}
+
+// Set up a freshly created achievements account
+pub(crate) fn init_achievements_if_needed(
+    achievements: &mut Achievements,
+    player: Pubkey,
+    bump: u8,
+) {
+    if achievements.player == Pubkey::default() {
+        achievements.player = player;
+        achievements.bump = bump;
+    }
+}
+
+// Unlock achievements earned after stats were updated; `win_payout` is 0 for a loss
+pub(crate) fn unlock_achievements(
+    achievements: &mut Achievements,
+    stats: &PlayerStats,
+    win_payout: u64,
+    game_id: u64,
+    unlocked_at: i64,
+) {
+    let mut earned = 0u64;
+    if stats.wins >= 1 {
+        earned |= ACHIEVEMENT_FIRST_WIN;
+    }
+    if stats.games_played >= 100 {
+        earned |= ACHIEVEMENT_100_GAMES;
+    }
+    if stats.current_streak >= 10 {
+        earned |= ACHIEVEMENT_10_WIN_STREAK;
+    }
+    if win_payout >= 1_000_000_000 {
+        earned |= ACHIEVEMENT_1_SOL_WIN;
+    }
+
+    let newly_unlocked = earned & !achievements.unlocked;
+    if newly_unlocked == 0 {
+        return;
+    }
+    achievements.unlocked |= newly_unlocked;
+
+    let mut remaining = newly_unlocked;
+    while remaining != 0 {
+        let achievement = remaining & remaining.wrapping_neg();
+        remaining &= !achievement;
+        emit!(AchievementUnlocked {
+            event_version: EVENT_VERSION,
+            player: achievements.player,
+            achievement,
+            game_id,
+            unlocked_at,
+        });
+    }
+}
+
+// Unix day index used to key DailyStats accounts
+pub fn unix_day(timestamp: i64) -> u64 {
+    timestamp.div_euclid(SECONDS_PER_DAY) as u64
+}
+
+pub(crate) fn current_unix_day() -> Result<u64> {
+    Ok(unix_day(Clock::get()?.unix_timestamp))
+}
+
+// Add a resolved game to the day's aggregate counters
+pub(crate) fn record_daily_stats(
+    daily_stats: &mut DailyStats,
+    bump: u8,
+    timestamp: i64,
+    total_pot: u64,
+    house_fee: u64,
+) {
+    if daily_stats.games_resolved == 0 {
+        daily_stats.day = unix_day(timestamp);
+        daily_stats.bump = bump;
+    }
+    daily_stats.games_resolved = daily_stats.games_resolved.saturating_add(1);
+    daily_stats.volume = daily_stats.volume.saturating_add(total_pot);
+    daily_stats.fees = daily_stats.fees.saturating_add(house_fee);
+}
+
+// Highest multiple of `interval` in (before, after], if any
+pub fn crossed_milestone(before: u128, after: u128, interval: u128) -> Option<u128> {
+    let reached = after / interval;
+    (reached > before / interval).then(|| reached * interval)
+}
+
+// Update lifetime totals and hall-of-fame records in GlobalState, announcing any
+// records broken and milestones crossed
+pub(crate) fn update_records(
+    global_state: &mut GlobalState,
+    winner: Pubkey,
+    game_id: u64,
+    total_pot: u64,
+    winner_streak: u32,
+    resolution_seconds: i64,
+) {
+    let (games_before, volume_before) = (global_state.total_games, global_state.total_volume);
+    global_state.total_games = global_state.total_games.saturating_add(1);
+    global_state.total_volume = global_state.total_volume.saturating_add(total_pot as u128);
+    for (kind, before, after, interval) in [
+        (
+            MilestoneKind::GamesResolved,
+            games_before as u128,
+            global_state.total_games as u128,
+            MILESTONE_GAMES_INTERVAL,
+        ),
+        (
+            MilestoneKind::VolumeSettled,
+            volume_before,
+            global_state.total_volume,
+            MILESTONE_VOLUME_INTERVAL,
+        ),
+    ] {
+        if let Some(threshold) = crossed_milestone(before, after, interval) {
+            emit!(Milestone {
+                event_version: EVENT_VERSION,
+                kind,
+                threshold,
+                game_id,
+            });
+        }
+    }
+
+    if total_pot > global_state.largest_pot {
+        emit!(NewRecord {
+            event_version: EVENT_VERSION,
+            kind: RecordKind::LargestPot,
+            holder: winner,
+            value: total_pot,
+            previous_holder: global_state.largest_pot_holder,
+            previous_value: global_state.largest_pot,
+            game_id,
+        });
+        global_state.largest_pot = total_pot;
+        global_state.largest_pot_holder = winner;
+    }
+
+    if winner_streak > global_state.longest_streak {
+        emit!(NewRecord {
+            event_version: EVENT_VERSION,
+            kind: RecordKind::LongestStreak,
+            holder: winner,
+            value: winner_streak as u64,
+            previous_holder: global_state.longest_streak_holder,
+            previous_value: global_state.longest_streak as u64,
+            game_id,
+        });
+        global_state.longest_streak = winner_streak;
+        global_state.longest_streak_holder = winner;
+    }
+
+    // A zero fastest time means no record has been set yet
+    let resolution_seconds = resolution_seconds.max(0);
+    if global_state.fastest_resolution_seconds == 0
+        || resolution_seconds < global_state.fastest_resolution_seconds
+    {
+        emit!(NewRecord {
+            event_version: EVENT_VERSION,
+            kind: RecordKind::FastestResolution,
+            holder: winner,
+            value: resolution_seconds as u64,
+            previous_holder: global_state.fastest_resolution_holder,
+            previous_value: global_state.fastest_resolution_seconds as u64,
+            game_id,
+        });
+        global_state.fastest_resolution_seconds = resolution_seconds;
+        global_state.fastest_resolution_holder = winner;
+    }
+}
+
+// Usernames are 3-16 chars of lowercase letters, digits, or underscores,
+// so the name PDA check is effectively case-insensitive
+pub fn is_valid_username(username: &str) -> bool {
+    (MIN_USERNAME_LENGTH..=MAX_USERNAME_LENGTH).contains(&username.len())
+        && username
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'_')
+}
+
+// Set up a freshly created history account
+pub(crate) fn init_game_history_if_needed(history: &mut GameHistory, player: Pubkey, bump: u8) {
+    if history.player == Pubkey::default() {
+        history.player = player;
+        history.bump = bump;
+    }
+}
+
+// Write a summary into the ring buffer, overwriting the oldest entry when full
+pub(crate) fn push_game_history(history: &mut GameHistory, summary: GameSummary) {
+    history.entries[history.head as usize] = summary;
+    history.head = ((history.head as usize + 1) % GAME_HISTORY_LENGTH) as u8;
+    if (history.count as usize) < GAME_HISTORY_LENGTH {
+        history.count += 1;
+    }
+}
+
+// Write a game into the global feed, overwriting the oldest entry when full
+pub(crate) fn push_recent_game(recent_games: &mut RecentGames, game: RecentGame) {
+    recent_games.entries[recent_games.head as usize] = game;
+    recent_games.head = ((recent_games.head as usize + 1) % RECENT_GAMES_LENGTH) as u8;
+    if (recent_games.count as usize) < RECENT_GAMES_LENGTH {
+        recent_games.count += 1;
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameError, GameStatus};
+
+// Everything that can move a game from one status to another
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GameEvent {
+    Join,
+    Commit,
+    CommitmentsComplete,
+    Reveal,
+    Resolve,
+    Cancel,
+}
+
+// The single table of legal status changes. Instructions call this instead of
+// checking and assigning statuses by hand.
+pub fn advance(status: &GameStatus, event: GameEvent) -> Result<GameStatus> {
+    use GameStatus::*;
+
+    let next = match (status, event) {
+        (WaitingForPlayer, GameEvent::Join) => PlayersReady,
+        (PlayersReady, GameEvent::Commit) => PlayersReady,
+        (PlayersReady, GameEvent::CommitmentsComplete) => CommitmentsReady,
+        (CommitmentsReady | RevealingPhase, GameEvent::Reveal) => RevealingPhase,
+        (RevealingPhase, GameEvent::Resolve) => Resolved,
+        (
+            WaitingForPlayer | PlayersReady | CommitmentsReady | RevealingPhase,
+            GameEvent::Cancel,
+        ) => Cancelled,
+        _ => return err!(GameError::InvalidGameStatus),
+    };
+    Ok(next)
+}
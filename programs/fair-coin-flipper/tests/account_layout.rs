@@ -0,0 +1,91 @@
+use anchor_lang::Space;
+use bytemuck::Zeroable;
+use fair_coin_flipper::{
+    CoinSide, Game, GameStatus, Receipt, ResolvedSummary, FLAG_COMMITMENTS_COMPLETE,
+    FLAG_REPAY_SPONSOR_B, FLAG_SETTLEMENT_HALTED,
+};
+
+#[test]
+fn game_size_matches_documented_layout() {
+    assert_eq!(std::mem::size_of::<Game>(), 584);
+    // No implicit padding, so the derived space is the in-memory size
+    assert_eq!(Game::INIT_SPACE, std::mem::size_of::<Game>());
+}
+
+#[test]
+fn resolved_summary_matches_documented_size() {
+    assert_eq!(ResolvedSummary::INIT_SPACE, 210);
+}
+
+#[test]
+fn receipt_matches_documented_size() {
+    assert_eq!(Receipt::INIT_SPACE, 130);
+}
+
+#[test]
+fn resolved_summary_keeps_the_result() {
+    let mut game = Game::zeroed();
+    game.game_id = 7;
+    game.winner = game.player_b;
+    game.set_status(GameStatus::Resolved);
+    assert!(ResolvedSummary::from_game(&game).is_err());
+
+    game.set_coin_result(CoinSide::Tails);
+    game.resolved_at = 1_700_000_000;
+    let archived = ResolvedSummary::from_game(&game).unwrap().archived();
+    assert_eq!(archived.game_id, 7);
+    assert!(archived.status == GameStatus::Resolved);
+    assert!(archived.coin_result == Some(CoinSide::Tails));
+    assert_eq!(archived.resolved_at, Some(1_700_000_000));
+}
+
+#[test]
+fn seq_counts_up_and_survives_compaction() {
+    let mut game = Game::zeroed();
+    assert_eq!(game.next_seq(), 1);
+    assert_eq!(game.next_seq(), 2);
+
+    game.set_status(GameStatus::Resolved);
+    game.set_coin_result(CoinSide::Heads);
+    assert_eq!(ResolvedSummary::from_game(&game).unwrap().seq, 2);
+}
+
+#[test]
+fn zeroed_game_has_nothing_set() {
+    let game = Game::zeroed();
+    assert!(game.status() == GameStatus::WaitingForPlayer);
+    assert!(!game.flag(FLAG_COMMITMENTS_COMPLETE));
+    assert!(game.choice_a().is_none());
+    assert!(game.choice_b().is_none());
+    assert!(game.coin_result().is_none());
+    assert!(game.winner().is_none());
+    assert!(game.resolved_at().is_none());
+}
+
+#[test]
+fn packed_fields_do_not_overlap() {
+    let mut game = Game::zeroed();
+    game.set_flag(FLAG_SETTLEMENT_HALTED, true);
+    game.set_flag(FLAG_REPAY_SPONSOR_B, true);
+    game.set_status(GameStatus::Cancelled);
+    game.set_choice_a(CoinSide::Tails);
+    game.set_choice_b(CoinSide::Heads);
+    game.set_coin_result(CoinSide::Tails);
+
+    assert!(game.status() == GameStatus::Cancelled);
+    assert!(game.flag(FLAG_SETTLEMENT_HALTED));
+    assert!(game.flag(FLAG_REPAY_SPONSOR_B));
+    assert!(!game.flag(FLAG_COMMITMENTS_COMPLETE));
+    assert!(game.choice_a() == Some(CoinSide::Tails));
+    assert!(game.choice_b() == Some(CoinSide::Heads));
+    assert!(game.coin_result() == Some(CoinSide::Tails));
+
+    game.set_flag(FLAG_SETTLEMENT_HALTED, false);
+    game.set_status(GameStatus::Resolved);
+    game.set_choice_a(CoinSide::Heads);
+    assert!(!game.flag(FLAG_SETTLEMENT_HALTED));
+    assert!(game.flag(FLAG_REPAY_SPONSOR_B));
+    assert!(game.status() == GameStatus::Resolved);
+    assert!(game.choice_a() == Some(CoinSide::Heads));
+    assert!(game.choice_b() == Some(CoinSide::Heads));
+}
@@ -0,0 +1,1749 @@
+#![allow(dead_code)]
+
+use anchor_lang::prelude::{AccountInfo, Clock, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction, InstructionError};
+use anchor_lang::solana_program::program::{invoke, MAX_RETURN_DATA};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::{
+    system_program, AccountDeserialize, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use fair_coin_flipper::{
+    accounts, draw_commitment, generate_commitment, instruction, CoinSide, Game, GameResolved,
+    FLAG_VAULT_ESCROW, ID,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::sysvar;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const BET: u64 = 100_000_000; // 0.1 SOL
+pub const PLAYER_FUNDING: u64 = 10_000_000_000;
+pub const CANCEL_TIMEOUT_SECONDS: i64 = 3_600;
+pub const EMERGENCY_RECOVERY_MIN_AGE_SECONDS: i64 = 604_800;
+pub const RESOLVE_CU_LIMIT: u32 = 1_000_000;
+
+// Anchor's entry point ties the account slice to the AccountInfo lifetime,
+// which processor! can't express; leaking the slice for the test run is fine
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    fair_coin_flipper::entry(program_id, accounts, data)
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ID).0
+}
+
+pub fn global_state() -> Pubkey {
+    pda(&[b"global_state"])
+}
+
+pub fn treasury() -> Pubkey {
+    pda(&[b"treasury"])
+}
+
+pub fn promo_vault() -> Pubkey {
+    pda(&[b"promo_vault"])
+}
+
+pub fn event_authority() -> Pubkey {
+    pda(&[b"__event_authority"])
+}
+
+pub fn game_pda(creator: &Pubkey, game_id: u64) -> Pubkey {
+    pda(&[b"game", creator.as_ref(), &game_id.to_le_bytes()])
+}
+
+pub fn escrow_pda(creator: &Pubkey, game_id: u64) -> Pubkey {
+    pda(&[b"escrow", creator.as_ref(), &game_id.to_le_bytes()])
+}
+
+pub fn receipt_pda(creator: &Pubkey, game_id: u64) -> Pubkey {
+    pda(&[b"receipt", creator.as_ref(), &game_id.to_le_bytes()])
+}
+
+pub fn escrow_vault() -> Pubkey {
+    pda(&[b"escrow_vault"])
+}
+
+// Where a created game's stakes are held
+pub fn game_escrow(game: &Game) -> Pubkey {
+    if game.flag(FLAG_VAULT_ESCROW) {
+        escrow_vault()
+    } else {
+        escrow_pda(&game.creator, game.game_id)
+    }
+}
+
+pub fn player_stats(player: &Pubkey) -> Pubkey {
+    pda(&[b"player_stats", player.as_ref()])
+}
+
+pub fn achievements(player: &Pubkey) -> Pubkey {
+    pda(&[b"achievements", player.as_ref()])
+}
+
+pub fn game_history(player: &Pubkey) -> Pubkey {
+    pda(&[b"game_history", player.as_ref()])
+}
+
+pub fn delegate(player: &Pubkey) -> Pubkey {
+    pda(&[b"delegate", player.as_ref()])
+}
+
+pub fn terms_acceptance(player: &Pubkey) -> Pubkey {
+    pda(&[b"terms", player.as_ref()])
+}
+
+pub fn market_pda(market_id: u64) -> Pubkey {
+    pda(&[b"market", &market_id.to_le_bytes()])
+}
+
+pub fn market_escrow(market_id: u64) -> Pubkey {
+    pda(&[b"market_escrow", market_pda(market_id).as_ref()])
+}
+
+pub fn market_position(market_id: u64, player: &Pubkey) -> Pubkey {
+    pda(&[
+        b"market_position",
+        market_pda(market_id).as_ref(),
+        player.as_ref(),
+    ])
+}
+
+pub fn lottery_pda(lottery_id: u64) -> Pubkey {
+    pda(&[b"lottery", &lottery_id.to_le_bytes()])
+}
+
+pub fn lottery_escrow(lottery_id: u64) -> Pubkey {
+    pda(&[b"lottery_escrow", lottery_pda(lottery_id).as_ref()])
+}
+
+pub fn lottery_ticket(lottery_id: u64, number: u32) -> Pubkey {
+    pda(&[
+        b"lottery_ticket",
+        lottery_pda(lottery_id).as_ref(),
+        &number.to_le_bytes(),
+    ])
+}
+
+pub fn room_pda(creator: &Pubkey, room_id: u64) -> Pubkey {
+    pda(&[b"room", creator.as_ref(), &room_id.to_le_bytes()])
+}
+
+pub fn room_escrow(creator: &Pubkey, room_id: u64) -> Pubkey {
+    pda(&[b"room_escrow", room_pda(creator, room_id).as_ref()])
+}
+
+pub fn match_queue_pda(stake: u64) -> Pubkey {
+    pda(&[b"match_queue", &stake.to_le_bytes()])
+}
+
+pub fn match_queue_escrow(stake: u64) -> Pubkey {
+    pda(&[b"match_queue_escrow", match_queue_pda(stake).as_ref()])
+}
+
+pub fn bracket_pda(bracket_id: u64) -> Pubkey {
+    pda(&[b"bracket", &bracket_id.to_le_bytes()])
+}
+
+pub fn bracket_escrow(bracket_id: u64) -> Pubkey {
+    pda(&[b"bracket_escrow", bracket_pda(bracket_id).as_ref()])
+}
+
+pub fn subscription(player: &Pubkey) -> Pubkey {
+    pda(&[b"subscription", player.as_ref()])
+}
+
+pub fn subscription_escrow(player: &Pubkey) -> Pubkey {
+    pda(&[b"subscription_escrow", subscription(player).as_ref()])
+}
+
+pub fn referrer_pda(wallet: &Pubkey) -> Pubkey {
+    pda(&[b"referrer", wallet.as_ref()])
+}
+
+pub fn referral_leaderboard() -> Pubkey {
+    pda(&[b"referral_leaderboard"])
+}
+
+pub fn daily_stats(now: i64) -> Pubkey {
+    pda(&[
+        b"daily_stats",
+        &fair_coin_flipper::unix_day(now).to_le_bytes(),
+    ])
+}
+
+pub struct Harness {
+    pub ctx: ProgramTestContext,
+    pub authority: Keypair,
+    pub vault_escrow: bool, // New games escrow in the shared vault
+    pub terms_version: u32, // Nonzero once players must accept terms
+}
+
+pub struct Players {
+    pub a: Keypair,
+    pub b: Keypair,
+}
+
+impl Harness {
+    pub async fn new() -> Self {
+        Self::new_with(|_| {}).await
+    }
+
+    // Lets a test register extra programs, such as a relay or a resolution hook
+    pub async fn new_with(configure: impl FnOnce(&mut ProgramTest)) -> Self {
+        let mut program_test =
+            ProgramTest::new("fair_coin_flipper", ID, processor!(process_instruction));
+        configure(&mut program_test);
+        Self::start(program_test).await
+    }
+
+    // Loads fair_coin_flipper.so from the SBF build instead of running the program
+    // natively, so every instruction is metered as it would be on chain. Only
+    // available under `cargo test-sbf`, which builds it and points SBF_OUT_DIR at it.
+    pub async fn new_sbf() -> Self {
+        let mut program_test = ProgramTest::new("fair_coin_flipper", ID, None);
+        program_test.prefer_bpf(true);
+        Self::start(program_test).await
+    }
+
+    async fn start(program_test: ProgramTest) -> Self {
+        let ctx = program_test.start_with_context().await;
+        let mut harness = Harness {
+            ctx,
+            authority: Keypair::new(),
+            vault_escrow: false,
+            terms_version: 0,
+        };
+        harness
+            .fund(&harness.authority.pubkey(), PLAYER_FUNDING)
+            .await;
+        harness.initialize().await;
+        harness
+    }
+
+    pub async fn fund(&mut self, to: &Pubkey, lamports: u64) {
+        let payer = self.ctx.payer.insecure_clone();
+        let ix = system_instruction::transfer(&payer.pubkey(), to, lamports);
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    pub async fn new_players(&mut self) -> Players {
+        let players = Players {
+            a: Keypair::new(),
+            b: Keypair::new(),
+        };
+        self.fund(&players.a.pubkey(), PLAYER_FUNDING).await;
+        self.fund(&players.b.pubkey(), PLAYER_FUNDING).await;
+        players
+    }
+
+    pub async fn send(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let tx = self.transaction(ixs, signers).await?;
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    // Simulate first to read the compute units used, then land the transaction
+    pub async fn send_measured(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> u64 {
+        let tx = self.transaction(ixs, signers).await.unwrap();
+        let simulation = self
+            .ctx
+            .banks_client
+            .simulate_transaction(tx.clone())
+            .await
+            .unwrap();
+        if let Some(Err(err)) = simulation.result {
+            panic!("simulation failed: {err:?}");
+        }
+        let units = simulation.simulation_details.unwrap().units_consumed;
+        self.ctx.banks_client.process_transaction(tx).await.unwrap();
+        units
+    }
+
+    // Simulate a read-only view instruction and decode what it returned
+    pub async fn view<T: AnchorDeserialize>(&mut self, ix: Instruction) -> T {
+        let tx = self.transaction(&[ix], &[]).await.unwrap();
+        let simulation = self
+            .ctx
+            .banks_client
+            .simulate_transaction(tx)
+            .await
+            .unwrap();
+        if let Some(Err(err)) = simulation.result {
+            panic!("simulation failed: {err:?}");
+        }
+        // Trailing zero bytes are trimmed from return data, so pad them back
+        let mut data = simulation
+            .simulation_details
+            .unwrap()
+            .return_data
+            .unwrap()
+            .data;
+        data.resize(MAX_RETURN_DATA, 0);
+        T::deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    async fn transaction(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Transaction, BanksClientError> {
+        let payer = self.ctx.payer.insecure_clone();
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await?;
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        Ok(Transaction::new_signed_with_payer(
+            ixs,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        ))
+    }
+
+    pub async fn initialize(&mut self) {
+        let payer = self.ctx.payer.pubkey();
+        let authority = self.authority.pubkey();
+
+        let init_global = Instruction {
+            program_id: ID,
+            accounts: accounts::InitializeGlobalState {
+                payer,
+                global_state: global_state(),
+                treasury: treasury(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeGlobalState {
+                authority,
+                house_wallet: authority,
+            }
+            .data(),
+        };
+        let init_recent_games = Instruction {
+            program_id: ID,
+            accounts: accounts::InitializeRecentGames {
+                payer,
+                authority,
+                global_state: global_state(),
+                recent_games: pda(&[b"recent_games"]),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeRecentGames {}.data(),
+        };
+        let init_leaderboard = Instruction {
+            program_id: ID,
+            accounts: accounts::InitializeReferralLeaderboard {
+                payer,
+                authority,
+                global_state: global_state(),
+                referral_leaderboard: pda(&[b"referral_leaderboard"]),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeReferralLeaderboard {}.data(),
+        };
+        let init_admin_log = Instruction {
+            program_id: ID,
+            accounts: accounts::InitializeAdminLog {
+                payer,
+                authority,
+                global_state: global_state(),
+                admin_log: pda(&[b"admin_log"]),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeAdminLog {}.data(),
+        };
+
+        let authority_kp = self.authority.insecure_clone();
+        self.send(&[init_global], &[]).await.unwrap();
+        self.send(
+            &[init_recent_games, init_leaderboard, init_admin_log],
+            &[&authority_kp],
+        )
+        .await
+        .unwrap();
+    }
+
+    // Admin instructions share the AdminAction accounts and the authority's signature
+    pub fn admin_ix(&self, data: impl InstructionData) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::AdminAction {
+                authority: self.authority.pubkey(),
+                global_state: global_state(),
+                admin_log: pda(&[b"admin_log"]),
+            }
+            .to_account_metas(None),
+            data: data.data(),
+        }
+    }
+
+    pub async fn admin(&mut self, data: impl InstructionData) -> Result<(), BanksClientError> {
+        let ix = self.admin_ix(data);
+        let authority = self.authority.insecure_clone();
+        self.send(&[ix], &[&authority]).await
+    }
+
+    pub fn migrate_global_state_ix(&self) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::MigrateGlobalState {
+                payer: self.ctx.payer.pubkey(),
+                authority: self.authority.pubkey(),
+                global_state: global_state(),
+                admin_log: Some(pda(&[b"admin_log"])),
+                treasury: treasury(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::MigrateGlobalState {}.data(),
+        }
+    }
+
+    pub async fn set_escrow_mode(&mut self, vault_escrow: bool) {
+        let ix = Instruction {
+            program_id: ID,
+            accounts: accounts::SetEscrowMode {
+                authority: self.authority.pubkey(),
+                payer: self.ctx.payer.pubkey(),
+                global_state: global_state(),
+                escrow_vault: escrow_vault(),
+                admin_log: pda(&[b"admin_log"]),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::SetEscrowMode { vault_escrow }.data(),
+        };
+        let authority = self.authority.insecure_clone();
+        self.send(&[ix], &[&authority]).await.unwrap();
+        self.vault_escrow = vault_escrow;
+    }
+
+    pub async fn set_game_cooldown(&mut self, seconds: i64) {
+        self.admin(instruction::SetGameCooldown { seconds })
+            .await
+            .unwrap();
+    }
+
+    pub async fn set_golden_flip(&mut self, odds: u32, bonus_bps: u64) {
+        self.admin(instruction::SetGoldenFlip { odds, bonus_bps })
+            .await
+            .unwrap();
+    }
+
+    pub fn fund_promo_vault_ix(funder: &Pubkey, amount: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::FundPromoVault {
+                funder: *funder,
+                promo_vault: promo_vault(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::FundPromoVault { amount }.data(),
+        }
+    }
+
+    pub async fn set_operator(&mut self, operator: &Pubkey) {
+        self.admin(instruction::SetOperator {
+            operator: *operator,
+        })
+        .await
+        .unwrap();
+    }
+
+    pub async fn set_terms_version(&mut self, version: u32) {
+        self.admin(instruction::SetTermsVersion { version })
+            .await
+            .unwrap();
+        self.terms_version = version;
+    }
+
+    pub fn accept_terms_ix(&self, player: &Pubkey, version: u32) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::AcceptTerms {
+                player: *player,
+                payer: self.ctx.payer.pubkey(),
+                global_state: global_state(),
+                terms_acceptance: terms_acceptance(player),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::AcceptTerms { version }.data(),
+        }
+    }
+
+    pub fn set_loss_limit_ix(&self, player: &Pubkey, season_loss_limit: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::SetLossLimit {
+                player: *player,
+                payer: self.ctx.payer.pubkey(),
+                global_state: global_state(),
+                player_stats: player_stats(player),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::SetLossLimit { season_loss_limit }.data(),
+        }
+    }
+
+    pub fn freeze_self_ix(&self, player: &Pubkey, until: i64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::FreezeSelf {
+                player: *player,
+                payer: self.ctx.payer.pubkey(),
+                player_stats: player_stats(player),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::FreezeSelf { until }.data(),
+        }
+    }
+
+    pub fn register_referrer_ix(&self, wallet: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::RegisterReferrer {
+                wallet: *wallet,
+                payer: self.ctx.payer.pubkey(),
+                referrer: referrer_pda(wallet),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::RegisterReferrer {}.data(),
+        }
+    }
+
+    // Terms acceptance to pass to create and join, once terms are required
+    fn terms(&self, player: &Pubkey) -> Option<Pubkey> {
+        (self.terms_version != 0).then(|| terms_acceptance(player))
+    }
+
+    // Escrow a game about to be created will use
+    pub fn new_escrow(&self, creator: &Pubkey, game_id: u64) -> Pubkey {
+        if self.vault_escrow {
+            escrow_vault()
+        } else {
+            escrow_pda(creator, game_id)
+        }
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    pub async fn slot(&mut self) -> u64 {
+        let clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.slot
+    }
+
+    pub async fn warp_slots(&mut self, slots: u64) {
+        let slot = self.slot().await;
+        self.ctx.warp_to_slot(slot + slots).unwrap();
+    }
+
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        // Move past the current slot first so follow-up transactions get a fresh
+        // blockhash, then overwrite the clock the warp produced
+        let slot = self.ctx.banks_client.get_root_slot().await.unwrap();
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        self.ctx.warp_to_slot(slot + 2).unwrap();
+        clock.slot = slot + 2;
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn lamports(&mut self, key: &Pubkey) -> u64 {
+        self.ctx
+            .banks_client
+            .get_account(*key)
+            .await
+            .unwrap()
+            .map(|account| account.lamports)
+            .unwrap_or(0)
+    }
+
+    pub async fn data_len(&mut self, key: &Pubkey) -> usize {
+        self.ctx
+            .banks_client
+            .get_account(*key)
+            .await
+            .unwrap()
+            .map(|account| account.data.len())
+            .unwrap_or(0)
+    }
+
+    pub async fn rent_exempt(&mut self, data_len: usize) -> u64 {
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        rent.minimum_balance(data_len)
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, key: &Pubkey) -> T {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(*key)
+            .await
+            .unwrap()
+            .unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn create_game_accounts(&self, player_a: &Pubkey, game_id: u64) -> accounts::CreateGame {
+        accounts::CreateGame {
+            player_a: *player_a,
+            payer: self.ctx.payer.pubkey(),
+            global_state: global_state(),
+            attestation: None,
+            terms_acceptance: self.terms(player_a),
+            sponsor: None,
+            referrer: None,
+            player_nonce: pda(&[b"player_nonce", player_a.as_ref()]),
+            player_a_stats: player_stats(player_a),
+            player_a_achievements: achievements(player_a),
+            player_a_history: game_history(player_a),
+            game: game_pda(player_a, game_id),
+            escrow: self.new_escrow(player_a, game_id),
+            instructions: None,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub fn create_game_ix(&self, player_a: &Pubkey, game_id: u64) -> Instruction {
+        Self::create_game_with(self.create_game_accounts(player_a, game_id), game_id)
+    }
+
+    pub fn create_game_with(accounts: accounts::CreateGame, game_id: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts.to_account_metas(None),
+            data: instruction::CreateGame {
+                game_id,
+                bet_amount: BET,
+                allowed_joiners: vec![],
+                join_lock_seconds: 0,
+                tags: 0,
+                repay_sponsor: false,
+            }
+            .data(),
+        }
+    }
+
+    pub fn create_games_batch_ix(
+        &self,
+        player_a: &Pubkey,
+        first_game_id: u64,
+        bet_amounts: &[u64],
+    ) -> Instruction {
+        let mut accounts = accounts::CreateGamesBatch {
+            player_a: *player_a,
+            payer: self.ctx.payer.pubkey(),
+            global_state: global_state(),
+            attestation: None,
+            terms_acceptance: self.terms(player_a),
+            player_nonce: pda(&[b"player_nonce", player_a.as_ref()]),
+            player_a_stats: player_stats(player_a),
+            player_a_achievements: achievements(player_a),
+            player_a_history: game_history(player_a),
+            instructions: None,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        for game_id in first_game_id..first_game_id + bet_amounts.len() as u64 {
+            accounts.push(AccountMeta::new(game_pda(player_a, game_id), false));
+            accounts.push(AccountMeta::new(self.new_escrow(player_a, game_id), false));
+        }
+        Instruction {
+            program_id: ID,
+            accounts,
+            data: instruction::CreateGamesBatch {
+                first_game_id,
+                bet_amounts: bet_amounts.to_vec(),
+                tags: 0,
+            }
+            .data(),
+        }
+    }
+
+    pub fn join_game_accounts(
+        &self,
+        player_b: &Pubkey,
+        creator: &Pubkey,
+        game_id: u64,
+    ) -> accounts::JoinGame {
+        accounts::JoinGame {
+            player_b: *player_b,
+            payer: self.ctx.payer.pubkey(),
+            global_state: global_state(),
+            attestation: None,
+            terms_acceptance: self.terms(player_b),
+            sponsor: None,
+            referrer: None,
+            player_b_stats: player_stats(player_b),
+            player_b_achievements: achievements(player_b),
+            player_b_history: game_history(player_b),
+            game: game_pda(creator, game_id),
+            escrow: self.new_escrow(creator, game_id),
+            instructions: None,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub fn join_game_ix(&self, player_b: &Pubkey, creator: &Pubkey, game_id: u64) -> Instruction {
+        Self::join_game_with(self.join_game_accounts(player_b, creator, game_id))
+    }
+
+    pub fn join_game_with(accounts: accounts::JoinGame) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts.to_account_metas(None),
+            data: instruction::JoinGame {
+                repay_sponsor: false,
+                auto_pick: false,
+            }
+            .data(),
+        }
+    }
+
+    pub fn join_game_lite_ix(
+        &self,
+        player_b: &Pubkey,
+        creator: &Pubkey,
+        game_id: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::JoinGameLite {
+                player_b: *player_b,
+                global_state: global_state(),
+                game: game_pda(creator, game_id),
+                player_b_stats: player_stats(player_b),
+                escrow: self.new_escrow(creator, game_id),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::JoinGameLite {}.data(),
+        }
+    }
+
+    pub fn offer_game_ownership_ix(
+        owner: &Pubkey,
+        game: Pubkey,
+        new_owner: &Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::OfferGameOwnership {
+                owner: *owner,
+                game,
+            }
+            .to_account_metas(None),
+            data: instruction::OfferGameOwnership {
+                new_owner: *new_owner,
+            }
+            .data(),
+        }
+    }
+
+    pub fn accept_game_ownership_ix(&self, new_owner: &Pubkey, game: Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::AcceptGameOwnership {
+                new_owner: *new_owner,
+                payer: *new_owner,
+                global_state: global_state(),
+                attestation: None,
+                terms_acceptance: self.terms(new_owner),
+                new_owner_stats: player_stats(new_owner),
+                new_owner_achievements: achievements(new_owner),
+                new_owner_history: game_history(new_owner),
+                game,
+                instructions: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::AcceptGameOwnership {}.data(),
+        }
+    }
+
+    pub fn commit_ix(
+        &self,
+        player: &Pubkey,
+        game: Pubkey,
+        choice: CoinSide,
+        secret: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::MakeCommitment {
+                player: *player,
+                delegate: None,
+                payer: self.ctx.payer.pubkey(),
+                game,
+                global_state: global_state(),
+                player_stats: player_stats(player),
+                player_achievements: achievements(player),
+                player_history: game_history(player),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::MakeCommitment {
+                commitment: generate_commitment(choice, secret),
+            }
+            .data(),
+        }
+    }
+
+    // Commit for `player` through their session key
+    pub fn delegated_commit_ix(
+        &self,
+        session_key: &Pubkey,
+        player: &Pubkey,
+        game: Pubkey,
+        choice: CoinSide,
+        secret: u64,
+    ) -> Instruction {
+        let mut ix = self.commit_ix(player, game, choice, secret);
+        ix.accounts = accounts::MakeCommitment {
+            player: *session_key,
+            delegate: Some(delegate(player)),
+            payer: self.ctx.payer.pubkey(),
+            game,
+            global_state: global_state(),
+            player_stats: player_stats(player),
+            player_achievements: achievements(player),
+            player_history: game_history(player),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        ix
+    }
+
+    pub fn reveal_accounts(
+        &self,
+        player: &Pubkey,
+        game: &Game,
+        now: i64,
+    ) -> accounts::RevealChoice {
+        accounts::RevealChoice {
+            player: *player,
+            delegate: None,
+            payer: self.ctx.payer.pubkey(),
+            game: game_pda(&game.creator, game.game_id),
+            player_a: game.player_a,
+            player_b: game.player_b,
+            sponsor_a: None,
+            sponsor_b: None,
+            treasury: treasury(),
+            creator: game.creator,
+            escrow: game_escrow(game),
+            global_state: global_state(),
+            player_a_stats: player_stats(&game.player_a),
+            player_b_stats: player_stats(&game.player_b),
+            player_a_achievements: achievements(&game.player_a),
+            player_b_achievements: achievements(&game.player_b),
+            daily_stats: daily_stats(now),
+            player_a_history: game_history(&game.player_a),
+            player_b_history: game_history(&game.player_b),
+            recent_games: pda(&[b"recent_games"]),
+            player_a_referrer: None,
+            player_b_referrer: None,
+            referral_leaderboard: pda(&[b"referral_leaderboard"]),
+            instructions: None,
+            memo_program: None,
+            receipt: None,
+            hook_program: None,
+            hook_authority: None,
+            promo_vault: Some(promo_vault()),
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: ID,
+        }
+    }
+
+    pub fn reveal_ix(
+        accounts: accounts::RevealChoice,
+        choice: CoinSide,
+        secret: u64,
+    ) -> Vec<Instruction> {
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(RESOLVE_CU_LIMIT),
+            Instruction {
+                program_id: ID,
+                accounts: accounts.to_account_metas(None),
+                data: instruction::RevealChoice { choice, secret }.data(),
+            },
+        ]
+    }
+
+    // Relayed commitment paid for by the harness payer; pair it with ed25519_ix
+    pub fn commit_relayed_ix(
+        &self,
+        player: &Pubkey,
+        game: Pubkey,
+        choice: CoinSide,
+        secret: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::MakeCommitmentRelayed {
+                relayer: self.ctx.payer.pubkey(),
+                game,
+                global_state: global_state(),
+                player_stats: player_stats(player),
+                player_achievements: achievements(player),
+                player_history: game_history(player),
+                instructions: sysvar::instructions::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::MakeCommitmentRelayed {
+                player: *player,
+                commitment: generate_commitment(choice, secret),
+            }
+            .data(),
+        }
+    }
+
+    pub fn authorize_delegate_ix(
+        &self,
+        player: &Pubkey,
+        session_key: &Pubkey,
+        expires_at: i64,
+        max_stake: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::AuthorizeDelegate {
+                player: *player,
+                payer: self.ctx.payer.pubkey(),
+                delegate: delegate(player),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::AuthorizeDelegate {
+                session_key: *session_key,
+                expires_at,
+                max_stake,
+            }
+            .data(),
+        }
+    }
+
+    pub fn auto_pick_side_ix(cranker: &Pubkey, game: Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::AutoPickSide {
+                cranker: *cranker,
+                game,
+                global_state: global_state(),
+            }
+            .to_account_metas(None),
+            data: instruction::AutoPickSide {}.data(),
+        }
+    }
+
+    pub fn cancel_ix(&self, canceller: &Pubkey, game: &Game) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::CancelGame {
+                canceller: *canceller,
+                game: game_pda(&game.creator, game.game_id),
+                global_state: global_state(),
+                player_a: game.player_a,
+                player_b: game.player_b,
+                player_a_stats: player_stats(&game.player_a),
+                player_b_stats: (game.player_b != Pubkey::default())
+                    .then(|| player_stats(&game.player_b)),
+                sponsor_a: None,
+                sponsor_b: None,
+                treasury: treasury(),
+                creator: game.creator,
+                escrow: game_escrow(game),
+                instructions: None,
+                system_program: system_program::ID,
+                event_authority: event_authority(),
+                program: ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CancelGame {}.data(),
+        }
+    }
+
+    pub fn resolve_manual_ix(&self, resolver: &Pubkey, game: &Game, now: i64) -> Vec<Instruction> {
+        Self::resolve_manual_with(self.resolve_manual_accounts(resolver, game, now))
+    }
+
+    pub fn resolve_manual_accounts(
+        &self,
+        resolver: &Pubkey,
+        game: &Game,
+        now: i64,
+    ) -> accounts::ResolveGameManual {
+        accounts::ResolveGameManual {
+            resolver: *resolver,
+            payer: self.ctx.payer.pubkey(),
+            game: game_pda(&game.creator, game.game_id),
+            player_a: game.player_a,
+            player_b: game.player_b,
+            sponsor_a: None,
+            sponsor_b: None,
+            treasury: treasury(),
+            creator: game.creator,
+            escrow: game_escrow(game),
+            global_state: global_state(),
+            player_a_stats: player_stats(&game.player_a),
+            player_b_stats: player_stats(&game.player_b),
+            player_a_achievements: achievements(&game.player_a),
+            player_b_achievements: achievements(&game.player_b),
+            daily_stats: daily_stats(now),
+            player_a_history: game_history(&game.player_a),
+            player_b_history: game_history(&game.player_b),
+            recent_games: pda(&[b"recent_games"]),
+            player_a_referrer: None,
+            player_b_referrer: None,
+            referral_leaderboard: referral_leaderboard(),
+            instructions: None,
+            memo_program: None,
+            receipt: None,
+            hook_program: None,
+            hook_authority: None,
+            promo_vault: Some(promo_vault()),
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: ID,
+        }
+    }
+
+    pub fn resolve_manual_with(accounts: accounts::ResolveGameManual) -> Vec<Instruction> {
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(RESOLVE_CU_LIMIT),
+            Instruction {
+                program_id: ID,
+                accounts: accounts.to_account_metas(None),
+                data: instruction::ResolveGameManual {}.data(),
+            },
+        ]
+    }
+
+    // Signed by the authority. An unjoined game has no player B, so player A fills
+    // the slot.
+    pub fn emergency_recover_ix(&self, game: &Game) -> Instruction {
+        let player_b = if game.player_b == Pubkey::default() {
+            game.player_a
+        } else {
+            game.player_b
+        };
+        Instruction {
+            program_id: ID,
+            accounts: accounts::EmergencyRecover {
+                authority: self.authority.pubkey(),
+                global_state: global_state(),
+                game: game_pda(&game.creator, game.game_id),
+                player_a: game.player_a,
+                player_b,
+                sponsor_a: None,
+                sponsor_b: None,
+                creator: game.creator,
+                escrow: game_escrow(game),
+                admin_log: pda(&[b"admin_log"]),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::EmergencyRecover {}.data(),
+        }
+    }
+
+    pub fn schedule_market_ix(
+        operator: &Pubkey,
+        market_id: u64,
+        cutoff_slot: u64,
+        draw_slot: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ScheduleMarket {
+                operator: *operator,
+                global_state: global_state(),
+                market: market_pda(market_id),
+                market_escrow: market_escrow(market_id),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ScheduleMarket {
+                market_id,
+                cutoff_slot,
+                draw_slot,
+            }
+            .data(),
+        }
+    }
+
+    pub fn back_market_ix(
+        &self,
+        player: &Pubkey,
+        market_id: u64,
+        side: CoinSide,
+        amount: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::BackMarket {
+                player: *player,
+                payer: self.ctx.payer.pubkey(),
+                global_state: global_state(),
+                attestation: None,
+                terms_acceptance: self.terms(player),
+                player_stats: player_stats(player),
+                market: market_pda(market_id),
+                market_escrow: market_escrow(market_id),
+                position: market_position(market_id, player),
+                instructions: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::BackMarket { side, amount }.data(),
+        }
+    }
+
+    pub fn resolve_market_ix(cranker: &Pubkey, market_id: u64, operator: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ResolveMarket {
+                cranker: *cranker,
+                global_state: global_state(),
+                market: market_pda(market_id),
+                market_escrow: market_escrow(market_id),
+                operator: *operator,
+                treasury: treasury(),
+                slot_hashes: sysvar::slot_hashes::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ResolveMarket {}.data(),
+        }
+    }
+
+    pub fn void_market_ix(caller: &Pubkey, market_id: u64, operator: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::VoidMarket {
+                caller: *caller,
+                market: market_pda(market_id),
+                market_escrow: market_escrow(market_id),
+                operator: *operator,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::VoidMarket {}.data(),
+        }
+    }
+
+    pub fn claim_market_ix(player: &Pubkey, market_id: u64, operator: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ClaimMarket {
+                player: *player,
+                global_state: global_state(),
+                market: market_pda(market_id),
+                market_escrow: market_escrow(market_id),
+                position: market_position(market_id, player),
+                player_stats: player_stats(player),
+                operator: *operator,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ClaimMarket {}.data(),
+        }
+    }
+
+    pub fn schedule_lottery_ix(
+        operator: &Pubkey,
+        lottery_id: u64,
+        secret: u64,
+        max_entries: u32,
+        cutoff_slot: u64,
+        draw_slot: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ScheduleLottery {
+                operator: *operator,
+                global_state: global_state(),
+                lottery: lottery_pda(lottery_id),
+                lottery_escrow: lottery_escrow(lottery_id),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ScheduleLottery {
+                lottery_id,
+                secret_commitment: draw_commitment(secret),
+                ticket_price: BET,
+                max_entries,
+                cutoff_slot,
+                draw_slot,
+            }
+            .data(),
+        }
+    }
+
+    // `number` is the lottery's current entry count, the next ticket to be sold
+    pub fn buy_ticket_ix(&self, player: &Pubkey, lottery_id: u64, number: u32) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::BuyTicket {
+                player: *player,
+                payer: self.ctx.payer.pubkey(),
+                global_state: global_state(),
+                attestation: None,
+                terms_acceptance: self.terms(player),
+                player_stats: player_stats(player),
+                lottery: lottery_pda(lottery_id),
+                lottery_escrow: lottery_escrow(lottery_id),
+                ticket: lottery_ticket(lottery_id, number),
+                instructions: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::BuyTicket {}.data(),
+        }
+    }
+
+    pub fn draw_lottery_ix(operator: &Pubkey, lottery_id: u64, secret: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::DrawLottery {
+                operator: *operator,
+                global_state: global_state(),
+                lottery: lottery_pda(lottery_id),
+                lottery_escrow: lottery_escrow(lottery_id),
+                treasury: treasury(),
+                slot_hashes: sysvar::slot_hashes::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::DrawLottery { secret }.data(),
+        }
+    }
+
+    pub fn void_lottery_ix(caller: &Pubkey, lottery_id: u64, operator: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::VoidLottery {
+                caller: *caller,
+                lottery: lottery_pda(lottery_id),
+                lottery_escrow: lottery_escrow(lottery_id),
+                operator: *operator,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::VoidLottery {}.data(),
+        }
+    }
+
+    pub fn claim_lottery_ix(
+        player: &Pubkey,
+        lottery_id: u64,
+        number: u32,
+        operator: &Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ClaimLottery {
+                player: *player,
+                global_state: global_state(),
+                lottery: lottery_pda(lottery_id),
+                lottery_escrow: lottery_escrow(lottery_id),
+                ticket: lottery_ticket(lottery_id, number),
+                player_stats: player_stats(player),
+                operator: *operator,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ClaimLottery {}.data(),
+        }
+    }
+
+    pub fn create_room_ix(&self, creator: &Pubkey, room_id: u64, secret: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::CreateRoom {
+                creator: *creator,
+                payer: self.ctx.payer.pubkey(),
+                global_state: global_state(),
+                attestation: None,
+                terms_acceptance: self.terms(creator),
+                player_stats: player_stats(creator),
+                room: room_pda(creator, room_id),
+                room_escrow: room_escrow(creator, room_id),
+                instructions: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CreateRoom {
+                room_id,
+                stake: BET,
+                champion_commitment: draw_commitment(secret),
+            }
+            .data(),
+        }
+    }
+
+    pub fn challenge_room_ix(
+        &self,
+        challenger: &Pubkey,
+        creator: &Pubkey,
+        room_id: u64,
+        side: CoinSide,
+        secret: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ChallengeRoom {
+                challenger: *challenger,
+                payer: self.ctx.payer.pubkey(),
+                global_state: global_state(),
+                attestation: None,
+                terms_acceptance: self.terms(challenger),
+                player_stats: player_stats(challenger),
+                room: room_pda(creator, room_id),
+                room_escrow: room_escrow(creator, room_id),
+                instructions: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ChallengeRoom {
+                side,
+                commitment: draw_commitment(secret),
+            }
+            .data(),
+        }
+    }
+
+    pub fn settle_bout_ix(
+        champion: &Pubkey,
+        creator: &Pubkey,
+        room_id: u64,
+        challenger: &Pubkey,
+        secret: u64,
+        next_secret: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::SettleBout {
+                champion: *champion,
+                global_state: global_state(),
+                room: room_pda(creator, room_id),
+                room_escrow: room_escrow(creator, room_id),
+                challenger: *challenger,
+                champion_stats: player_stats(champion),
+                challenger_stats: player_stats(challenger),
+                treasury: treasury(),
+                slot_hashes: sysvar::slot_hashes::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::SettleBout {
+                secret,
+                next_commitment: draw_commitment(next_secret),
+            }
+            .data(),
+        }
+    }
+
+    pub fn forfeit_bout_ix(
+        caller: &Pubkey,
+        creator: &Pubkey,
+        room_id: u64,
+        champion: &Pubkey,
+        challenger: &Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ForfeitBout {
+                caller: *caller,
+                global_state: global_state(),
+                room: room_pda(creator, room_id),
+                room_escrow: room_escrow(creator, room_id),
+                challenger: *challenger,
+                champion_stats: player_stats(champion),
+                challenger_stats: player_stats(challenger),
+                treasury: treasury(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ForfeitBout {}.data(),
+        }
+    }
+
+    pub fn retire_champion_ix(champion: &Pubkey, creator: &Pubkey, room_id: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::RetireChampion {
+                champion: *champion,
+                room: room_pda(creator, room_id),
+                room_escrow: room_escrow(creator, room_id),
+                creator: *creator,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::RetireChampion {}.data(),
+        }
+    }
+
+    pub fn enter_match_queue_ix(&self, player: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::EnterMatchQueue {
+                player: *player,
+                payer: self.ctx.payer.pubkey(),
+                global_state: global_state(),
+                attestation: None,
+                terms_acceptance: self.terms(player),
+                player_stats: player_stats(player),
+                match_queue: match_queue_pda(BET),
+                match_queue_escrow: match_queue_escrow(BET),
+                instructions: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::EnterMatchQueue { stake: BET }.data(),
+        }
+    }
+
+    pub fn leave_match_queue_ix(player: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::LeaveMatchQueue {
+                player: *player,
+                global_state: global_state(),
+                match_queue: match_queue_pda(BET),
+                match_queue_escrow: match_queue_escrow(BET),
+                player_stats: player_stats(player),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::LeaveMatchQueue {}.data(),
+        }
+    }
+
+    pub fn start_bracket_ix(operator: &Pubkey, bracket_id: u64, secret: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::StartBracket {
+                operator: *operator,
+                global_state: global_state(),
+                match_queue: match_queue_pda(BET),
+                match_queue_escrow: match_queue_escrow(BET),
+                bracket: bracket_pda(bracket_id),
+                bracket_escrow: bracket_escrow(bracket_id),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::StartBracket {
+                bracket_id,
+                secret_commitment: draw_commitment(secret),
+            }
+            .data(),
+        }
+    }
+
+    pub fn draw_bracket_round_ix(
+        operator: &Pubkey,
+        bracket_id: u64,
+        secret: u64,
+        next_secret: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::DrawBracketRound {
+                operator: *operator,
+                global_state: global_state(),
+                bracket: bracket_pda(bracket_id),
+                bracket_escrow: bracket_escrow(bracket_id),
+                treasury: treasury(),
+                slot_hashes: sysvar::slot_hashes::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::DrawBracketRound {
+                secret,
+                next_commitment: draw_commitment(next_secret),
+            }
+            .data(),
+        }
+    }
+
+    pub fn void_bracket_ix(caller: &Pubkey, bracket_id: u64) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::VoidBracket {
+                caller: *caller,
+                bracket: bracket_pda(bracket_id),
+            }
+            .to_account_metas(None),
+            data: instruction::VoidBracket {}.data(),
+        }
+    }
+
+    pub fn claim_bracket_ix(player: &Pubkey, bracket_id: u64, operator: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ClaimBracket {
+                player: *player,
+                global_state: global_state(),
+                bracket: bracket_pda(bracket_id),
+                bracket_escrow: bracket_escrow(bracket_id),
+                player_stats: player_stats(player),
+                operator: *operator,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ClaimBracket {}.data(),
+        }
+    }
+
+    pub fn subscribe_ix(
+        &self,
+        player: &Pubkey,
+        max_games: u32,
+        opponent: Pubkey,
+        budget: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::Subscribe {
+                player: *player,
+                payer: *player,
+                global_state: global_state(),
+                terms_acceptance: self.terms(player),
+                subscription: subscription(player),
+                subscription_escrow: subscription_escrow(player),
+                player_stats: player_stats(player),
+                player_achievements: achievements(player),
+                player_history: game_history(player),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Subscribe {
+                stake: BET,
+                max_games,
+                opponent,
+                budget,
+            }
+            .data(),
+        }
+    }
+
+    pub fn join_game_for_subscription_ix(
+        &self,
+        cranker: &Pubkey,
+        player: &Pubkey,
+        creator: &Pubkey,
+        game_id: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::JoinGameForSubscription {
+                cranker: *cranker,
+                global_state: global_state(),
+                subscription: subscription(player),
+                subscription_escrow: subscription_escrow(player),
+                attestation: None,
+                terms_acceptance: self.terms(player),
+                player_stats: player_stats(player),
+                game: game_pda(creator, game_id),
+                escrow: self.new_escrow(creator, game_id),
+                instructions: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::JoinGameForSubscription {}.data(),
+        }
+    }
+
+    pub fn cancel_subscription_ix(player: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::CancelSubscription {
+                player: *player,
+                subscription: subscription(player),
+                subscription_escrow: subscription_escrow(player),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CancelSubscription {}.data(),
+        }
+    }
+
+    // Create, join and commit a game, leaving it ready for reveals
+    pub async fn committed_game(
+        &mut self,
+        players: &Players,
+        choice_a: CoinSide,
+        choice_b: CoinSide,
+    ) -> Pubkey {
+        let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+        let game = game_pda(&a.pubkey(), 0);
+
+        self.send(&[self.create_game_ix(&a.pubkey(), 0)], &[&a])
+            .await
+            .unwrap();
+        self.send(&[self.join_game_ix(&b.pubkey(), &a.pubkey(), 0)], &[&b])
+            .await
+            .unwrap();
+        self.send(
+            &[self.commit_ix(&a.pubkey(), game, choice_a, SECRET_A)],
+            &[&a],
+        )
+        .await
+        .unwrap();
+        self.send(
+            &[self.commit_ix(&b.pubkey(), game, choice_b, SECRET_B)],
+            &[&b],
+        )
+        .await
+        .unwrap();
+        game
+    }
+}
+
+// Stand-in for another program composing the flipper: forwards its instruction
+// data to the flipper with every account after the flipper's own
+pub fn relay_process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let ix = Instruction {
+        program_id: ID,
+        accounts: accounts[1..]
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
+    invoke(&ix, accounts)
+}
+
+pub fn hook_authority() -> Pubkey {
+    pda(&[b"hook_authority"])
+}
+
+// Stand-in resolution hook: accepts only an on_game_resolved call signed by the
+// flipper's hook authority and carrying a GameResolved payload for the full game
+// passed with it
+pub fn hook_process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let discriminator = &hash(b"global:on_game_resolved").to_bytes()[..8];
+    let (authority, game) = (&accounts[0], &accounts[1]);
+    if !authority.is_signer || *authority.key != hook_authority() || data[..8] != *discriminator {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let resolved = GameResolved::try_from_slice(&data[8..])?;
+    let game = Game::try_deserialize(&mut &game.try_borrow_data()?[..])?;
+    if game.game_id != resolved.game_id
+        || (resolved.winner != game.player_a && resolved.winner != game.player_b)
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+// Wrap a flipper instruction so the relay program at `relay` makes it as a CPI
+pub fn relayed_ix(relay: Pubkey, ix: Instruction) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(ID, false)];
+    accounts.extend(ix.accounts);
+    Instruction {
+        program_id: relay,
+        accounts,
+        data: ix.data,
+    }
+}
+
+pub const SECRET_A: u64 = 0x5eed_1234_abcd_0001;
+pub const SECRET_B: u64 = 0x5eed_5678_ef01_0002;
+
+// Ed25519 program instruction carrying one self-contained signature, laid out as
+// [header, offsets, pubkey, signature, message]
+pub fn ed25519_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+    const PUBKEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = PUBKEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+
+    let mut data = vec![1, 0];
+    for field in [
+        SIGNATURE_OFFSET,
+        u16::MAX,
+        PUBKEY_OFFSET,
+        u16::MAX,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signer.sign_message(message).as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: solana_sdk::ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+// Ticket a matchmaker signs to let `player` join `game` until `expiry`
+pub fn match_ticket_message(game: &Pubkey, player: &Pubkey, expiry: i64) -> Vec<u8> {
+    let mut message = game.to_bytes().to_vec();
+    message.extend_from_slice(player.as_ref());
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+// Message a player signs to authorize a relayed commitment
+pub fn relayed_commitment_message(
+    game: &Pubkey,
+    choice: CoinSide,
+    secret: u64,
+    expiry: i64,
+) -> Vec<u8> {
+    let mut message = game.to_bytes().to_vec();
+    message.extend_from_slice(&generate_commitment(choice, secret));
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+// Pull the custom error code out of a failed transaction, whichever instruction raised it
+pub fn custom_error(err: BanksClientError) -> u32 {
+    match err.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        other => panic!("expected a custom program error, got {other:?}"),
+    }
+}
@@ -0,0 +1,70 @@
+// Runs against the SBF build, so the units are what each instruction costs on
+// chain, hashing and account (de)serialization included. Run with
+// `cargo test-sbf`; a plain `cargo test` has no .so to load and skips the file.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::{game_pda, Harness, SECRET_A, SECRET_B};
+use fair_coin_flipper::{CoinSide, Game};
+use solana_sdk::signature::Signer;
+
+// Ceilings per instruction. The resolving reveal settles inline with several
+// transfers plus the memo and event CPIs, so it has to stay inside the default
+// 200k per-instruction limit clients get without a compute budget instruction.
+const CREATE_GAME_BUDGET: u64 = 120_000;
+const JOIN_GAME_BUDGET: u64 = 100_000;
+const COMMIT_BUDGET: u64 = 20_000;
+const REVEAL_BUDGET: u64 = 60_000;
+const RESOLVE_BUDGET: u64 = 200_000;
+
+fn assert_within_budget(name: &str, used: u64, budget: u64) {
+    println!("{name}: {used} CU (budget {budget})");
+    assert!(
+        used <= budget,
+        "{name} used {used} compute units, over its budget of {budget}"
+    );
+}
+
+#[tokio::test]
+async fn game_instructions_stay_within_compute_budgets() {
+    let mut harness = Harness::new_sbf().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    let ix = harness.create_game_ix(&a.pubkey(), 0);
+    let used = harness.send_measured(&[ix], &[&a]).await;
+    assert_within_budget("create_game", used, CREATE_GAME_BUDGET);
+
+    let ix = harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0);
+    let used = harness.send_measured(&[ix], &[&b]).await;
+    assert_within_budget("join_game", used, JOIN_GAME_BUDGET);
+
+    let ix = harness.commit_ix(&a.pubkey(), game_key, CoinSide::Heads, SECRET_A);
+    let used = harness.send_measured(&[ix], &[&a]).await;
+    assert_within_budget("make_commitment", used, COMMIT_BUDGET);
+
+    let ix = harness.commit_ix(&b.pubkey(), game_key, CoinSide::Tails, SECRET_B);
+    let used = harness.send_measured(&[ix], &[&b]).await;
+    assert_within_budget("make_commitment (completing)", used, COMMIT_BUDGET);
+
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    let used = harness.send_measured(&ixs, &[&a]).await;
+    assert_within_budget("reveal_choice", used, REVEAL_BUDGET);
+
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&b.pubkey(), &game, now),
+        CoinSide::Tails,
+        SECRET_B,
+    );
+    let used = harness.send_measured(&ixs, &[&b]).await;
+    assert_within_budget("reveal_choice (resolving)", used, RESOLVE_BUDGET);
+}
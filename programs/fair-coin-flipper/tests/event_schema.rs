@@ -0,0 +1,76 @@
+use anchor_lang::{AnchorSerialize, Event};
+use fair_coin_flipper::{
+    decode_event, CoinSide, GameCreated, GameListed, GameResolved, EVENT_VERSION,
+};
+
+fn resolved() -> GameResolved {
+    GameResolved {
+        event_version: EVENT_VERSION,
+        game_id: 9,
+        winner: Default::default(),
+        coin_result: CoinSide::Heads,
+        winner_payout: 1_860_000,
+        house_fee: 140_000,
+        sponsor_repayment: 0,
+        referral_fees: 0,
+        resolved_at: 1_700_000_000,
+        winner_rating: 1216,
+        loser_rating: 1184,
+        rating_delta: 16,
+        resolution_slot: 250_000_000,
+        secret_a: 1234,
+        secret_b: 5678,
+        randomness: [7; 32],
+        seq: 4,
+        resolved_by: Default::default(),
+        golden_flip_odds: 0,
+        golden_flip_bonus: 0,
+    }
+}
+
+#[test]
+fn event_version_is_the_first_field() {
+    let data = resolved().data();
+    assert_eq!(data[8], EVENT_VERSION);
+}
+
+#[test]
+fn appended_fields_do_not_break_older_decoders() {
+    // A newer program version appends fields the decoder doesn't know about
+    let mut data = resolved().data();
+    42u64.serialize(&mut data).unwrap();
+    true.serialize(&mut data).unwrap();
+
+    let decoded: GameResolved = decode_event(&data).unwrap();
+    assert_eq!(decoded.game_id, 9);
+    assert_eq!(decoded.rating_delta, 16);
+}
+
+#[test]
+fn other_events_are_not_decoded() {
+    assert!(decode_event::<GameCreated>(&resolved().data()).is_none());
+    assert!(decode_event::<GameResolved>(&[0; 4]).is_none());
+}
+
+#[test]
+fn game_listed_ends_with_its_join_lock() {
+    // Appended in v6, so a lobby built against v5 reads the same fields as before
+    let listed = GameListed {
+        event_version: EVENT_VERSION,
+        game_id: 3,
+        creator: Default::default(),
+        bet_amount: 100_000_000,
+        tags: 0,
+        listed_at: 1_700_000_000,
+        seq: 1,
+        join_locked_until: 1_700_000_060,
+    };
+    let data = listed.data();
+    assert_eq!(data[data.len() - 8..], 1_700_000_060i64.to_le_bytes());
+
+    let decoded: GameListed = decode_event(&data).unwrap();
+    assert_eq!(
+        (decoded.listed_at, decoded.join_locked_until),
+        (1_700_000_000, 1_700_000_060)
+    );
+}
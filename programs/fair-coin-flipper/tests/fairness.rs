@@ -0,0 +1,216 @@
+use anchor_lang::prelude::Pubkey;
+use fair_coin_flipper::{
+    auto_pick, bracket_round_winners, bracket_size, coin_side, draw_index, draw_randomness,
+    flip_randomness, generate_coin_flip, is_golden_flip, slot_hash_at, CoinSide,
+};
+
+const SAMPLES: u64 = 1_000_000;
+
+// Chi-square critical value for one degree of freedom at p = 0.001
+const CHI_SQUARE_CRITICAL: f64 = 10.828;
+
+// Deterministic xorshift so failures are reproducible
+fn next(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn chi_square(observed: &[u64], expected: &[f64]) -> f64 {
+    observed
+        .iter()
+        .zip(expected)
+        .map(|(&o, &e)| (o as f64 - e).powi(2) / e)
+        .sum()
+}
+
+// 2x2 contingency test: rows are a property of the input, columns are heads/tails
+fn independence(table: [[u64; 2]; 2]) -> f64 {
+    let total: u64 = table.iter().flatten().sum();
+    let mut observed = Vec::new();
+    let mut expected = Vec::new();
+    for row in 0..2 {
+        for col in 0..2 {
+            let row_total = (table[row][0] + table[row][1]) as f64;
+            let col_total = (table[0][col] + table[1][col]) as f64;
+            observed.push(table[row][col]);
+            expected.push(row_total * col_total / total as f64);
+        }
+    }
+    chi_square(&observed, &expected)
+}
+
+struct Tally {
+    heads: u64,
+    by_slot_parity: [[u64; 2]; 2],
+    by_secret_parity: [[u64; 2]; 2],
+}
+
+fn run() -> Tally {
+    let mut state = 0x9E37_79B9_7F4A_7C15;
+    let mut tally = Tally {
+        heads: 0,
+        by_slot_parity: [[0; 2]; 2],
+        by_secret_parity: [[0; 2]; 2],
+    };
+
+    for i in 0..SAMPLES {
+        let secret_a = next(&mut state);
+        let secret_b = next(&mut state);
+        let slot = 200_000_000 + i;
+        let timestamp = 1_700_000_000 + (i / 2) as i64;
+
+        let side = match generate_coin_flip(secret_a, secret_b, slot, timestamp) {
+            CoinSide::Heads => 0,
+            CoinSide::Tails => 1,
+        };
+        if side == 0 {
+            tally.heads += 1;
+        }
+        tally.by_slot_parity[(slot % 2) as usize][side] += 1;
+        tally.by_secret_parity[(secret_a % 2) as usize][side] += 1;
+    }
+    tally
+}
+
+#[test]
+fn coin_flip_distribution_and_independence() {
+    let tally = run();
+
+    let half = SAMPLES as f64 / 2.0;
+    let balance = chi_square(&[tally.heads, SAMPLES - tally.heads], &[half, half]);
+    assert!(
+        balance < CHI_SQUARE_CRITICAL,
+        "heads/tails chi-square {balance}"
+    );
+
+    let slot = independence(tally.by_slot_parity);
+    assert!(slot < CHI_SQUARE_CRITICAL, "slot parity chi-square {slot}");
+
+    let secret = independence(tally.by_secret_parity);
+    assert!(
+        secret < CHI_SQUARE_CRITICAL,
+        "secret parity chi-square {secret}"
+    );
+}
+
+#[test]
+fn published_randomness_rederives_the_flip() {
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..1_000 {
+        let (secret_a, secret_b, slot) = (next(&mut state), next(&mut state), next(&mut state));
+        let timestamp = 1_700_000_000 + (slot % 86_400) as i64;
+        let randomness = flip_randomness(secret_a, secret_b, slot, timestamp);
+        assert!(coin_side(&randomness) == generate_coin_flip(secret_a, secret_b, slot, timestamp));
+    }
+}
+
+// SlotHashes sysvar bytes: a u64 count, then (slot, hash) entries newest first
+fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+    let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+    for &slot in slots {
+        data.extend_from_slice(&slot.to_le_bytes());
+        data.extend_from_slice(&[slot as u8; 32]);
+    }
+    data
+}
+
+#[test]
+fn slot_hash_at_reads_the_sysvar_layout() {
+    // Slot 103 was skipped
+    let data = slot_hashes(&[105, 104, 102, 101, 100]);
+    for slot in [100, 101, 102, 104, 105] {
+        assert_eq!(slot_hash_at(&data, slot), Some([slot as u8; 32]));
+    }
+    assert_eq!(slot_hash_at(&data, 103), None);
+    assert_eq!(slot_hash_at(&data, 99), None);
+    assert_eq!(slot_hash_at(&data, 106), None);
+    assert_eq!(slot_hash_at(&data[..50], 100), None);
+}
+
+#[test]
+fn draw_index_spreads_evenly_over_the_entries() {
+    const ENTRIES: u32 = 7;
+    let mut counts = [0u64; ENTRIES as usize];
+    let mut state = 0x5eed_u64;
+    for _ in 0..SAMPLES {
+        let randomness = draw_randomness(next(&mut state), &[0xab; 32]);
+        counts[draw_index(&randomness, ENTRIES) as usize] += 1;
+    }
+    let expected = [SAMPLES as f64 / ENTRIES as f64; ENTRIES as usize];
+    // 6 degrees of freedom, p = 0.001
+    assert!(chi_square(&counts, &expected) < 22.46);
+}
+
+#[test]
+fn bracket_rounds_read_one_bit_per_match() {
+    let players: Vec<Pubkey> = (0..8).map(|i| Pubkey::new_from_array([i; 32])).collect();
+    let mut randomness = [0; 32];
+    // Matches 1 and 2 go to the second player of their pair
+    randomness[0] = 0b0110;
+    assert_eq!(
+        bracket_round_winners(&players, &randomness),
+        [players[0], players[3], players[5], players[6]]
+    );
+    assert_eq!(
+        bracket_round_winners(&players[..2], &[0xff; 32]),
+        [players[1]]
+    );
+
+    // Brackets seed the largest power of two waiting
+    let sizes: Vec<Option<usize>> = [0, 1, 2, 3, 7, 8, 16].map(bracket_size).to_vec();
+    assert_eq!(
+        sizes,
+        [None, None, Some(2), Some(2), Some(4), Some(8), Some(16)]
+    );
+}
+
+#[test]
+fn golden_flips_hit_one_in_odds_on_either_side() {
+    const ODDS: u32 = 20;
+    // Rows are golden/plain, columns heads/tails
+    let mut table = [[0u64; 2]; 2];
+    let mut state = 0x901d_u64;
+    for _ in 0..SAMPLES {
+        let randomness = flip_randomness(next(&mut state), next(&mut state), 1, 2);
+        let row = if is_golden_flip(&randomness, ODDS) {
+            0
+        } else {
+            1
+        };
+        let col = match coin_side(&randomness) {
+            CoinSide::Heads => 0,
+            CoinSide::Tails => 1,
+        };
+        table[row][col] += 1;
+    }
+    assert!(independence(table) < CHI_SQUARE_CRITICAL);
+
+    let golden = table[0][0] + table[0][1];
+    let expected = SAMPLES as f64 / ODDS as f64;
+    let observed = [golden, SAMPLES - golden];
+    assert!(chi_square(&observed, &[expected, SAMPLES as f64 - expected]) < CHI_SQUARE_CRITICAL);
+
+    assert!(!is_golden_flip(&[0; 32], 0));
+    assert!(is_golden_flip(&[0xff; 32], 1));
+}
+
+#[test]
+fn auto_pick_balances_sides_with_revealable_secrets() {
+    let (game, player) = (
+        Pubkey::new_from_array([1; 32]),
+        Pubkey::new_from_array([2; 32]),
+    );
+    let mut counts = [0u64; 2];
+    for slot in 0..SAMPLES {
+        let (side, secret) = auto_pick(&game, &player, slot);
+        assert!(secret > 1 && secret != u64::MAX);
+        counts[match side {
+            CoinSide::Heads => 0,
+            CoinSide::Tails => 1,
+        }] += 1;
+    }
+    let expected = [SAMPLES as f64 / 2.0; 2];
+    assert!(chi_square(&counts, &expected) < CHI_SQUARE_CRITICAL);
+}
@@ -0,0 +1,2565 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountSerialize, AnchorSerialize, InstructionData, Space, ToAccountMetas};
+use common::{
+    bracket_escrow, bracket_pda, custom_error, delegate, ed25519_ix, escrow_pda, escrow_vault,
+    game_pda, global_state, hook_authority, hook_process_instruction, lottery_escrow, lottery_pda,
+    lottery_ticket, market_escrow, market_pda, match_queue_pda, match_ticket_message, player_stats,
+    promo_vault, receipt_pda, referral_leaderboard, referrer_pda, relay_process_instruction,
+    relayed_commitment_message, relayed_ix, room_escrow, room_pda, subscription_escrow, treasury,
+    Harness, Players, BET, CANCEL_TIMEOUT_SECONDS, EMERGENCY_RECOVERY_MIN_AGE_SECONDS,
+    PLAYER_FUNDING, SECRET_A, SECRET_B,
+};
+use fair_coin_flipper::{
+    accounts, bout_amounts, bps_of, bracket_round_winners, coin_side, draw_commitment, draw_index,
+    draw_randomness, generate_commitment, instruction, Bracket, BracketStatus, CoinSide,
+    ConfigChange, EscrowVault, Game, GameError, GameStatus, GlobalState, LiveGameSummary, Lottery,
+    LotteryTicket, Market, MarketPosition, MarketStatus, MatchQueue, PauseMode,
+    PendingConfigChange, PlayerStats, Receipt, ReferralLeaderboard, Referrer, ResolvedSummary,
+    Room, BRACKET_ROUND_DELAY_SLOTS, DRAW_WINDOW_SLOTS, FLAG_SETTLEMENT_HALTED, ID,
+    REFERRAL_FEE_SHARE_BPS, ROOM_BOUT_DELAY_SLOTS,
+};
+use solana_program_test::processor;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::slot_hashes::SlotHashes;
+use solana_sdk::sysvar;
+
+async fn play_to_resolution(
+    harness: &mut Harness,
+    players: &Players,
+    choice_a: CoinSide,
+    choice_b: CoinSide,
+) -> ResolvedSummary {
+    let game_key = harness.committed_game(players, choice_a, choice_b).await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        choice_a,
+        SECRET_A,
+    );
+    harness.send(&ixs, &[&a]).await.unwrap();
+
+    let treasury_before = harness.lamports(&treasury()).await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&b.pubkey(), &game, now),
+        choice_b,
+        SECRET_B,
+    );
+    harness.send(&ixs, &[&b]).await.unwrap();
+
+    // The game account is shrunk to its summary, keeping only that size's rent
+    let summary: ResolvedSummary = harness.account(&game_key).await;
+    assert_eq!(summary.game_id, game.game_id);
+    assert_eq!(summary.resolved_by, b.pubkey());
+    assert_eq!(
+        harness.data_len(&game_key).await,
+        8 + ResolvedSummary::INIT_SPACE
+    );
+    assert_eq!(
+        harness.lamports(&game_key).await,
+        harness.rent_exempt(8 + ResolvedSummary::INIT_SPACE).await
+    );
+    assert_eq!(harness.lamports(&escrow_pda(&a.pubkey(), 0)).await, 0);
+    assert_eq!(
+        harness.lamports(&treasury()).await - treasury_before,
+        summary.house_fee
+    );
+    summary
+}
+
+#[tokio::test]
+async fn happy_path_pays_the_winner_and_the_treasury() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+
+    let game = play_to_resolution(&mut harness, &players, CoinSide::Heads, CoinSide::Tails).await;
+
+    let winner = game.winner;
+    let expected_winner = if game.coin_result == CoinSide::Heads {
+        players.a.pubkey()
+    } else {
+        players.b.pubkey()
+    };
+    assert_eq!(winner, expected_winner);
+
+    let loser = if winner == players.a.pubkey() {
+        players.b.pubkey()
+    } else {
+        players.a.pubkey()
+    };
+    let winner_stats: PlayerStats = harness.account(&player_stats(&winner)).await;
+    let loser_stats: PlayerStats = harness.account(&player_stats(&loser)).await;
+    assert_eq!((winner_stats.wins, winner_stats.losses), (1, 0));
+    assert_eq!((loser_stats.wins, loser_stats.losses), (0, 1));
+}
+
+#[tokio::test]
+async fn tie_path_still_picks_exactly_one_winner() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+
+    let game = play_to_resolution(&mut harness, &players, CoinSide::Heads, CoinSide::Heads).await;
+
+    let winner = game.winner;
+    assert!(winner == players.a.pubkey() || winner == players.b.pubkey());
+
+    let a_stats: PlayerStats = harness.account(&player_stats(&players.a.pubkey())).await;
+    let b_stats: PlayerStats = harness.account(&player_stats(&players.b.pubkey())).await;
+    assert_eq!(a_stats.wins + b_stats.wins, 1);
+    assert_eq!(a_stats.losses + b_stats.losses, 1);
+}
+
+#[tokio::test]
+async fn receipt_records_the_resolution() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+
+    let game_key = harness
+        .committed_game(&players, CoinSide::Heads, CoinSide::Tails)
+        .await;
+    let game: Game = harness.account(&game_key).await;
+    let receipt = receipt_pda(&game.creator, game.game_id);
+    let now = harness.now().await;
+
+    // Only the resolving reveal may write it
+    let mut accounts = harness.reveal_accounts(&a.pubkey(), &game, now);
+    accounts.receipt = Some(receipt);
+    let ixs = Harness::reveal_ix(accounts, CoinSide::Heads, SECRET_A);
+    let err = harness.send(&ixs, &[&a]).await.unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(GameError::ReceiptBeforeResolution)
+    );
+
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    harness.send(&ixs, &[&a]).await.unwrap();
+    let mut accounts = harness.reveal_accounts(&b.pubkey(), &game, now);
+    accounts.receipt = Some(receipt);
+    let ixs = Harness::reveal_ix(accounts, CoinSide::Tails, SECRET_B);
+    harness.send(&ixs, &[&b]).await.unwrap();
+
+    let summary: ResolvedSummary = harness.account(&game_key).await;
+    let receipt: Receipt = harness.account(&receipt).await;
+    assert_eq!(receipt.game_id, summary.game_id);
+    assert_eq!(receipt.player_a, a.pubkey());
+    assert_eq!(receipt.player_b, b.pubkey());
+    assert_eq!(receipt.winner, summary.winner);
+    assert!(receipt.coin_result == summary.coin_result);
+    assert_eq!(receipt.house_fee, summary.house_fee);
+    assert_eq!(receipt.winner_payout, 2 * BET - summary.house_fee);
+}
+
+#[tokio::test]
+async fn game_summary_reads_live_and_resolved_games() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let game_key = game_pda(&players.a.pubkey(), 0);
+    let summary_ix = Instruction {
+        program_id: ID,
+        accounts: accounts::GetGameSummary { game: game_key }.to_account_metas(None),
+        data: instruction::GetGameSummary {}.data(),
+    };
+
+    let ix = harness.create_game_ix(&players.a.pubkey(), 0);
+    harness.send(&[ix], &[&players.a]).await.unwrap();
+    let live: LiveGameSummary = harness.view(summary_ix.clone()).await;
+    assert!(live.status == GameStatus::WaitingForPlayer);
+    assert_eq!(live.player_a, players.a.pubkey());
+    assert!(!live.committed_a && live.winner.is_none());
+
+    // Settle a separate pair's game and read it back after it was shrunk
+    let players = harness.new_players().await;
+    let resolved =
+        play_to_resolution(&mut harness, &players, CoinSide::Heads, CoinSide::Tails).await;
+    let summary_ix = Instruction {
+        program_id: ID,
+        accounts: accounts::GetGameSummary {
+            game: game_pda(&players.a.pubkey(), 0),
+        }
+        .to_account_metas(None),
+        data: instruction::GetGameSummary {}.data(),
+    };
+    let view: LiveGameSummary = harness.view(summary_ix).await;
+    assert!(view.status == GameStatus::Resolved);
+    assert_eq!(view.game_id, resolved.game_id);
+    assert_eq!(view.bet_amount, resolved.bet_amount);
+    assert_eq!(view.winner, Some(resolved.winner));
+    assert!(view.coin_result == Some(resolved.coin_result));
+    assert!(view.revealed_a && view.revealed_b);
+}
+
+#[tokio::test]
+async fn cooldown_blocks_new_games_until_it_passes() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let a = players.a.insecure_clone();
+
+    let game = play_to_resolution(&mut harness, &players, CoinSide::Heads, CoinSide::Tails).await;
+    let a_stats: PlayerStats = harness.account(&player_stats(&a.pubkey())).await;
+    assert_eq!(a_stats.last_resolved_at, game.resolved_at);
+
+    harness.set_game_cooldown(600).await;
+    let err = harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 1)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::CooldownActive));
+    // Other modes wait out the same cooldown
+    let ix = harness.enter_match_queue_ix(&a.pubkey());
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::CooldownActive));
+
+    harness.advance_clock(600).await;
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 1)], &[&a])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn games_need_the_current_terms_accepted() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let a = players.a.insecure_clone();
+    harness.set_terms_version(1).await;
+
+    let err = harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(AnchorErrorCode::AccountNotInitialized)
+    );
+
+    let err = harness
+        .send(&[harness.accept_terms_ix(&a.pubkey(), 2)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::InvalidTermsVersion));
+    harness
+        .send(&[harness.accept_terms_ix(&a.pubkey(), 1)], &[&a])
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+
+    // A new version has to be accepted again
+    harness.set_terms_version(2).await;
+    let err = harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 1)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::TermsNotAccepted));
+}
+
+#[tokio::test]
+async fn loss_limit_stops_the_loser_for_the_rest_of_the_season() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+
+    let game = play_to_resolution(&mut harness, &players, CoinSide::Heads, CoinSide::Tails).await;
+    let (winner, loser) = if game.winner == players.a.pubkey() {
+        (players.a.insecure_clone(), players.b.insecure_clone())
+    } else {
+        (players.b.insecure_clone(), players.a.insecure_clone())
+    };
+    for player in [&winner, &loser] {
+        harness
+            .send(
+                &[harness.set_loss_limit_ix(&player.pubkey(), BET)],
+                &[player],
+            )
+            .await
+            .unwrap();
+    }
+
+    // Player A already created game 0
+    let next_id = |player: &Keypair| u64::from(player.pubkey() == players.a.pubkey());
+
+    // The loser is down a full stake, the winner is up
+    let err = harness
+        .send(
+            &[harness.create_game_ix(&loser.pubkey(), next_id(&loser))],
+            &[&loser],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::LossLimitReached));
+    harness
+        .send(
+            &[harness.create_game_ix(&winner.pubkey(), next_id(&winner))],
+            &[&winner],
+        )
+        .await
+        .unwrap();
+
+    // Having played this season, the limit can't be lifted until the next one
+    let err = harness
+        .send(&[harness.set_loss_limit_ix(&loser.pubkey(), 0)], &[&loser])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::LossLimitLocked));
+}
+
+#[tokio::test]
+async fn queued_stake_counts_toward_the_loss_limit_until_refunded() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let a = players.a.insecure_clone();
+    harness
+        .send(&[harness.set_loss_limit_ix(&a.pubkey(), BET)], &[&a])
+        .await
+        .unwrap();
+
+    let ix = harness.enter_match_queue_ix(&a.pubkey());
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let err = harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::LossLimitReached));
+
+    let ix = Harness::leave_match_queue_ix(&a.pubkey());
+    harness.send(&[ix], &[&a]).await.unwrap();
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn frozen_player_cannot_create_games_until_the_freeze_ends() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let a = players.a.insecure_clone();
+
+    let now = harness.now().await;
+    harness
+        .send(&[harness.freeze_self_ix(&a.pubkey(), now + 3_600)], &[&a])
+        .await
+        .unwrap();
+    let stats: PlayerStats = harness.account(&player_stats(&a.pubkey())).await;
+    assert!(stats.is_frozen(now));
+
+    let err = harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::AccountFrozen));
+
+    // An active freeze can't be cut short
+    let err = harness
+        .send(&[harness.freeze_self_ix(&a.pubkey(), now + 60)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::InvalidFreezePeriod));
+
+    harness.advance_clock(3_600).await;
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn drawn_market_pays_the_winning_side_its_share() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let operator = Keypair::new();
+    let cranker = Keypair::new();
+    for wallet in [&operator, &cranker] {
+        harness.fund(&wallet.pubkey(), PLAYER_FUNDING).await;
+    }
+    harness.set_operator(&operator.pubkey()).await;
+
+    let slot = harness.slot().await;
+    let draw_slot = slot + 20;
+    let ix = Harness::schedule_market_ix(&operator.pubkey(), 1, slot + 10, draw_slot);
+    harness.send(&[ix], &[&operator]).await.unwrap();
+    let sides = [(&a, CoinSide::Heads, BET), (&b, CoinSide::Tails, 2 * BET)];
+    for (player, side, amount) in sides {
+        let ix = harness.back_market_ix(&player.pubkey(), 1, side, amount);
+        harness.send(&[ix], &[player]).await.unwrap();
+    }
+
+    // Anyone draws it once the draw slot's hash exists, with no secret involved
+    harness.ctx.warp_to_slot(draw_slot).unwrap();
+    harness.warp_slots(1).await;
+    let treasury_before = harness.lamports(&treasury()).await;
+    let ix = Harness::resolve_market_ix(&cranker.pubkey(), 1, &operator.pubkey());
+    harness.send(&[ix], &[&cranker]).await.unwrap();
+
+    let market: Market = harness.account(&market_pda(1)).await;
+    let slot_hashes: SlotHashes = harness.ctx.banks_client.get_sysvar().await.unwrap();
+    let slot_hash = slot_hashes.get(&draw_slot).unwrap().to_bytes();
+    assert_eq!(market.randomness, draw_randomness(1, &slot_hash));
+    assert!(market.result == coin_side(&market.randomness));
+    assert!(market.status == MarketStatus::Resolved);
+    let house_fee = bps_of(3 * BET, market.house_fee_bps).unwrap();
+    assert_eq!(market.payout_pool, 3 * BET - house_fee);
+    assert_eq!(
+        harness.lamports(&treasury()).await - treasury_before,
+        house_fee
+    );
+
+    // The winning side takes the whole pool, being the only position on it; the
+    // losing side gets its position rent back
+    let position_rent = harness.rent_exempt(8 + MarketPosition::INIT_SPACE).await;
+    let operator_before = harness.lamports(&operator.pubkey()).await;
+    for (player, side, _) in sides {
+        let before = harness.lamports(&player.pubkey()).await;
+        let ix = Harness::claim_market_ix(&player.pubkey(), 1, &operator.pubkey());
+        harness.send(&[ix], &[player]).await.unwrap();
+        let payout = if side == market.result {
+            market.payout_pool
+        } else {
+            0
+        };
+        assert_eq!(
+            harness.lamports(&player.pubkey()).await - before,
+            payout + position_rent
+        );
+    }
+    assert_eq!(harness.lamports(&market_escrow(1)).await, 0);
+    assert_eq!(
+        harness.lamports(&operator.pubkey()).await - operator_before,
+        harness.rent_exempt(0).await
+    );
+}
+
+#[tokio::test]
+async fn undrawn_market_is_voided_and_refunded() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let operator = Keypair::new();
+    harness.fund(&operator.pubkey(), PLAYER_FUNDING).await;
+    harness.set_operator(&operator.pubkey()).await;
+
+    let slot = harness.slot().await;
+    let ix = Harness::schedule_market_ix(&operator.pubkey(), 1, slot + 10, slot + 20);
+    harness.send(&[ix], &[&operator]).await.unwrap();
+    for (player, side, amount) in [(&a, CoinSide::Heads, BET), (&b, CoinSide::Tails, 2 * BET)] {
+        let ix = harness.back_market_ix(&player.pubkey(), 1, side, amount);
+        harness.send(&[ix], &[player]).await.unwrap();
+    }
+    let market: Market = harness.account(&market_pda(1)).await;
+    assert_eq!((market.heads_total, market.tails_total), (BET, 2 * BET));
+    assert_eq!(market.positions, 2);
+
+    // Nothing can be claimed or voided while the market can still be drawn
+    let err = harness
+        .send(
+            &[Harness::claim_market_ix(&a.pubkey(), 1, &operator.pubkey())],
+            &[&a],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotYetDrawn));
+    let err = harness
+        .send(
+            &[Harness::void_market_ix(&a.pubkey(), 1, &operator.pubkey())],
+            &[&a],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotVoidable));
+    let err = harness
+        .send(
+            &[Harness::resolve_market_ix(
+                &a.pubkey(),
+                1,
+                &operator.pubkey(),
+            )],
+            &[&a],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotDrawable));
+
+    // No one draws it
+    harness.warp_slots(20 + DRAW_WINDOW_SLOTS + 1).await;
+    let err = harness
+        .send(
+            &[harness.back_market_ix(&a.pubkey(), 1, CoinSide::Heads, BET)],
+            &[&a],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::MarketClosed));
+    harness
+        .send(
+            &[Harness::void_market_ix(&a.pubkey(), 1, &operator.pubkey())],
+            &[&a],
+        )
+        .await
+        .unwrap();
+
+    // Everyone gets their stakes and position rent back, and the operator the reserve
+    let position_rent = harness.rent_exempt(8 + MarketPosition::INIT_SPACE).await;
+    let operator_before = harness.lamports(&operator.pubkey()).await;
+    for (player, staked) in [(&a, BET), (&b, 2 * BET)] {
+        let before = harness.lamports(&player.pubkey()).await;
+        let ix = Harness::claim_market_ix(&player.pubkey(), 1, &operator.pubkey());
+        harness.send(&[ix], &[player]).await.unwrap();
+        assert_eq!(
+            harness.lamports(&player.pubkey()).await - before,
+            staked + position_rent
+        );
+    }
+    assert_eq!(harness.lamports(&market_escrow(1)).await, 0);
+    assert_eq!(
+        harness.lamports(&operator.pubkey()).await - operator_before,
+        harness.rent_exempt(0).await
+    );
+}
+
+#[tokio::test]
+async fn vault_mode_keeps_stakes_in_the_shared_vault() {
+    let mut harness = Harness::new().await;
+    harness.set_escrow_mode(true).await;
+    let players = harness.new_players().await;
+    let vault_reserve = harness.lamports(&escrow_vault()).await;
+
+    let game_key = harness
+        .committed_game(&players, CoinSide::Heads, CoinSide::Tails)
+        .await;
+    let vault: EscrowVault = harness.account(&escrow_vault()).await;
+    assert_eq!(vault.liabilities, 2 * BET);
+    assert_eq!(
+        harness.lamports(&escrow_vault()).await,
+        vault_reserve + 2 * BET
+    );
+
+    // Settlement pays out of the vault and clears the game's ledger entry
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    for (player, choice, secret) in [
+        (&players.a, CoinSide::Heads, SECRET_A),
+        (&players.b, CoinSide::Tails, SECRET_B),
+    ] {
+        let ixs = Harness::reveal_ix(
+            harness.reveal_accounts(&player.pubkey(), &game, now),
+            choice,
+            secret,
+        );
+        harness.send(&ixs, &[player]).await.unwrap();
+    }
+
+    let vault: EscrowVault = harness.account(&escrow_vault()).await;
+    assert_eq!(vault.liabilities, 0);
+    assert_eq!(harness.lamports(&escrow_vault()).await, vault_reserve);
+    assert_eq!(
+        harness.lamports(&escrow_pda(&players.a.pubkey(), 0)).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn stalled_game_refunds_both_players_after_timeout() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0)], &[&b])
+        .await
+        .unwrap();
+    harness
+        .send(
+            &[harness.commit_ix(&a.pubkey(), game_key, CoinSide::Heads, SECRET_A)],
+            &[&a],
+        )
+        .await
+        .unwrap();
+
+    // Player B never commits; cancelling before the deadline is refused
+    let game: Game = harness.account(&game_key).await;
+    let err = harness
+        .send(&[harness.cancel_ix(&a.pubkey(), &game)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::TooEarlyToCancel));
+
+    harness.advance_clock(CANCEL_TIMEOUT_SECONDS + 1).await;
+
+    let b_before = harness.lamports(&b.pubkey()).await;
+    let treasury_before = harness.lamports(&treasury()).await;
+    harness
+        .send(&[harness.cancel_ix(&a.pubkey(), &game)], &[&a])
+        .await
+        .unwrap();
+
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.status() == GameStatus::Cancelled);
+    assert_eq!(harness.lamports(&escrow_pda(&a.pubkey(), 0)).await, 0);
+
+    let (fee, refund) =
+        fair_coin_flipper::cancellation_amounts(BET, game.cancellation_fee_bps).unwrap();
+    assert_eq!(harness.lamports(&b.pubkey()).await - b_before, refund);
+    assert_eq!(
+        harness.lamports(&treasury()).await - treasury_before,
+        2 * fee
+    );
+
+    // Each player's stats count the fee they lost
+    for player in [&a, &b] {
+        let stats: PlayerStats = harness.account(&player_stats(&player.pubkey())).await;
+        assert_eq!(stats.total_wagered, fee);
+        assert_eq!(stats.season.wagered, fee);
+    }
+}
+
+#[tokio::test]
+async fn auto_pick_plays_the_joiner_instead_of_refunding() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    let mut ix = harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0);
+    ix.data = instruction::JoinGame {
+        repay_sponsor: false,
+        auto_pick: true,
+    }
+    .data();
+    harness.send(&[ix], &[&b]).await.unwrap();
+    harness
+        .send(
+            &[harness.commit_ix(&a.pubkey(), game_key, CoinSide::Heads, SECRET_A)],
+            &[&a],
+        )
+        .await
+        .unwrap();
+
+    // Nothing is picked before the deadline, and after it there's no refund
+    let ix = Harness::auto_pick_side_ix(&a.pubkey(), game_key);
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NoAutoPickDue));
+    harness.advance_clock(CANCEL_TIMEOUT_SECONDS + 1).await;
+    let game: Game = harness.account(&game_key).await;
+    let err = harness
+        .send(&[harness.cancel_ix(&a.pubkey(), &game)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::AutoPickPending));
+
+    // The commit phase closes without B, and nothing is drawn until A reveals
+    let ix = Harness::auto_pick_side_ix(&a.pubkey(), game_key);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.status() == GameStatus::CommitmentsReady);
+    assert_eq!(game.commitment_b, [0; 32]);
+    harness.warp_slots(1).await;
+    let ix = Harness::auto_pick_side_ix(&b.pubkey(), game_key);
+    let err = harness.send(&[ix], &[&b]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NoAutoPickDue));
+
+    let now = harness.now().await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    harness.send(&ixs, &[&a]).await.unwrap();
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.status() == GameStatus::RevealingPhase);
+    assert!(game.choice_b().is_none());
+
+    // A can't choose the slot B's side is drawn from
+    let ix = Harness::auto_pick_side_ix(&a.pubkey(), game_key);
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::AutoPickByPlayerA));
+
+    let ix = Harness::auto_pick_side_ix(&b.pubkey(), game_key);
+    harness.send(&[ix], &[&b]).await.unwrap();
+    let game: Game = harness.account(&game_key).await;
+    let choice_b = game.choice_b().unwrap();
+    assert_eq!(
+        game.commitment_b,
+        generate_commitment(choice_b, game.secret_b)
+    );
+
+    // With both sides in, the game resolves like any other
+    let now = harness.now().await;
+    let ixs = harness.resolve_manual_ix(&a.pubkey(), &game, now);
+    harness.send(&ixs, &[&a]).await.unwrap();
+    let summary: ResolvedSummary = harness.account(&game_key).await;
+    assert!(summary.winner == a.pubkey() || summary.winner == b.pubkey());
+}
+
+#[tokio::test]
+async fn reveal_after_deadline_is_rejected() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let a = players.a.insecure_clone();
+
+    let game_key = harness
+        .committed_game(&players, CoinSide::Heads, CoinSide::Tails)
+        .await;
+    harness.advance_clock(CANCEL_TIMEOUT_SECONDS + 1).await;
+
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    let err = harness.send(&ixs, &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::GameExpired));
+}
+
+#[tokio::test]
+async fn substituted_payout_accounts_are_rejected() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let attacker = Keypair::new().pubkey();
+
+    let game_key = harness
+        .committed_game(&players, CoinSide::Heads, CoinSide::Tails)
+        .await;
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    harness.send(&ixs, &[&a]).await.unwrap();
+
+    // The final reveal pays out, so every payout destination is pinned to the game
+    let mut swapped_a = harness.reveal_accounts(&b.pubkey(), &game, now);
+    swapped_a.player_a = attacker;
+    let err = harness
+        .send(
+            &Harness::reveal_ix(swapped_a, CoinSide::Tails, SECRET_B),
+            &[&b],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(AnchorErrorCode::ConstraintAddress)
+    );
+
+    let mut swapped_b = harness.reveal_accounts(&b.pubkey(), &game, now);
+    swapped_b.player_b = attacker;
+    let err = harness
+        .send(
+            &Harness::reveal_ix(swapped_b, CoinSide::Tails, SECRET_B),
+            &[&b],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(AnchorErrorCode::ConstraintAddress)
+    );
+
+    let mut swapped_treasury = harness.reveal_accounts(&b.pubkey(), &game, now);
+    swapped_treasury.treasury = attacker;
+    let err = harness
+        .send(
+            &Harness::reveal_ix(swapped_treasury, CoinSide::Tails, SECRET_B),
+            &[&b],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(AnchorErrorCode::ConstraintSeeds)
+    );
+
+    let mut swapped_creator = harness.reveal_accounts(&b.pubkey(), &game, now);
+    swapped_creator.creator = attacker;
+    let err = harness
+        .send(
+            &Harness::reveal_ix(swapped_creator, CoinSide::Tails, SECRET_B),
+            &[&b],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(AnchorErrorCode::ConstraintAddress)
+    );
+
+    // Untouched, the same reveal goes through
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&b.pubkey(), &game, now),
+        CoinSide::Tails,
+        SECRET_B,
+    );
+    harness.send(&ixs, &[&b]).await.unwrap();
+}
+
+#[tokio::test]
+async fn manual_resolution_pays_only_the_stored_players() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let attacker = Keypair::new().pubkey();
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    // An auto-picked joiner leaves a game with both sides in and nothing settled
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    let mut ix = harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0);
+    ix.data = instruction::JoinGame {
+        repay_sponsor: false,
+        auto_pick: true,
+    }
+    .data();
+    harness.send(&[ix], &[&b]).await.unwrap();
+    harness
+        .send(
+            &[harness.commit_ix(&a.pubkey(), game_key, CoinSide::Heads, SECRET_A)],
+            &[&a],
+        )
+        .await
+        .unwrap();
+    harness.advance_clock(CANCEL_TIMEOUT_SECONDS + 1).await;
+    let ix = Harness::auto_pick_side_ix(&a.pubkey(), game_key);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    harness.send(&ixs, &[&a]).await.unwrap();
+    harness.warp_slots(1).await;
+    let ix = Harness::auto_pick_side_ix(&b.pubkey(), game_key);
+    harness.send(&[ix], &[&b]).await.unwrap();
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+
+    // The resolver can't point either payout at another wallet
+    let mut swapped_a = harness.resolve_manual_accounts(&a.pubkey(), &game, now);
+    swapped_a.player_a = attacker;
+    let mut swapped_b = harness.resolve_manual_accounts(&a.pubkey(), &game, now);
+    swapped_b.player_b = attacker;
+    for swapped in [swapped_a, swapped_b] {
+        let ixs = Harness::resolve_manual_with(swapped);
+        let err = harness.send(&ixs, &[&a]).await.unwrap_err();
+        assert_eq!(
+            custom_error(err),
+            u32::from(AnchorErrorCode::ConstraintAddress)
+        );
+    }
+    assert_eq!(harness.lamports(&attacker).await, 0);
+
+    // Untouched, the pot goes to whichever stored player won; a creator who wins
+    // also gets the escrow's reserve back
+    let (a_before, b_before) = (
+        harness.lamports(&a.pubkey()).await,
+        harness.lamports(&b.pubkey()).await,
+    );
+    let ixs = harness.resolve_manual_ix(&a.pubkey(), &game, now);
+    harness.send(&ixs, &[&a]).await.unwrap();
+    let summary: ResolvedSummary = harness.account(&game_key).await;
+    let (winner_before, winner) = if summary.winner == a.pubkey() {
+        (a_before, &a)
+    } else {
+        (b_before, &b)
+    };
+    assert_eq!(summary.winner, winner.pubkey());
+    let (_, _, winner_payout) =
+        fair_coin_flipper::settlement_amounts(BET, game.house_fee_bps).unwrap();
+    assert!(harness.lamports(&winner.pubkey()).await >= winner_before + winner_payout);
+}
+
+#[tokio::test]
+async fn outsiders_cannot_commit_or_reveal() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let outsider = Keypair::new();
+    harness.fund(&outsider.pubkey(), PLAYER_FUNDING).await;
+
+    let game_key = game_pda(&a.pubkey(), 0);
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0)], &[&b])
+        .await
+        .unwrap();
+
+    let err = harness
+        .send(
+            &[harness.commit_ix(&outsider.pubkey(), game_key, CoinSide::Heads, SECRET_A)],
+            &[&outsider],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotAPlayer));
+
+    harness
+        .send(
+            &[harness.commit_ix(&a.pubkey(), game_key, CoinSide::Heads, SECRET_A)],
+            &[&a],
+        )
+        .await
+        .unwrap();
+    harness
+        .send(
+            &[harness.commit_ix(&b.pubkey(), game_key, CoinSide::Tails, SECRET_B)],
+            &[&b],
+        )
+        .await
+        .unwrap();
+
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&outsider.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    let err = harness.send(&ixs, &[&outsider]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotAPlayer));
+}
+
+#[tokio::test]
+async fn ownership_offer_is_accepted_only_by_its_target() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let c = Keypair::new();
+    harness.fund(&c.pubkey(), PLAYER_FUNDING).await;
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    let ix = Harness::offer_game_ownership_ix(&b.pubkey(), game_key, &c.pubkey());
+    let err = harness.send(&[ix], &[&b]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotGameOwner));
+    let ix = Harness::offer_game_ownership_ix(&a.pubkey(), game_key, &c.pubkey());
+    harness.send(&[ix], &[&a]).await.unwrap();
+
+    // Someone else can't take the offer
+    let ix = harness.accept_game_ownership_ix(&b.pubkey(), game_key);
+    let err = harness.send(&[ix], &[&b]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotPendingOwner));
+
+    // The new owner passes the same player checks as a joiner
+    let now = harness.now().await;
+    harness
+        .send(&[harness.freeze_self_ix(&c.pubkey(), now + 3_600)], &[&c])
+        .await
+        .unwrap();
+    let ix = harness.accept_game_ownership_ix(&c.pubkey(), game_key);
+    let err = harness.send(&[ix], &[&c]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::AccountFrozen));
+
+    harness.advance_clock(3_600).await;
+    let ix = harness.accept_game_ownership_ix(&c.pubkey(), game_key);
+    harness.send(&[ix], &[&c]).await.unwrap();
+    let game: Game = harness.account(&game_key).await;
+    assert_eq!(game.player_a, c.pubkey());
+    assert_eq!(game.pending_owner, Pubkey::default());
+}
+
+#[tokio::test]
+async fn join_lock_admits_only_allowlisted_joiners_until_it_ends() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let sniper = Keypair::new();
+    harness.fund(&sniper.pubkey(), PLAYER_FUNDING).await;
+
+    for game_id in 0..2 {
+        let ix = Instruction {
+            program_id: ID,
+            accounts: harness
+                .create_game_accounts(&a.pubkey(), game_id)
+                .to_account_metas(None),
+            data: instruction::CreateGame {
+                game_id,
+                bet_amount: BET,
+                allowed_joiners: vec![b.pubkey()],
+                join_lock_seconds: 60,
+                tags: 0,
+                repay_sponsor: false,
+            }
+            .data(),
+        };
+        harness.send(&[ix], &[&a]).await.unwrap();
+    }
+
+    let ix = harness.join_game_ix(&sniper.pubkey(), &a.pubkey(), 0);
+    let err = harness.send(&[ix], &[&sniper]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::JoinLocked));
+
+    let ix = harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0);
+    harness.send(&[ix], &[&b]).await.unwrap();
+    let game: Game = harness.account(&game_pda(&a.pubkey(), 0)).await;
+    assert_eq!(game.player_b, b.pubkey());
+
+    // Once the lock runs out the game is open to anyone
+    harness.advance_clock(60).await;
+    let ix = harness.join_game_ix(&sniper.pubkey(), &a.pubkey(), 1);
+    harness.send(&[ix], &[&sniper]).await.unwrap();
+    let game: Game = harness.account(&game_pda(&a.pubkey(), 1)).await;
+    assert_eq!(game.player_b, sniper.pubkey());
+}
+
+#[tokio::test]
+async fn allowlist_without_a_join_lock_admits_anyone() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let sniper = Keypair::new();
+    harness.fund(&sniper.pubkey(), PLAYER_FUNDING).await;
+
+    // The allowlist only binds while the lock runs, so a game created without one
+    // is public from the start and goes on the lobby feed like any other
+    let ix = Instruction {
+        program_id: ID,
+        accounts: harness
+            .create_game_accounts(&a.pubkey(), 0)
+            .to_account_metas(None),
+        data: instruction::CreateGame {
+            game_id: 0,
+            bet_amount: BET,
+            allowed_joiners: vec![b.pubkey()],
+            join_lock_seconds: 0,
+            tags: 0,
+            repay_sponsor: false,
+        }
+        .data(),
+    };
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let game: Game = harness.account(&game_pda(&a.pubkey(), 0)).await;
+    assert!(game.join_locked_until <= harness.now().await);
+
+    let ix = harness.join_game_ix(&sniper.pubkey(), &a.pubkey(), 0);
+    harness.send(&[ix], &[&sniper]).await.unwrap();
+    let game: Game = harness.account(&game_pda(&a.pubkey(), 0)).await;
+    assert_eq!(game.player_b, sniper.pubkey());
+}
+
+#[tokio::test]
+async fn cpi_guard_admits_only_allowlisted_calling_programs() {
+    let relay = Pubkey::new_unique();
+    let mut harness = Harness::new_with(|program_test| {
+        program_test.add_program("relay", relay, processor!(relay_process_instruction))
+    })
+    .await;
+    let players = harness.new_players().await;
+    let a = players.a.insecure_clone();
+    harness
+        .admin(instruction::SetCpiGuard {
+            enabled: true,
+            allowed_callers: vec![],
+        })
+        .await
+        .unwrap();
+
+    // Top-level calls never need the allowlist
+    let ix = harness.create_game_ix(&a.pubkey(), 0);
+    harness.send(&[ix], &[&a]).await.unwrap();
+
+    let mut accounts = harness.create_game_accounts(&a.pubkey(), 1);
+    accounts.instructions = Some(sysvar::instructions::ID);
+    let ix = relayed_ix(relay, Harness::create_game_with(accounts, 1));
+    let err = harness
+        .send(std::slice::from_ref(&ix), &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::UntrustedCaller));
+
+    harness
+        .admin(instruction::SetCpiGuard {
+            enabled: true,
+            allowed_callers: vec![relay],
+        })
+        .await
+        .unwrap();
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let game: Game = harness.account(&game_pda(&a.pubkey(), 1)).await;
+    assert_eq!(game.player_a, a.pubkey());
+}
+
+#[tokio::test]
+async fn lite_join_defers_player_accounts_to_first_commitment() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    harness
+        .send(
+            &[harness.join_game_lite_ix(&b.pubkey(), &a.pubkey(), 0)],
+            &[&b],
+        )
+        .await
+        .unwrap();
+
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.status() == GameStatus::PlayersReady);
+    assert_eq!(game.player_b, b.pubkey());
+    assert_eq!(harness.lamports(&player_stats(&b.pubkey())).await, 0);
+
+    harness
+        .send(
+            &[harness.commit_ix(&b.pubkey(), game_key, CoinSide::Tails, SECRET_B)],
+            &[&b],
+        )
+        .await
+        .unwrap();
+
+    let b_stats: PlayerStats = harness.account(&player_stats(&b.pubkey())).await;
+    assert_eq!(b_stats.player, b.pubkey());
+}
+
+#[tokio::test]
+async fn cancel_charges_a_lite_joiner_who_never_committed() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    harness
+        .send(
+            &[harness.join_game_lite_ix(&b.pubkey(), &a.pubkey(), 0)],
+            &[&b],
+        )
+        .await
+        .unwrap();
+    harness.advance_clock(CANCEL_TIMEOUT_SECONDS + 1).await;
+    let game: Game = harness.account(&game_key).await;
+
+    // Leaving out B's stats would skip B's fee and loss limit
+    let mut ix = harness.cancel_ix(&a.pubkey(), &game);
+    let b_stats = player_stats(&b.pubkey());
+    for meta in ix.accounts.iter_mut().filter(|meta| meta.pubkey == b_stats) {
+        *meta = AccountMeta::new_readonly(ID, false);
+    }
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::MissingPlayerStats));
+
+    // With them, cancelling creates the stats B never made and records the fee
+    harness
+        .send(&[harness.cancel_ix(&a.pubkey(), &game)], &[&a])
+        .await
+        .unwrap();
+    let stats: PlayerStats = harness.account(&b_stats).await;
+    assert_eq!(stats.player, b.pubkey());
+    assert_eq!(
+        stats.total_wagered,
+        bps_of(BET, game.cancellation_fee_bps).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn cancel_refunds_only_the_stored_players() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let attacker = Keypair::new().pubkey();
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0)], &[&b])
+        .await
+        .unwrap();
+    harness.advance_clock(CANCEL_TIMEOUT_SECONDS + 1).await;
+    let game: Game = harness.account(&game_key).await;
+
+    // Neither refund can be sent anywhere but to the player who staked it
+    let mut swapped_a = game;
+    swapped_a.player_a = attacker;
+    let err = harness
+        .send(&[harness.cancel_ix(&a.pubkey(), &swapped_a)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(AnchorErrorCode::ConstraintAddress)
+    );
+    let mut swapped_b = game;
+    swapped_b.player_b = attacker;
+    let err = harness
+        .send(&[harness.cancel_ix(&a.pubkey(), &swapped_b)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(AnchorErrorCode::ConstraintRaw));
+    assert_eq!(harness.lamports(&attacker).await, 0);
+
+    // Untouched, the same cancel refunds both players
+    let b_before = harness.lamports(&b.pubkey()).await;
+    harness
+        .send(&[harness.cancel_ix(&a.pubkey(), &game)], &[&a])
+        .await
+        .unwrap();
+    let (_, refund) =
+        fair_coin_flipper::cancellation_amounts(BET, game.cancellation_fee_bps).unwrap();
+    assert_eq!(harness.lamports(&b.pubkey()).await - b_before, refund);
+}
+
+#[tokio::test]
+async fn lite_join_still_honours_a_returning_joiners_freeze() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+
+    let now = harness.now().await;
+    harness
+        .send(&[harness.freeze_self_ix(&b.pubkey(), now + 3_600)], &[&b])
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+
+    let err = harness
+        .send(
+            &[harness.join_game_lite_ix(&b.pubkey(), &a.pubkey(), 0)],
+            &[&b],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::AccountFrozen));
+
+    harness.advance_clock(3_600).await;
+    harness
+        .send(
+            &[harness.join_game_lite_ix(&b.pubkey(), &a.pubkey(), 0)],
+            &[&b],
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn session_key_commits_and_reveals_within_its_limits() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let session = Keypair::new();
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0)], &[&b])
+        .await
+        .unwrap();
+    let now = harness.now().await;
+    harness
+        .send(
+            &[harness.authorize_delegate_ix(&b.pubkey(), &session.pubkey(), now + 600, BET)],
+            &[&b],
+        )
+        .await
+        .unwrap();
+
+    let delegated_commit = harness.delegated_commit_ix(
+        &session.pubkey(),
+        &b.pubkey(),
+        game_key,
+        CoinSide::Tails,
+        SECRET_B,
+    );
+    harness
+        .send(&[delegated_commit], &[&session])
+        .await
+        .unwrap();
+    harness
+        .send(
+            &[harness.commit_ix(&a.pubkey(), game_key, CoinSide::Heads, SECRET_A)],
+            &[&a],
+        )
+        .await
+        .unwrap();
+
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.status() == GameStatus::CommitmentsReady);
+
+    // Without the delegate account the session key is just an outsider
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&session.pubkey(), &game, now),
+        CoinSide::Tails,
+        SECRET_B,
+    );
+    let err = harness.send(&ixs, &[&session]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotAPlayer));
+
+    let mut delegated_reveal = harness.reveal_accounts(&session.pubkey(), &game, now);
+    delegated_reveal.delegate = Some(delegate(&b.pubkey()));
+    harness
+        .send(
+            &Harness::reveal_ix(delegated_reveal, CoinSide::Tails, SECRET_B),
+            &[&session],
+        )
+        .await
+        .unwrap();
+
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.choice_b() == Some(CoinSide::Tails));
+
+    // Once expired the session key can no longer act
+    harness.advance_clock(601).await;
+    let now = harness.now().await;
+    let mut expired = harness.reveal_accounts(&session.pubkey(), &game, now);
+    expired.delegate = Some(delegate(&b.pubkey()));
+    let err = harness
+        .send(
+            &Harness::reveal_ix(expired, CoinSide::Tails, SECRET_B),
+            &[&session],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::DelegateExpired));
+}
+
+#[tokio::test]
+async fn relayer_submits_a_commitment_signed_by_the_player() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.join_game_ix(&b.pubkey(), &a.pubkey(), 0)], &[&b])
+        .await
+        .unwrap();
+    let expiry = harness.now().await + 60;
+
+    // Without the player's signature the relayer can't commit for them
+    let relayed = harness.commit_relayed_ix(&b.pubkey(), game_key, CoinSide::Tails, SECRET_B);
+    let err = harness
+        .send(std::slice::from_ref(&relayed), &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(GameError::MissingPlayerSignature)
+    );
+
+    // A signature over a different commitment doesn't authorize this one
+    let other = relayed_commitment_message(&game_key, CoinSide::Heads, SECRET_B, expiry);
+    let err = harness
+        .send(&[ed25519_ix(&b, &other), relayed.clone()], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(GameError::InvalidPlayerSignature)
+    );
+
+    // Player B only signs the message; the harness payer relays and pays
+    let message = relayed_commitment_message(&game_key, CoinSide::Tails, SECRET_B, expiry);
+    harness
+        .send(&[ed25519_ix(&b, &message), relayed], &[])
+        .await
+        .unwrap();
+
+    let game: Game = harness.account(&game_key).await;
+    assert_eq!(
+        game.commitment_b,
+        generate_commitment(CoinSide::Tails, SECRET_B)
+    );
+    let b_stats: PlayerStats = harness.account(&player_stats(&b.pubkey())).await;
+    assert_eq!(b_stats.player, b.pubkey());
+}
+
+#[tokio::test]
+async fn matchmaker_ticket_gates_joins_until_it_expires() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let (matchmaker, forger) = (Keypair::new(), Keypair::new());
+    let game_key = game_pda(&a.pubkey(), 0);
+
+    harness
+        .admin(instruction::SetMatchmaker {
+            matchmaker: matchmaker.pubkey(),
+        })
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    let mut accounts = harness.join_game_accounts(&b.pubkey(), &a.pubkey(), 0);
+    accounts.instructions = Some(sysvar::instructions::ID);
+    let join = Harness::join_game_with(accounts);
+    let expiry = harness.now().await + 60;
+    let ticket = match_ticket_message(&game_key, &b.pubkey(), expiry);
+
+    let err = harness
+        .send(std::slice::from_ref(&join), &[&b])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::MissingMatchTicket));
+    let ix = harness.join_game_lite_ix(&b.pubkey(), &a.pubkey(), 0);
+    let err = harness.send(&[ix], &[&b]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::MissingMatchTicket));
+
+    // Signed by anyone but the matchmaker, or for another player, it's refused
+    let err = harness
+        .send(&[ed25519_ix(&forger, &ticket), join.clone()], &[&b])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::InvalidMatchTicket));
+    let other = match_ticket_message(&game_key, &a.pubkey(), expiry);
+    let err = harness
+        .send(&[ed25519_ix(&matchmaker, &other), join.clone()], &[&b])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::InvalidMatchTicket));
+
+    harness.advance_clock(61).await;
+    let err = harness
+        .send(&[ed25519_ix(&matchmaker, &ticket), join.clone()], &[&b])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::MatchTicketExpired));
+
+    let expiry = harness.now().await + 60;
+    let ticket = match_ticket_message(&game_key, &b.pubkey(), expiry);
+    harness
+        .send(&[ed25519_ix(&matchmaker, &ticket), join], &[&b])
+        .await
+        .unwrap();
+    let game: Game = harness.account(&game_key).await;
+    assert_eq!(game.player_b, b.pubkey());
+}
+
+#[tokio::test]
+async fn batch_creates_games_at_different_stakes() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let stakes = [BET, 2 * BET, 3 * BET];
+
+    // Game accounts must follow the nonce; starting at the wrong id is rejected
+    let ix = harness.create_games_batch_ix(&a.pubkey(), 1, &stakes);
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::InvalidGameId));
+
+    let balance_before = harness.lamports(&a.pubkey()).await;
+    let ix = harness.create_games_batch_ix(&a.pubkey(), 0, &stakes);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    assert_eq!(
+        balance_before - harness.lamports(&a.pubkey()).await,
+        stakes.iter().sum::<u64>()
+    );
+
+    for (game_id, stake) in stakes.iter().enumerate() {
+        let game: Game = harness
+            .account(&game_pda(&a.pubkey(), game_id as u64))
+            .await;
+        assert_eq!(game.game_id, game_id as u64);
+        assert_eq!(game.bet_amount, *stake);
+        assert!(game.status() == GameStatus::WaitingForPlayer);
+    }
+
+    // Batched games are ordinary games; the next create_game continues the ids
+    harness
+        .send(&[harness.join_game_ix(&b.pubkey(), &a.pubkey(), 1)], &[&b])
+        .await
+        .unwrap();
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 3)], &[&a])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn drawn_lottery_pays_the_winning_ticket_the_prize() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let operator = Keypair::new();
+    harness.fund(&operator.pubkey(), PLAYER_FUNDING).await;
+    harness.set_operator(&operator.pubkey()).await;
+
+    let slot = harness.slot().await;
+    let draw_slot = slot + 20;
+    let ix = Harness::schedule_lottery_ix(&operator.pubkey(), 1, SECRET_A, 3, slot + 10, draw_slot);
+    harness.send(&[ix], &[&operator]).await.unwrap();
+    let tickets = [(0, &a), (1, &b), (2, &a)];
+    for (number, player) in tickets {
+        let ix = harness.buy_ticket_ix(&player.pubkey(), 1, number);
+        harness.send(&[ix], &[player]).await.unwrap();
+    }
+
+    // Only the committed secret draws it
+    harness.ctx.warp_to_slot(draw_slot).unwrap();
+    harness.warp_slots(1).await;
+    let err = harness
+        .send(
+            &[Harness::draw_lottery_ix(&operator.pubkey(), 1, SECRET_B)],
+            &[&operator],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::InvalidDrawSecret));
+    let treasury_before = harness.lamports(&treasury()).await;
+    harness
+        .send(
+            &[Harness::draw_lottery_ix(&operator.pubkey(), 1, SECRET_A)],
+            &[&operator],
+        )
+        .await
+        .unwrap();
+
+    let lottery: Lottery = harness.account(&lottery_pda(1)).await;
+    let slot_hashes: SlotHashes = harness.ctx.banks_client.get_sysvar().await.unwrap();
+    let slot_hash = slot_hashes.get(&draw_slot).unwrap().to_bytes();
+    let randomness = draw_randomness(SECRET_A, &slot_hash);
+    assert_eq!(lottery.randomness, randomness);
+    assert_eq!(lottery.winning_ticket, draw_index(&randomness, 3));
+    let house_fee = bps_of(3 * BET, lottery.house_fee_bps).unwrap();
+    assert_eq!(lottery.prize, 3 * BET - house_fee);
+    assert_eq!(
+        harness.lamports(&treasury()).await - treasury_before,
+        house_fee
+    );
+
+    // The winning ticket takes the prize; every ticket gets its rent back
+    let ticket_rent = harness.rent_exempt(8 + LotteryTicket::INIT_SPACE).await;
+    let operator_before = harness.lamports(&operator.pubkey()).await;
+    for (number, player) in tickets {
+        let before = harness.lamports(&player.pubkey()).await;
+        let ix = Harness::claim_lottery_ix(&player.pubkey(), 1, number, &operator.pubkey());
+        harness.send(&[ix], &[player]).await.unwrap();
+        let prize = if number == lottery.winning_ticket {
+            lottery.prize
+        } else {
+            0
+        };
+        assert_eq!(
+            harness.lamports(&player.pubkey()).await - before,
+            prize + ticket_rent
+        );
+    }
+    assert_eq!(harness.lamports(&lottery_escrow(1)).await, 0);
+    assert_eq!(
+        harness.lamports(&operator.pubkey()).await - operator_before,
+        harness.rent_exempt(0).await
+    );
+}
+
+#[tokio::test]
+async fn sold_out_lottery_voids_and_refunds_every_ticket() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let operator = Keypair::new();
+    harness.fund(&operator.pubkey(), PLAYER_FUNDING).await;
+    harness.set_operator(&operator.pubkey()).await;
+
+    let slot = harness.slot().await;
+    let ix = Harness::schedule_lottery_ix(&operator.pubkey(), 1, SECRET_A, 2, slot + 10, slot + 20);
+    harness.send(&[ix], &[&operator]).await.unwrap();
+    for (number, player) in [(0, &a), (1, &b)] {
+        let ix = harness.buy_ticket_ix(&player.pubkey(), 1, number);
+        harness.send(&[ix], &[player]).await.unwrap();
+    }
+    let lottery: Lottery = harness.account(&lottery_pda(1)).await;
+    assert_eq!(lottery.entries, 2);
+    let ticket: LotteryTicket = harness.account(&lottery_ticket(1, 1)).await;
+    assert_eq!((ticket.player, ticket.number), (b.pubkey(), 1));
+
+    // Sold out well before the cutoff
+    let err = harness
+        .send(&[harness.buy_ticket_ix(&a.pubkey(), 1, 2)], &[&a])
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::LotteryClosed));
+
+    // The operator never draws it
+    harness.warp_slots(20 + DRAW_WINDOW_SLOTS + 1).await;
+    harness
+        .send(
+            &[Harness::void_lottery_ix(&a.pubkey(), 1, &operator.pubkey())],
+            &[&a],
+        )
+        .await
+        .unwrap();
+
+    // Only a ticket's holder can claim it
+    let err = harness
+        .send(
+            &[Harness::claim_lottery_ix(
+                &a.pubkey(),
+                1,
+                1,
+                &operator.pubkey(),
+            )],
+            &[&a],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::Unauthorized));
+
+    // Every ticket refunds its price and rent, and the operator gets the reserve
+    let ticket_rent = harness.rent_exempt(8 + LotteryTicket::INIT_SPACE).await;
+    let operator_before = harness.lamports(&operator.pubkey()).await;
+    for (number, player) in [(0, &a), (1, &b)] {
+        let before = harness.lamports(&player.pubkey()).await;
+        let ix = Harness::claim_lottery_ix(&player.pubkey(), 1, number, &operator.pubkey());
+        harness.send(&[ix], &[player]).await.unwrap();
+        assert_eq!(
+            harness.lamports(&player.pubkey()).await - before,
+            BET + ticket_rent
+        );
+    }
+    assert_eq!(harness.lamports(&lottery_escrow(1)).await, 0);
+    assert_eq!(
+        harness.lamports(&operator.pubkey()).await - operator_before,
+        harness.rent_exempt(0).await
+    );
+}
+
+#[tokio::test]
+async fn silent_champion_forfeits_the_room() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let creator = a.pubkey();
+
+    let ix = harness.create_room_ix(&creator, 1, SECRET_A);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let ix = harness.challenge_room_ix(&creator, &creator, 1, CoinSide::Heads, SECRET_A);
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::AlreadyInRoom));
+    let ix = harness.challenge_room_ix(&b.pubkey(), &creator, 1, CoinSide::Tails, SECRET_B);
+    harness.send(&[ix], &[&b]).await.unwrap();
+    let room: Room = harness.account(&room_pda(&creator, 1)).await;
+    assert_eq!((room.champion, room.queue_len), (creator, 1));
+
+    // The bout can't be drawn, or forfeited, before its draw slot
+    let ix = Harness::settle_bout_ix(&creator, &creator, 1, &b.pubkey(), SECRET_A, SECRET_B);
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotDrawable));
+    let ix = Harness::forfeit_bout_ix(&b.pubkey(), &creator, 1, &creator, &b.pubkey());
+    let err = harness.send(&[ix], &[&b]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::BoutStillDrawable));
+
+    // The champion never reveals, so the challenger takes the room and the creator's
+    // stake less the fee
+    harness
+        .warp_slots(ROOM_BOUT_DELAY_SLOTS + DRAW_WINDOW_SLOTS + 1)
+        .await;
+    let before = harness.lamports(&b.pubkey()).await;
+    let ix = Harness::forfeit_bout_ix(&b.pubkey(), &creator, 1, &creator, &b.pubkey());
+    harness.send(&[ix], &[&b]).await.unwrap();
+    let (_, winnings) = bout_amounts(BET, room.house_fee_bps).unwrap();
+    assert_eq!(harness.lamports(&b.pubkey()).await - before, winnings);
+    let room: Room = harness.account(&room_pda(&creator, 1)).await;
+    assert_eq!((room.champion, room.streak), (b.pubkey(), 1));
+    assert_eq!(
+        (room.winnings, room.bouts, room.queue_len),
+        (winnings, 1, 0)
+    );
+
+    // The bout counts on both players' stats like a game
+    let season_id = harness
+        .account::<GlobalState>(&global_state())
+        .await
+        .current_season_id;
+    let loser: PlayerStats = harness.account(&player_stats(&creator)).await;
+    let winner: PlayerStats = harness.account(&player_stats(&b.pubkey())).await;
+    assert_eq!((loser.losses, winner.wins), (1, 1));
+    assert_eq!(loser.season_net_loss(season_id), BET);
+    assert_eq!(winner.season.winnings, BET + winnings);
+
+    // Only the champion can retire; they get their stake back and the creator the rent
+    let ix = Harness::retire_champion_ix(&creator, &creator, 1);
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::Unauthorized));
+    let before = harness.lamports(&b.pubkey()).await;
+    let ix = Harness::retire_champion_ix(&b.pubkey(), &creator, 1);
+    harness.send(&[ix], &[&b]).await.unwrap();
+    assert_eq!(harness.lamports(&b.pubkey()).await - before, BET);
+    assert_eq!(harness.lamports(&room_escrow(&creator, 1)).await, 0);
+    assert_eq!(harness.lamports(&room_pda(&creator, 1)).await, 0);
+}
+
+#[tokio::test]
+async fn settled_bout_pays_the_winner_and_the_champion_retires_with_their_stake() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let creator = a.pubkey();
+
+    let ix = harness.create_room_ix(&creator, 1, SECRET_A);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let ix = harness.challenge_room_ix(&b.pubkey(), &creator, 1, CoinSide::Heads, SECRET_B);
+    harness.send(&[ix], &[&b]).await.unwrap();
+    let room: Room = harness.account(&room_pda(&creator, 1)).await;
+    let draw_slot = room.bout_draw_slot;
+    harness.ctx.warp_to_slot(draw_slot).unwrap();
+    harness.warp_slots(1).await;
+
+    // Only the champion reveals, and only the secret they committed to
+    let ix = Harness::settle_bout_ix(&b.pubkey(), &creator, 1, &b.pubkey(), SECRET_B, SECRET_B);
+    let err = harness.send(&[ix], &[&b]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::Unauthorized));
+    let ix = Harness::settle_bout_ix(&creator, &creator, 1, &b.pubkey(), SECRET_B, SECRET_B);
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::InvalidDrawSecret));
+
+    let slot_hashes: SlotHashes = harness.ctx.banks_client.get_sysvar().await.unwrap();
+    let slot_hash = slot_hashes.get(&draw_slot).unwrap().to_bytes();
+    let challenger_won = coin_side(&draw_randomness(SECRET_A, &slot_hash)) == CoinSide::Heads;
+    let (winner, next_secret) = if challenger_won {
+        (b.insecure_clone(), SECRET_B)
+    } else {
+        (a.insecure_clone(), SECRET_A + 1)
+    };
+    let (house_fee, winnings) = bout_amounts(BET, room.house_fee_bps).unwrap();
+    let winner_before = harness.lamports(&winner.pubkey()).await;
+    let treasury_before = harness.lamports(&treasury()).await;
+    let escrow_before = harness.lamports(&room_escrow(&creator, 1)).await;
+
+    let ix = Harness::settle_bout_ix(&creator, &creator, 1, &b.pubkey(), SECRET_A, SECRET_A + 1);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    assert_eq!(
+        harness.lamports(&winner.pubkey()).await - winner_before,
+        winnings
+    );
+    assert_eq!(
+        harness.lamports(&treasury()).await - treasury_before,
+        house_fee
+    );
+    assert_eq!(
+        escrow_before - harness.lamports(&room_escrow(&creator, 1)).await,
+        winnings + house_fee
+    );
+    let room: Room = harness.account(&room_pda(&creator, 1)).await;
+    assert_eq!((room.champion, room.streak), (winner.pubkey(), 1));
+    assert_eq!(room.champion_commitment, draw_commitment(next_secret));
+    assert_eq!(
+        (
+            room.winnings,
+            room.bouts,
+            room.queue_len,
+            room.bout_draw_slot
+        ),
+        (winnings, 1, 0, 0)
+    );
+
+    // The champion, whoever won, leaves with their stake; the creator also gets
+    // the room's rent and the escrow's reserve back
+    let closed = harness.lamports(&room_pda(&creator, 1)).await
+        + harness.lamports(&room_escrow(&creator, 1)).await
+        - BET;
+    let refund = if challenger_won { BET } else { BET + closed };
+    let before = harness.lamports(&winner.pubkey()).await;
+    let ix = Harness::retire_champion_ix(&winner.pubkey(), &creator, 1);
+    harness.send(&[ix], &[&winner]).await.unwrap();
+    assert_eq!(harness.lamports(&winner.pubkey()).await - before, refund);
+    assert_eq!(harness.lamports(&room_pda(&creator, 1)).await, 0);
+}
+
+#[tokio::test]
+async fn bracket_snapshots_the_queue_and_refunds_when_undrawn() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let c = Keypair::new();
+    harness.fund(&c.pubkey(), PLAYER_FUNDING).await;
+    let operator = Keypair::new();
+    harness.fund(&operator.pubkey(), PLAYER_FUNDING).await;
+    harness.set_operator(&operator.pubkey()).await;
+
+    for player in [&a, &b, &c] {
+        let ix = harness.enter_match_queue_ix(&player.pubkey());
+        harness.send(&[ix], &[player]).await.unwrap();
+    }
+
+    // Three waiting seed a bracket of two; the third keeps their place
+    let ix = Harness::start_bracket_ix(&operator.pubkey(), 1, SECRET_A);
+    harness.send(&[ix], &[&operator]).await.unwrap();
+    let bracket: Bracket = harness.account(&bracket_pda(1)).await;
+    assert_eq!(bracket.player_count, 2);
+    assert_eq!(bracket.players[..2], [a.pubkey(), b.pubkey()]);
+    let queue: MatchQueue = harness.account(&match_queue_pda(BET)).await;
+    assert_eq!((queue.len, queue.players[0]), (1, c.pubkey()));
+    let ix = Harness::start_bracket_ix(&operator.pubkey(), 2, SECRET_A);
+    let err = harness.send(&[ix], &[&operator]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::BracketTooSmall));
+
+    let before = harness.lamports(&c.pubkey()).await;
+    let ix = Harness::leave_match_queue_ix(&c.pubkey());
+    harness.send(&[ix], &[&c]).await.unwrap();
+    assert_eq!(harness.lamports(&c.pubkey()).await - before, BET);
+
+    // The first round can't be drawn before its slot, and the operator never draws it
+    let ix = Harness::draw_bracket_round_ix(&operator.pubkey(), 1, SECRET_A, SECRET_B);
+    let err = harness.send(&[ix], &[&operator]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NotDrawable));
+    harness
+        .warp_slots(BRACKET_ROUND_DELAY_SLOTS + DRAW_WINDOW_SLOTS + 1)
+        .await;
+    let ix = Harness::void_bracket_ix(&c.pubkey(), 1);
+    harness.send(&[ix], &[&c]).await.unwrap();
+    let bracket: Bracket = harness.account(&bracket_pda(1)).await;
+    assert!(bracket.status == BracketStatus::Voided);
+
+    // Every player gets their stake back once, and the operator the reserve
+    let operator_before = harness.lamports(&operator.pubkey()).await;
+    let before = harness.lamports(&a.pubkey()).await;
+    let ix = Harness::claim_bracket_ix(&a.pubkey(), 1, &operator.pubkey());
+    harness.send(&[ix], &[&a]).await.unwrap();
+    assert_eq!(harness.lamports(&a.pubkey()).await - before, BET);
+    // Fresh blockhash, so the repeat isn't dropped as a duplicate transaction
+    harness.warp_slots(1).await;
+    let ix = Harness::claim_bracket_ix(&a.pubkey(), 1, &operator.pubkey());
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NothingToClaim));
+    let ix = Harness::claim_bracket_ix(&b.pubkey(), 1, &operator.pubkey());
+    harness.send(&[ix], &[&b]).await.unwrap();
+    assert_eq!(harness.lamports(&bracket_escrow(1)).await, 0);
+    assert_eq!(
+        harness.lamports(&operator.pubkey()).await - operator_before,
+        harness.rent_exempt(0).await
+    );
+}
+
+#[tokio::test]
+async fn bracket_draws_each_round_and_pays_the_champion() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (c, d) = (Keypair::new(), Keypair::new());
+    harness.fund(&c.pubkey(), PLAYER_FUNDING).await;
+    harness.fund(&d.pubkey(), PLAYER_FUNDING).await;
+    let operator = Keypair::new();
+    harness.fund(&operator.pubkey(), PLAYER_FUNDING).await;
+    harness.set_operator(&operator.pubkey()).await;
+    let entrants = [players.a.insecure_clone(), players.b.insecure_clone(), c, d];
+
+    for player in &entrants {
+        let ix = harness.enter_match_queue_ix(&player.pubkey());
+        harness.send(&[ix], &[player]).await.unwrap();
+    }
+    let ix = Harness::start_bracket_ix(&operator.pubkey(), 1, SECRET_A);
+    harness.send(&[ix], &[&operator]).await.unwrap();
+
+    // Each round draws from its own slot with the secret committed for it, and the
+    // winners of one round meet in the next
+    let mut bracket: Bracket = harness.account(&bracket_pda(1)).await;
+    assert_eq!(bracket.player_count, 4);
+    let treasury_before = harness.lamports(&treasury()).await;
+    for (round, (secret, next_secret)) in [(SECRET_A, SECRET_B), (SECRET_B, SECRET_A)]
+        .into_iter()
+        .enumerate()
+    {
+        let draw_slot = bracket.round_draw_slot;
+        harness.ctx.warp_to_slot(draw_slot).unwrap();
+        harness.warp_slots(1).await;
+        let ix = Harness::draw_bracket_round_ix(&operator.pubkey(), 1, next_secret, secret);
+        let err = harness.send(&[ix], &[&operator]).await.unwrap_err();
+        assert_eq!(custom_error(err), u32::from(GameError::InvalidDrawSecret));
+
+        let slot_hashes: SlotHashes = harness.ctx.banks_client.get_sysvar().await.unwrap();
+        let slot_hash = slot_hashes.get(&draw_slot).unwrap().to_bytes();
+        let winners = bracket_round_winners(
+            &bracket.alive[..bracket.alive_count as usize],
+            &draw_randomness(secret, &slot_hash),
+        );
+        let ix = Harness::draw_bracket_round_ix(&operator.pubkey(), 1, secret, next_secret);
+        harness.send(&[ix], &[&operator]).await.unwrap();
+        bracket = harness.account(&bracket_pda(1)).await;
+        assert_eq!(bracket.round as usize, round + 1);
+        assert_eq!(bracket.alive[..bracket.alive_count as usize], winners[..]);
+        assert_eq!(bracket.secret_commitment, draw_commitment(next_secret));
+    }
+
+    // Two rounds leave one player, who wins the pot less the house fee
+    let pot = BET * 4;
+    let house_fee = bps_of(pot, bracket.house_fee_bps).unwrap();
+    assert!(bracket.status == BracketStatus::Finished);
+    assert_eq!(bracket.champion, bracket.alive[0]);
+    assert_eq!(bracket.prize, pot - house_fee);
+    assert_eq!(
+        harness.lamports(&treasury()).await - treasury_before,
+        house_fee
+    );
+    let ix = Harness::draw_bracket_round_ix(&operator.pubkey(), 1, SECRET_A, SECRET_B);
+    let err = harness.send(&[ix], &[&operator]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::AlreadySettled));
+
+    // Only the champion has anything to claim, and claiming closes the escrow
+    let champion = entrants
+        .iter()
+        .find(|player| player.pubkey() == bracket.champion)
+        .unwrap();
+    let loser = entrants
+        .iter()
+        .find(|player| player.pubkey() != bracket.champion)
+        .unwrap();
+    let ix = Harness::claim_bracket_ix(&loser.pubkey(), 1, &operator.pubkey());
+    let err = harness.send(&[ix], &[loser]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::NothingToClaim));
+    let operator_before = harness.lamports(&operator.pubkey()).await;
+    let before = harness.lamports(&champion.pubkey()).await;
+    let ix = Harness::claim_bracket_ix(&champion.pubkey(), 1, &operator.pubkey());
+    harness.send(&[ix], &[champion]).await.unwrap();
+    assert_eq!(
+        harness.lamports(&champion.pubkey()).await - before,
+        bracket.prize
+    );
+    assert_eq!(harness.lamports(&bracket_escrow(1)).await, 0);
+    assert_eq!(
+        harness.lamports(&operator.pubkey()).await - operator_before,
+        harness.rent_exempt(0).await
+    );
+    let stats: PlayerStats = harness.account(&player_stats(&champion.pubkey())).await;
+    assert_eq!(stats.season.winnings, bracket.prize);
+}
+
+#[tokio::test]
+async fn golden_flip_pays_the_winner_from_the_promo_vault() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    // Every flip is golden and pays half the pot on top
+    harness.set_golden_flip(1, 5_000).await;
+    let ix = Harness::fund_promo_vault_ix(&a.pubkey(), 3 * BET);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    assert_eq!(
+        harness.lamports(&promo_vault()).await,
+        harness.rent_exempt(0).await + 3 * BET
+    );
+
+    let game_key = harness
+        .committed_game(&players, CoinSide::Heads, CoinSide::Tails)
+        .await;
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    harness.send(&ixs, &[&a]).await.unwrap();
+
+    // The vault is required whatever the outcome once golden flips are on
+    let mut accounts = harness.reveal_accounts(&b.pubkey(), &game, now);
+    accounts.promo_vault = None;
+    let ixs = Harness::reveal_ix(accounts, CoinSide::Tails, SECRET_B);
+    let err = harness.send(&ixs, &[&b]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::MissingPromoVault));
+
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&b.pubkey(), &game, now),
+        CoinSide::Tails,
+        SECRET_B,
+    );
+    harness.send(&ixs, &[&b]).await.unwrap();
+    assert_eq!(
+        harness.lamports(&promo_vault()).await,
+        harness.rent_exempt(0).await + 2 * BET
+    );
+}
+
+#[tokio::test]
+async fn subscription_joins_matching_games_until_its_budget_runs_out() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let escrow = subscription_escrow(&b.pubkey());
+
+    // B only wants games against A, and funds a game and a half
+    let ix = harness.subscribe_ix(&b.pubkey(), 3, a.pubkey(), BET + BET / 2);
+    harness.send(&[ix], &[&b]).await.unwrap();
+    let reserve = harness.rent_exempt(0).await;
+    assert_eq!(harness.lamports(&escrow).await, reserve + BET + BET / 2);
+
+    // Anyone can crank the join; B plays their own side through a session key
+    let session = Keypair::new();
+    let now = harness.now().await;
+    let ix = harness.authorize_delegate_ix(&b.pubkey(), &session.pubkey(), now + 600, BET);
+    harness.send(&[ix], &[&b]).await.unwrap();
+    for game_id in 0..2 {
+        harness
+            .send(&[harness.create_game_ix(&a.pubkey(), game_id)], &[&a])
+            .await
+            .unwrap();
+    }
+    let ix = harness.join_game_for_subscription_ix(&a.pubkey(), &b.pubkey(), &a.pubkey(), 0);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let game: Game = harness.account(&game_pda(&a.pubkey(), 0)).await;
+    assert_eq!(game.player_b, b.pubkey());
+    assert_eq!(game.sponsor_b, escrow);
+    assert!(!game.auto_pick_b());
+    assert_eq!(harness.lamports(&escrow).await, reserve + BET / 2);
+
+    let game_key = game_pda(&a.pubkey(), 0);
+    let ix = harness.delegated_commit_ix(
+        &session.pubkey(),
+        &b.pubkey(),
+        game_key,
+        CoinSide::Tails,
+        SECRET_B,
+    );
+    harness.send(&[ix], &[&session]).await.unwrap();
+    let game: Game = harness.account(&game_key).await;
+    assert_eq!(
+        game.commitment_b,
+        generate_commitment(CoinSide::Tails, SECRET_B)
+    );
+
+    let ix = harness.join_game_for_subscription_ix(&a.pubkey(), &b.pubkey(), &a.pubkey(), 1);
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(GameError::SubscriptionExhausted)
+    );
+
+    // Cancelling returns what's left, reserve included
+    let before = harness.lamports(&b.pubkey()).await;
+    let ix = Harness::cancel_subscription_ix(&b.pubkey());
+    harness.send(&[ix], &[&b]).await.unwrap();
+    assert!(harness.lamports(&b.pubkey()).await > before + BET / 2);
+    assert_eq!(harness.lamports(&escrow).await, 0);
+}
+
+#[tokio::test]
+async fn referrer_earns_a_share_of_the_fee_on_their_players_stake() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let referrer = Keypair::new();
+    for wallet in [&referrer, &a] {
+        let ix = harness.register_referrer_ix(&wallet.pubkey());
+        harness.send(&[ix], &[wallet]).await.unwrap();
+    }
+
+    // A referring themselves is ignored; B picks up the referrer on joining
+    let mut accounts = harness.create_game_accounts(&a.pubkey(), 0);
+    accounts.referrer = Some(referrer_pda(&a.pubkey()));
+    let ix = Harness::create_game_with(accounts, 0);
+    harness.send(&[ix], &[&a]).await.unwrap();
+    let mut accounts = harness.join_game_accounts(&b.pubkey(), &a.pubkey(), 0);
+    accounts.referrer = Some(referrer_pda(&referrer.pubkey()));
+    harness
+        .send(&[Harness::join_game_with(accounts)], &[&b])
+        .await
+        .unwrap();
+
+    let stats: PlayerStats = harness.account(&player_stats(&a.pubkey())).await;
+    assert_eq!(stats.referrer, Pubkey::default());
+    let stats: PlayerStats = harness.account(&player_stats(&b.pubkey())).await;
+    assert_eq!(stats.referrer, referrer.pubkey());
+    let account: Referrer = harness.account(&referrer_pda(&referrer.pubkey())).await;
+    assert_eq!(account.referred_players, 1);
+
+    let game_key = game_pda(&a.pubkey(), 0);
+    for (player, choice, secret) in [
+        (&a, CoinSide::Heads, SECRET_A),
+        (&b, CoinSide::Tails, SECRET_B),
+    ] {
+        let ix = harness.commit_ix(&player.pubkey(), game_key, choice, secret);
+        harness.send(&[ix], &[player]).await.unwrap();
+    }
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    harness.send(&ixs, &[&a]).await.unwrap();
+
+    // Resolution needs the Referrer account of any referred player
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&b.pubkey(), &game, now),
+        CoinSide::Tails,
+        SECRET_B,
+    );
+    let err = harness.send(&ixs, &[&b]).await.unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(GameError::MissingReferrerAccount)
+    );
+
+    let before = harness.lamports(&referrer_pda(&referrer.pubkey())).await;
+    let mut accounts = harness.reveal_accounts(&b.pubkey(), &game, now);
+    accounts.player_b_referrer = Some(referrer_pda(&referrer.pubkey()));
+    let ixs = Harness::reveal_ix(accounts, CoinSide::Tails, SECRET_B);
+    harness.send(&ixs, &[&b]).await.unwrap();
+
+    let summary: ResolvedSummary = harness.account(&game_key).await;
+    let share = bps_of(summary.house_fee / 2, REFERRAL_FEE_SHARE_BPS).unwrap();
+    assert!(share > 0);
+    assert_eq!(
+        harness.lamports(&referrer_pda(&referrer.pubkey())).await,
+        before + share
+    );
+    let account: Referrer = harness.account(&referrer_pda(&referrer.pubkey())).await;
+    assert_eq!(account.fees_earned, share);
+    assert_eq!(account.referred_volume, BET);
+    let leaderboard: ReferralLeaderboard = harness.account(&referral_leaderboard()).await;
+    assert_eq!(leaderboard.entries[0].referrer, referrer.pubkey());
+}
+
+#[tokio::test]
+async fn unjoined_game_is_recovered_once_old_enough() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let a = players.a.insecure_clone();
+    let game_key = game_pda(&a.pubkey(), 0);
+    harness
+        .send(&[harness.create_game_ix(&a.pubkey(), 0)], &[&a])
+        .await
+        .unwrap();
+    let authority = harness.authority.insecure_clone();
+
+    let game: Game = harness.account(&game_key).await;
+    let ix = harness.emergency_recover_ix(&game);
+    let err = harness.send(&[ix], &[&authority]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::TooEarlyForRecovery));
+
+    // Only the authority may recover
+    harness
+        .advance_clock(EMERGENCY_RECOVERY_MIN_AGE_SECONDS)
+        .await;
+    let mut ix = harness.emergency_recover_ix(&game);
+    ix.accounts[0].pubkey = a.pubkey();
+    let err = harness.send(&[ix], &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::Unauthorized));
+
+    let before = harness.lamports(&a.pubkey()).await;
+    let ix = harness.emergency_recover_ix(&game);
+    harness.send(&[ix], &[&authority]).await.unwrap();
+    let reserve = harness.rent_exempt(0).await;
+    assert_eq!(harness.lamports(&a.pubkey()).await, before + BET + reserve);
+    assert_eq!(harness.lamports(&escrow_pda(&a.pubkey(), 0)).await, 0);
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.status() == GameStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn configured_hook_is_notified_of_every_resolution() {
+    let hook = Pubkey::new_unique();
+    let mut harness = Harness::new_with(|program_test| {
+        program_test.add_program("hook", hook, processor!(hook_process_instruction))
+    })
+    .await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    harness
+        .admin(instruction::SetHookProgram { hook_program: hook })
+        .await
+        .unwrap();
+    let game_key = harness
+        .committed_game(&players, CoinSide::Heads, CoinSide::Tails)
+        .await;
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&a.pubkey(), &game, now),
+        CoinSide::Heads,
+        SECRET_A,
+    );
+    harness.send(&ixs, &[&a]).await.unwrap();
+
+    // The resolving reveal can't leave the hook out
+    let ixs = Harness::reveal_ix(
+        harness.reveal_accounts(&b.pubkey(), &game, now),
+        CoinSide::Tails,
+        SECRET_B,
+    );
+    let err = harness.send(&ixs, &[&b]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::MissingHookProgram));
+
+    let mut accounts = harness.reveal_accounts(&b.pubkey(), &game, now);
+    accounts.hook_program = Some(hook);
+    accounts.hook_authority = Some(hook_authority());
+    let ixs = Harness::reveal_ix(accounts, CoinSide::Tails, SECRET_B);
+    harness.send(&ixs, &[&b]).await.unwrap();
+    let summary: ResolvedSummary = harness.account(&game_key).await;
+    assert!(summary.winner == a.pubkey() || summary.winner == b.pubkey());
+}
+
+#[tokio::test]
+async fn halted_game_refuses_settlement_until_recovered() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let game_key = harness
+        .committed_game(&players, CoinSide::Heads, CoinSide::Tails)
+        .await;
+    // A stray lamport in the escrow fails the audit on the resolving reveal
+    harness.fund(&escrow_pda(&a.pubkey(), 0), 1).await;
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    for (player, choice, secret) in [
+        (&a, CoinSide::Heads, SECRET_A),
+        (&b, CoinSide::Tails, SECRET_B),
+    ] {
+        let ixs = Harness::reveal_ix(
+            harness.reveal_accounts(&player.pubkey(), &game, now),
+            choice,
+            secret,
+        );
+        harness.send(&ixs, &[player]).await.unwrap();
+    }
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.flag(FLAG_SETTLEMENT_HALTED));
+    assert!(game.status() == GameStatus::RevealingPhase);
+
+    // Nothing pays out of the halted game, even once the escrow is back in balance
+    let escrow = escrow_pda(&a.pubkey(), 0);
+    let mut account = harness
+        .ctx
+        .banks_client
+        .get_account(escrow)
+        .await
+        .unwrap()
+        .unwrap();
+    account.lamports -= 1;
+    harness.ctx.set_account(&escrow, &account.into());
+    let ixs = harness.resolve_manual_ix(&a.pubkey(), &game, now);
+    let err = harness.send(&ixs, &[&a]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::SettlementHalted));
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.coin_result().is_none());
+
+    let before = harness.lamports(&b.pubkey()).await;
+    let authority = harness.authority.insecure_clone();
+    let ix = harness.emergency_recover_ix(&game);
+    harness.send(&[ix], &[&authority]).await.unwrap();
+    assert_eq!(harness.lamports(&b.pubkey()).await, before + BET);
+    let game: Game = harness.account(&game_key).await;
+    assert!(game.status() == GameStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn no_new_games_pause_blocks_every_entry_point_but_not_settlement() {
+    let mut harness = Harness::new().await;
+    let players = harness.new_players().await;
+    let (a, b) = (players.a.insecure_clone(), players.b.insecure_clone());
+    let others = harness.new_players().await;
+    let (c, d) = (others.a.insecure_clone(), others.b.insecure_clone());
+    let late = Keypair::new();
+    harness.fund(&late.pubkey(), PLAYER_FUNDING).await;
+    let operator = Keypair::new();
+    harness.fund(&operator.pubkey(), PLAYER_FUNDING).await;
+    harness.set_operator(&operator.pubkey()).await;
+
+    // Everything already in flight when the pause starts
+    let game_key = harness
+        .committed_game(&players, CoinSide::Heads, CoinSide::Tails)
+        .await;
+    let slot = harness.slot().await;
+    let setup = [
+        (harness.create_game_ix(&c.pubkey(), 0), &c),
+        (harness.subscribe_ix(&d.pubkey(), 1, c.pubkey(), BET), &d),
+        (harness.create_room_ix(&c.pubkey(), 1, SECRET_A), &c),
+        (harness.enter_match_queue_ix(&c.pubkey()), &c),
+        (harness.enter_match_queue_ix(&d.pubkey()), &d),
+        (
+            Harness::schedule_market_ix(&operator.pubkey(), 1, slot + 10, slot + 20),
+            &operator,
+        ),
+        (
+            Harness::schedule_lottery_ix(&operator.pubkey(), 1, SECRET_A, 2, slot + 10, slot + 20),
+            &operator,
+        ),
+    ];
+    for (ix, signer) in setup {
+        harness.send(&[ix], &[signer]).await.unwrap();
+    }
+
+    harness
+        .admin(instruction::SetPauseMode {
+            mode: PauseMode::NoNewGames,
+        })
+        .await
+        .unwrap();
+
+    let blocked = [
+        (harness.create_game_ix(&late.pubkey(), 0), &late),
+        (
+            harness.create_games_batch_ix(&late.pubkey(), 0, &[BET, BET]),
+            &late,
+        ),
+        (harness.join_game_ix(&late.pubkey(), &c.pubkey(), 0), &late),
+        (
+            harness.join_game_lite_ix(&late.pubkey(), &c.pubkey(), 0),
+            &late,
+        ),
+        (
+            harness.join_game_for_subscription_ix(&late.pubkey(), &d.pubkey(), &c.pubkey(), 0),
+            &late,
+        ),
+        (harness.create_room_ix(&late.pubkey(), 1, SECRET_B), &late),
+        (
+            harness.challenge_room_ix(&late.pubkey(), &c.pubkey(), 1, CoinSide::Tails, SECRET_B),
+            &late,
+        ),
+        (harness.enter_match_queue_ix(&late.pubkey()), &late),
+        (
+            Harness::start_bracket_ix(&operator.pubkey(), 1, SECRET_A),
+            &operator,
+        ),
+        (
+            Harness::schedule_market_ix(&operator.pubkey(), 2, slot + 10, slot + 20),
+            &operator,
+        ),
+        (
+            harness.back_market_ix(&late.pubkey(), 1, CoinSide::Heads, BET),
+            &late,
+        ),
+        (
+            Harness::schedule_lottery_ix(&operator.pubkey(), 2, SECRET_A, 2, slot + 10, slot + 20),
+            &operator,
+        ),
+        (harness.buy_ticket_ix(&late.pubkey(), 1, 0), &late),
+    ];
+    for (ix, signer) in blocked {
+        let err = harness.send(&[ix], &[signer]).await.unwrap_err();
+        assert_eq!(custom_error(err), u32::from(GameError::NewGamesPaused));
+    }
+
+    // The game in flight still resolves and queued stakes can still leave
+    let game: Game = harness.account(&game_key).await;
+    let now = harness.now().await;
+    for (player, choice, secret) in [
+        (&a, CoinSide::Heads, SECRET_A),
+        (&b, CoinSide::Tails, SECRET_B),
+    ] {
+        let ixs = Harness::reveal_ix(
+            harness.reveal_accounts(&player.pubkey(), &game, now),
+            choice,
+            secret,
+        );
+        harness.send(&ixs, &[player]).await.unwrap();
+    }
+    let summary: ResolvedSummary = harness.account(&game_key).await;
+    assert_eq!(summary.game_id, game.game_id);
+    let ix = Harness::leave_match_queue_ix(&c.pubkey());
+    harness.send(&[ix], &[&c]).await.unwrap();
+
+    harness
+        .admin(instruction::SetPauseMode {
+            mode: PauseMode::Active,
+        })
+        .await
+        .unwrap();
+    let ix = harness.join_game_ix(&late.pubkey(), &c.pubkey(), 0);
+    harness.send(&[ix], &[&late]).await.unwrap();
+}
+
+#[tokio::test]
+async fn config_change_applies_only_after_its_timelock() {
+    let mut harness = Harness::new().await;
+    let before: GlobalState = harness.account(&global_state()).await;
+    let bps = before.house_fee_bps + 100;
+
+    harness
+        .admin(instruction::ProposeConfigChange {
+            change: ConfigChange::HouseFee { bps },
+        })
+        .await
+        .unwrap();
+    let err = harness
+        .admin(instruction::ProposeConfigChange {
+            change: ConfigChange::HouseFee { bps: bps + 1 },
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::ConfigChangePending));
+
+    let err = harness
+        .admin(instruction::ExecuteConfigChange {})
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::TimelockNotElapsed));
+    let state: GlobalState = harness.account(&global_state()).await;
+    assert_eq!(state.house_fee_bps, before.house_fee_bps);
+
+    harness.advance_clock(before.timelock_delay_seconds).await;
+    harness.warp_slots(1).await;
+    harness
+        .admin(instruction::ExecuteConfigChange {})
+        .await
+        .unwrap();
+    let state: GlobalState = harness.account(&global_state()).await;
+    assert_eq!(state.house_fee_bps, bps);
+    assert!(!state.pending_change.active);
+
+    // A cancelled change never applies, and there is nothing left to cancel
+    harness
+        .admin(instruction::ProposeConfigChange {
+            change: ConfigChange::HouseFee {
+                bps: before.house_fee_bps,
+            },
+        })
+        .await
+        .unwrap();
+    harness
+        .admin(instruction::CancelConfigChange {})
+        .await
+        .unwrap();
+    harness.warp_slots(1).await;
+    let err = harness
+        .admin(instruction::CancelConfigChange {})
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(GameError::NoPendingConfigChange)
+    );
+    harness.advance_clock(before.timelock_delay_seconds).await;
+    let err = harness
+        .admin(instruction::ExecuteConfigChange {})
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(err),
+        u32::from(GameError::NoPendingConfigChange)
+    );
+    let state: GlobalState = harness.account(&global_state()).await;
+    assert_eq!(state.house_fee_bps, bps);
+}
+
+#[tokio::test]
+async fn migration_moves_a_queued_config_change_to_the_end_of_the_layout() {
+    let mut harness = Harness::new().await;
+    harness.set_game_cooldown(CANCEL_TIMEOUT_SECONDS).await;
+    harness.set_golden_flip(10, 500).await;
+
+    // Queuing and clearing a change keeps GlobalState at its full size
+    let full_size = GlobalState::INIT_SPACE;
+    let wallet = Pubkey::new_unique();
+    let change = ConfigChange::HouseWallet { wallet };
+    harness
+        .admin(instruction::ProposeConfigChange {
+            change: ConfigChange::HouseFee { bps: 100 },
+        })
+        .await
+        .unwrap();
+    let state: GlobalState = harness.account(&global_state()).await;
+    assert_eq!(state.try_to_vec().unwrap().len(), full_size);
+    harness
+        .admin(instruction::CancelConfigChange {})
+        .await
+        .unwrap();
+    let state: GlobalState = harness.account(&global_state()).await;
+    assert!(!state.pending_change.active);
+    assert_eq!(state.try_to_vec().unwrap().len(), full_size);
+
+    // Rewrite the account in the v15 layout, with the change queued in the old
+    // Option mid-layout and without the v16 fixed-size slot at the end
+    let eta = harness.now().await + state.timelock_delay_seconds;
+    let mut state: GlobalState = harness.account(&global_state()).await;
+    let mut account = harness
+        .ctx
+        .banks_client
+        .get_account(global_state())
+        .await
+        .unwrap()
+        .unwrap();
+    state.version = 15;
+    let mut current = Vec::new();
+    state.try_serialize(&mut current).unwrap();
+    // Discriminator, four pubkeys and five 8-byte fields come before the old slot
+    let slot = 8 + 4 * 32 + 5 * 8;
+    let mut data = current[..slot].to_vec();
+    data.push(1);
+    data.extend_from_slice(&change.try_to_vec().unwrap());
+    data.extend_from_slice(&eta.to_le_bytes());
+    data.extend_from_slice(&current[slot + 1..current.len() - PendingConfigChange::INIT_SPACE]);
+    data.resize(account.data.len() - 1, 0);
+    account.data = data;
+    harness.ctx.set_account(&global_state(), &account.into());
+
+    let ix = harness.migrate_global_state_ix();
+    let authority = harness.authority.insecure_clone();
+    harness.send(&[ix], &[&authority]).await.unwrap();
+
+    let migrated: GlobalState = harness.account(&global_state()).await;
+    assert_eq!(migrated.version, 16);
+    assert_eq!(migrated.retired_pending_change, 0);
+    assert!(migrated.pending_change.active);
+    assert!(migrated.pending_change.change == change);
+    assert_eq!(migrated.pending_change.eta, eta);
+    assert_eq!(migrated.game_cooldown_seconds, CANCEL_TIMEOUT_SECONDS);
+    assert_eq!(
+        (migrated.golden_flip_odds, migrated.golden_flip_bonus_bps),
+        (10, 500)
+    );
+    assert_eq!(
+        harness.data_len(&global_state()).await,
+        8 + GlobalState::INIT_SPACE
+    );
+
+    // The moved change still runs through the timelock
+    harness.advance_clock(state.timelock_delay_seconds).await;
+    harness
+        .admin(instruction::ExecuteConfigChange {})
+        .await
+        .unwrap();
+    let state: GlobalState = harness.account(&global_state()).await;
+    assert_eq!(state.house_wallet, wallet);
+    assert!(!state.pending_change.active);
+
+    // Already current; a fresh blockhash keeps the repeat from being dropped as a duplicate
+    harness.warp_slots(1).await;
+    let ix = harness.migrate_global_state_ix();
+    let err = harness.send(&[ix], &[&authority]).await.unwrap_err();
+    assert_eq!(custom_error(err), u32::from(GameError::AlreadyMigrated));
+}
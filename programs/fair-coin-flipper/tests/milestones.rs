@@ -0,0 +1,19 @@
+use fair_coin_flipper::{crossed_milestone, MILESTONE_GAMES_INTERVAL, MILESTONE_VOLUME_INTERVAL};
+
+#[test]
+fn milestone_fires_once_when_the_interval_is_crossed() {
+    let interval = MILESTONE_GAMES_INTERVAL;
+    assert_eq!(crossed_milestone(998, 999, interval), None);
+    assert_eq!(crossed_milestone(999, 1_000, interval), Some(1_000));
+    assert_eq!(crossed_milestone(1_000, 1_001, interval), None);
+    assert_eq!(crossed_milestone(0, 0, interval), None);
+}
+
+#[test]
+fn large_pot_reports_the_highest_threshold_crossed() {
+    let interval = MILESTONE_VOLUME_INTERVAL;
+    assert_eq!(
+        crossed_milestone(interval - 1, 3 * interval + 5, interval),
+        Some(3 * interval)
+    );
+}
@@ -0,0 +1,35 @@
+use fair_coin_flipper::{expected_escrow_balance, is_settled, GameStatus};
+
+#[test]
+fn only_terminal_statuses_are_settled() {
+    for (status, settled) in [
+        (GameStatus::WaitingForPlayer, false),
+        (GameStatus::PlayersReady, false),
+        (GameStatus::CommitmentsReady, false),
+        (GameStatus::RevealingPhase, false),
+        (GameStatus::Resolved, true),
+        (GameStatus::Cancelled, true),
+    ] {
+        assert_eq!(is_settled(&status), settled);
+    }
+}
+
+#[test]
+fn expected_escrow_balance_follows_status() {
+    let bet = 100_000_000_000;
+    let reserve = 890_880;
+
+    for (status, expected) in [
+        (GameStatus::WaitingForPlayer, bet + reserve),
+        (GameStatus::PlayersReady, 2 * bet + reserve),
+        (GameStatus::CommitmentsReady, 2 * bet + reserve),
+        (GameStatus::RevealingPhase, 2 * bet + reserve),
+        (GameStatus::Resolved, 0),
+        (GameStatus::Cancelled, 0),
+    ] {
+        assert_eq!(
+            expected_escrow_balance(&status, bet, reserve).unwrap(),
+            expected
+        );
+    }
+}
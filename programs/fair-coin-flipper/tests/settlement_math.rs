@@ -0,0 +1,81 @@
+use fair_coin_flipper::{
+    bout_amounts, bps_of, cancellation_amounts, market_payout, settlement_amounts,
+};
+
+const MAX_BET_AMOUNT: u64 = 100_000_000_000;
+const MAX_HOUSE_FEE_BPS: u64 = 1000;
+const MAX_CANCELLATION_FEE_BPS: u64 = 500;
+
+#[test]
+fn settlement_at_max_bet_and_max_fee() {
+    let (total_pot, house_fee, winner_payout) =
+        settlement_amounts(MAX_BET_AMOUNT, MAX_HOUSE_FEE_BPS).unwrap();
+
+    assert_eq!(total_pot, 200_000_000_000);
+    assert_eq!(house_fee, 20_000_000_000);
+    assert_eq!(winner_payout, 180_000_000_000);
+}
+
+#[test]
+fn settlement_rejects_pot_overflow() {
+    assert!(settlement_amounts(u64::MAX / 2 + 1, MAX_HOUSE_FEE_BPS).is_err());
+}
+
+#[test]
+fn settlement_handles_largest_representable_pot() {
+    let (total_pot, house_fee, winner_payout) =
+        settlement_amounts(u64::MAX / 2, MAX_HOUSE_FEE_BPS).unwrap();
+
+    assert_eq!(house_fee + winner_payout, total_pot);
+}
+
+#[test]
+fn cancellation_at_max_bet_and_max_fee() {
+    let (fee, refund) = cancellation_amounts(MAX_BET_AMOUNT, MAX_CANCELLATION_FEE_BPS).unwrap();
+
+    assert_eq!(fee, 5_000_000_000);
+    assert_eq!(refund, 95_000_000_000);
+}
+
+#[test]
+fn bps_of_rejects_shares_above_the_amount() {
+    assert_eq!(bps_of(u64::MAX, 10000).unwrap(), u64::MAX);
+    assert!(bps_of(u64::MAX, 10001).is_err());
+}
+
+#[test]
+fn market_payout_splits_the_pool_pro_rata() {
+    // 3 SOL on the winning side, 1 SOL on the losing side, 10% fee
+    let payout_pool = 4_000_000_000 - bps_of(4_000_000_000, MAX_HOUSE_FEE_BPS).unwrap();
+    let stakes = [1_000_000_000, 2_000_000_000];
+    let payouts: Vec<u64> = stakes
+        .iter()
+        .map(|&stake| market_payout(stake, 3_000_000_000, payout_pool).unwrap())
+        .collect();
+
+    assert_eq!(payouts, [1_200_000_000, 2_400_000_000]);
+    assert_eq!(payouts.iter().sum::<u64>(), payout_pool);
+}
+
+#[test]
+fn market_payout_rounds_down_and_never_overpays() {
+    let payouts: Vec<u64> = (0..3).map(|_| market_payout(1, 3, 10).unwrap()).collect();
+    assert_eq!(payouts, [3, 3, 3]);
+
+    // A whole side's stake at the largest pool doesn't overflow
+    assert_eq!(
+        market_payout(u64::MAX, u64::MAX, u64::MAX).unwrap(),
+        u64::MAX
+    );
+    assert!(market_payout(1, 0, 10).is_err());
+}
+
+#[test]
+fn bout_pays_the_losing_stake_less_the_fee() {
+    let (house_fee, winnings) = bout_amounts(MAX_BET_AMOUNT, MAX_HOUSE_FEE_BPS).unwrap();
+
+    // The same fee as a game on both stakes; the winner keeps their own stake
+    assert_eq!(house_fee, 20_000_000_000);
+    assert_eq!(winnings, 80_000_000_000);
+    assert_eq!(MAX_BET_AMOUNT + house_fee + winnings, 2 * MAX_BET_AMOUNT);
+}
@@ -0,0 +1,57 @@
+use fair_coin_flipper::transitions::{advance, GameEvent};
+use fair_coin_flipper::GameStatus;
+
+const STATUSES: [GameStatus; 6] = [
+    GameStatus::WaitingForPlayer,
+    GameStatus::PlayersReady,
+    GameStatus::CommitmentsReady,
+    GameStatus::RevealingPhase,
+    GameStatus::Resolved,
+    GameStatus::Cancelled,
+];
+
+const EVENTS: [GameEvent; 6] = [
+    GameEvent::Join,
+    GameEvent::Commit,
+    GameEvent::CommitmentsComplete,
+    GameEvent::Reveal,
+    GameEvent::Resolve,
+    GameEvent::Cancel,
+];
+
+fn expected(status: &GameStatus, event: GameEvent) -> Option<GameStatus> {
+    use GameStatus::*;
+    match (status, event) {
+        (WaitingForPlayer, GameEvent::Join) => Some(PlayersReady),
+        (PlayersReady, GameEvent::Commit) => Some(PlayersReady),
+        (PlayersReady, GameEvent::CommitmentsComplete) => Some(CommitmentsReady),
+        (CommitmentsReady, GameEvent::Reveal) => Some(RevealingPhase),
+        (RevealingPhase, GameEvent::Reveal) => Some(RevealingPhase),
+        (RevealingPhase, GameEvent::Resolve) => Some(Resolved),
+        (Resolved | Cancelled, GameEvent::Cancel) => None,
+        (_, GameEvent::Cancel) => Some(Cancelled),
+        _ => None,
+    }
+}
+
+#[test]
+fn every_status_and_event_pair_matches_the_table() {
+    for status in STATUSES.iter() {
+        for event in EVENTS {
+            match (advance(status, event), expected(status, event)) {
+                (Ok(next), Some(want)) => assert!(next == want),
+                (Err(_), None) => {}
+                _ => panic!("unexpected transition result"),
+            }
+        }
+    }
+}
+
+#[test]
+fn settled_games_accept_no_events() {
+    for status in [GameStatus::Resolved, GameStatus::Cancelled] {
+        for event in EVENTS {
+            assert!(advance(&status, event).is_err());
+        }
+    }
+}